@@ -0,0 +1,88 @@
+//! Benchmarks for status queries against a manager with hundreds of processes.
+//!
+//! Run with `cargo bench --bench manager`. Spawns real `sleep` processes, so it is
+//! Unix only.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use pmdaemon::{ProcessConfig, ProcessManager};
+use std::sync::Arc;
+use tempfile::TempDir;
+use tokio::runtime::Runtime;
+
+/// Number of processes managed during the benchmarks
+const PROCESSES: usize = 200;
+
+fn sleeper(name: &str) -> ProcessConfig {
+    ProcessConfig::builder()
+        .name(name)
+        .script("sleep")
+        .args(vec!["300"])
+        .build()
+        .unwrap()
+}
+
+/// Manager with [`PROCESSES`] running processes named `worker-0`, `worker-1`, ...
+fn populated_manager(rt: &Runtime) -> (Arc<ProcessManager>, TempDir) {
+    let temp_dir = TempDir::new().unwrap();
+    let manager = rt.block_on(async {
+        let manager = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        for i in 0..PROCESSES {
+            manager
+                .start(sleeper(&format!("worker-{i}")))
+                .await
+                .unwrap();
+        }
+        manager
+    });
+    (Arc::new(manager), temp_dir)
+}
+
+fn status_queries(c: &mut Criterion) {
+    let rt = Runtime::new().unwrap();
+    let (manager, _temp_dir) = populated_manager(&rt);
+
+    let mut group = c.benchmark_group("status");
+    group.bench_function(BenchmarkId::new("list", PROCESSES), |b| {
+        b.iter(|| rt.block_on(manager.list()).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("get_process_info", PROCESSES), |b| {
+        b.iter(|| rt.block_on(manager.get_process_info("worker-100")).unwrap())
+    });
+
+    // A process that ignores SIGTERM is stopped and started over and over, so every
+    // stop waits out the kill timeout while the queries run
+    let stubborn = ProcessConfig::builder()
+        .name("stubborn")
+        .script("sh")
+        .args(vec!["-c", "trap '' TERM; sleep 300"])
+        .kill_timeout(1000)
+        .build()
+        .unwrap();
+    rt.block_on(manager.start(stubborn)).unwrap();
+    let churn = {
+        let manager = Arc::clone(&manager);
+        rt.spawn(async move {
+            loop {
+                let _ = manager.stop("stubborn").await;
+                let _ = manager.restart("stubborn").await;
+            }
+        })
+    };
+
+    group.bench_function(BenchmarkId::new("list_during_slow_stop", PROCESSES), |b| {
+        b.iter(|| rt.block_on(manager.list()).unwrap())
+    });
+    group.bench_function(
+        BenchmarkId::new("get_process_info_during_slow_stop", PROCESSES),
+        |b| b.iter(|| rt.block_on(manager.get_process_info("worker-100")).unwrap()),
+    );
+    group.finish();
+
+    churn.abort();
+    rt.block_on(manager.delete_all()).unwrap();
+}
+
+criterion_group!(benches, status_queries);
+criterion_main!(benches);
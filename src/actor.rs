@@ -0,0 +1,234 @@
+//! Per-process actors.
+//!
+//! Every managed [`Process`] is owned by its own task. The [`ProcessManager`] keeps a
+//! [`ProcessHandle`] per process and routes operations to it as commands: closures
+//! that the actor runs against its process one at a time. Slow operations such as a
+//! graceful stop waiting out `kill_timeout` therefore only queue up commands for that
+//! one process, while every other process keeps being served.
+//!
+//! After each command the actor publishes a fresh snapshot of the process' status and
+//! configuration, which [`ProcessHandle::status`] and [`ProcessHandle::config`] return
+//! without waiting for the actor, so status queries are answered even while the
+//! process is busy.
+//!
+//! [`ProcessManager`]: crate::manager::ProcessManager
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmdaemon::actor::ProcessHandle;
+//! use pmdaemon::{Process, ProcessConfig, ProcessState};
+//!
+//! # async fn example() -> pmdaemon::Result<()> {
+//! let config = ProcessConfig::builder()
+//!     .name("worker")
+//!     .script("sleep")
+//!     .args(vec!["30"])
+//!     .build()?;
+//! let handle = ProcessHandle::spawn(Process::new(config));
+//!
+//! handle.call(|process| Box::pin(process.start())).await??;
+//! assert_eq!(handle.status().state, ProcessState::Online);
+//!
+//! let pid = handle.with(|process| process.pid()).await?;
+//! assert!(pid.is_some());
+//! handle.call(|process| Box::pin(process.stop())).await??;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::ProcessConfig;
+use crate::error::{Error, Result};
+use crate::process::{Process, ProcessId, ProcessStatus};
+use std::future::Future;
+use std::pin::Pin;
+use tokio::sync::{mpsc, oneshot, watch};
+
+/// Future returned by the closures passed to [`ProcessHandle::call`]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Operation run by a process actor against the process it owns. It is handed the
+/// snapshot sender, so that it can publish the new state before replying.
+type Command = Box<
+    dyn for<'a> FnOnce(&'a mut Process, &'a watch::Sender<Snapshot>) -> BoxFuture<'a, ()> + Send,
+>;
+
+/// State published by a process actor after every command
+#[derive(Debug, Clone)]
+struct Snapshot {
+    status: ProcessStatus,
+    config: ProcessConfig,
+}
+
+impl Snapshot {
+    fn of(process: &Process) -> Self {
+        Self {
+            status: process.status(),
+            config: process.config.clone(),
+        }
+    }
+}
+
+/// Handle to the actor task that owns a process.
+///
+/// Handles are cheap to clone. The actor runs until the last handle is dropped and
+/// then drops the process, which leaves a still running child alone.
+#[derive(Debug, Clone)]
+pub struct ProcessHandle {
+    /// ID of the process owned by the actor
+    id: ProcessId,
+    /// Queue of commands for the actor
+    commands: mpsc::UnboundedSender<Command>,
+    /// State published by the actor after every command
+    snapshot: watch::Receiver<Snapshot>,
+}
+
+impl ProcessHandle {
+    /// Move `process` into a new actor task
+    pub fn spawn(mut process: Process) -> Self {
+        let id = process.id;
+        let (commands, mut queue) = mpsc::unbounded_channel::<Command>();
+        let (snapshot_tx, snapshot) = watch::channel(Snapshot::of(&process));
+
+        tokio::spawn(async move {
+            while let Some(command) = queue.recv().await {
+                command(&mut process, &snapshot_tx).await;
+            }
+        });
+
+        Self {
+            id,
+            commands,
+            snapshot,
+        }
+    }
+
+    /// ID of the process owned by the actor
+    pub fn id(&self) -> ProcessId {
+        self.id
+    }
+
+    /// Status of the process as of the last completed command
+    pub fn status(&self) -> ProcessStatus {
+        self.snapshot.borrow().status.clone()
+    }
+
+    /// Configuration of the process as of the last completed command
+    pub fn config(&self) -> ProcessConfig {
+        self.snapshot.borrow().config.clone()
+    }
+
+    /// Run an async operation on the process and return its result.
+    ///
+    /// Operations on the same process run one after another, in the order they were
+    /// sent. The snapshot returned by [`status`](Self::status) and
+    /// [`config`](Self::config) already reflects the operation when this returns.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProcessNotFound`] if the actor has shut down.
+    pub async fn call<R, F>(&self, operation: F) -> Result<R>
+    where
+        F: for<'a> FnOnce(&'a mut Process) -> BoxFuture<'a, R> + Send + 'static,
+        R: Send + 'static,
+    {
+        let (reply, result) = oneshot::channel();
+        let command: Command = Box::new(move |process, snapshot| {
+            Box::pin(async move {
+                let output = operation(process).await;
+                snapshot.send_replace(Snapshot::of(process));
+                let _ = reply.send(output);
+            })
+        });
+        self.commands
+            .send(command)
+            .map_err(|_| Error::process_not_found(self.id.to_string()))?;
+        result
+            .await
+            .map_err(|_| Error::process_not_found(self.id.to_string()))
+    }
+
+    /// Run a synchronous operation on the process and return its result
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProcessNotFound`] if the actor has shut down.
+    pub async fn with<R, F>(&self, operation: F) -> Result<R>
+    where
+        F: FnOnce(&mut Process) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        self.call(move |process| {
+            let result = operation(process);
+            Box::pin(async move { result })
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::ProcessState;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn handle(name: &str, script: &str, args: Vec<&str>) -> ProcessHandle {
+        let config = ProcessConfig::builder()
+            .name(name)
+            .script(script)
+            .args(args)
+            .build()
+            .unwrap();
+        ProcessHandle::spawn(Process::new(config))
+    }
+
+    #[tokio::test]
+    async fn test_commands_run_in_order() {
+        let handle = handle("ordered", "echo", vec![]);
+        let first = handle.with(|process| {
+            process.restarts += 1;
+            process.restarts
+        });
+        let second = handle.with(|process| {
+            process.restarts += 1;
+            process.restarts
+        });
+        assert_eq!((first.await.unwrap(), second.await.unwrap()), (1, 2));
+        assert_eq!(handle.status().restarts, 2);
+        assert_eq!(handle.status().id, handle.id());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_status_while_busy() {
+        // Ignores SIGTERM, so stopping it waits out the kill timeout
+        let stubborn = handle("stubborn", "sh", vec!["-c", "trap '' TERM; sleep 30"]);
+        stubborn
+            .with(|process| process.config.kill_timeout = 500)
+            .await
+            .unwrap();
+        stubborn
+            .call(|process| Box::pin(process.start()))
+            .await
+            .unwrap()
+            .unwrap();
+        let other = handle("other", "echo", vec![]);
+
+        let stopping = {
+            let stubborn = stubborn.clone();
+            tokio::spawn(async move { stubborn.call(|process| Box::pin(process.stop())).await })
+        };
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // Neither the snapshot nor other actors wait for the stop
+        let started = std::time::Instant::now();
+        assert_eq!(stubborn.status().state, ProcessState::Online);
+        other.with(|process| process.restarts).await.unwrap();
+        assert!(started.elapsed() < Duration::from_millis(200));
+
+        let report = stopping.await.unwrap().unwrap().unwrap();
+        assert!(report.forced);
+        assert_eq!(stubborn.status().state, ProcessState::Stopped);
+    }
+}
@@ -6,7 +6,16 @@ use chrono::{Local, Utc};
 use clap::{Parser, Subcommand};
 use comfy_table::{Attribute, Cell, Color, Table};
 use pmdaemon::config::format_memory;
-use pmdaemon::{EcosystemConfig, ProcessConfig, ProcessManager, Result};
+use pmdaemon::daemon::{Daemon, DaemonClient};
+use pmdaemon::health::{HealthCheckConfig, HealthState, HealthStatus};
+use pmdaemon::logs::{
+    humanize_json_line, LogEntry, LogFormat, LogLine, LogRotationConfig, LogSearch, LogShim,
+    LogStream,
+};
+use pmdaemon::{
+    EcosystemConfig, ProcessConfig, ProcessId, ProcessManager, ProcessState, ProcessStatus,
+    RestartReason, Result,
+};
 use rand::Rng;
 use std::path::PathBuf;
 use tracing::{error, info};
@@ -28,6 +37,8 @@ struct Cli {
     config: Option<PathBuf>,
 }
 
+// Parsed once at startup, so the size of the `Start` variant does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Start a process
@@ -59,6 +70,138 @@ enum Commands {
         #[arg(short, long)]
         port: Option<String>,
 
+        /// Restart the process when files in its working directory change
+        #[arg(long)]
+        watch: bool,
+
+        /// Glob patterns to ignore when watching (e.g., "node_modules", "*.log")
+        #[arg(long)]
+        ignore_watch: Vec<String>,
+
+        /// Quiet period in milliseconds before restarting after file changes
+        #[arg(long)]
+        watch_delay: Option<u64>,
+
+        /// User to run the process as (requires root)
+        #[arg(long)]
+        user: Option<String>,
+
+        /// Group to run the process as (requires root)
+        #[arg(long)]
+        group: Option<String>,
+
+        /// HTTP endpoint to check for health (2xx responses are healthy)
+        #[arg(long, conflicts_with = "health_check_script")]
+        health_check_url: Option<String>,
+
+        /// Script to run for health checks (exit code 0 is healthy)
+        #[arg(long)]
+        health_check_script: Option<PathBuf>,
+
+        /// Timeout for each health check (e.g., 5s, 500ms)
+        #[arg(long)]
+        health_check_timeout: Option<String>,
+
+        /// Interval between health checks (e.g., 30s, 1m)
+        #[arg(long)]
+        health_check_interval: Option<String>,
+
+        /// Consecutive failed checks before the process is unhealthy
+        #[arg(long)]
+        health_check_retries: Option<u32>,
+
+        /// Action when the process becomes unhealthy (restart, alert, stop)
+        #[arg(long, default_value = "restart")]
+        on_unhealthy: String,
+
+        /// Wait until the health check passes before returning (exits nonzero on failure)
+        #[arg(long)]
+        wait_ready: bool,
+
+        /// Maximum time to wait for the process to become healthy (e.g., 30s, 2m)
+        #[arg(long, requires = "wait_ready")]
+        wait_timeout: Option<String>,
+
+        /// Consecutive restarts within --min-uptime before the process is marked errored
+        #[arg(long)]
+        max_restarts: Option<u32>,
+
+        /// Minimum uptime for a run to count as stable (e.g., 5s, 500ms)
+        #[arg(long)]
+        min_uptime: Option<String>,
+
+        /// Delay between an exit and the automatic restart (e.g., 1s, 500ms)
+        #[arg(long)]
+        restart_delay: Option<String>,
+
+        /// Restart with an exponential backoff starting at this delay (e.g., 100ms)
+        #[arg(long, conflicts_with = "restart_delay")]
+        exp_backoff_restart_delay: Option<String>,
+
+        /// Upper bound of the exponential backoff restart delay (e.g., 1m; default: 15s)
+        #[arg(long, requires = "exp_backoff_restart_delay")]
+        max_exp_backoff_restart_delay: Option<String>,
+
+        /// When to restart after an exit (always, on-failure, on-abnormal, never)
+        #[arg(long)]
+        restart_mode: Option<String>,
+
+        /// Exit codes that count as a clean stop and never trigger a restart (e.g., 0,3)
+        #[arg(long, value_delimiter = ',')]
+        stop_exit_codes: Vec<i32>,
+
+        /// Time to wait for a graceful stop before force killing (e.g., 30s, default: 1600ms)
+        #[arg(long)]
+        kill_timeout: Option<String>,
+
+        /// Signal sent to stop the process (e.g., QUIT, default: TERM)
+        #[arg(long)]
+        kill_signal: Option<String>,
+
+        /// Signals to escalate through when stopping, as SIGNAL[:TIMEOUT] (e.g., TERM:5s,INT,KILL)
+        #[arg(long, value_delimiter = ',', conflicts_with = "kill_signal")]
+        kill_chain: Vec<String>,
+
+        /// Stdout log file (default: ~/.pmdaemon/logs/<name>-out.log)
+        #[arg(long)]
+        out_file: Option<PathBuf>,
+
+        /// Stderr log file (default: ~/.pmdaemon/logs/<name>-error.log)
+        #[arg(long)]
+        error_file: Option<PathBuf>,
+
+        /// Combined stdout and stderr log file (default: ~/.pmdaemon/logs/<name>.log)
+        #[arg(long)]
+        log_file: Option<PathBuf>,
+
+        /// PID file (default: ~/.pmdaemon/pids/<name>.pid)
+        #[arg(long)]
+        pid_file: Option<PathBuf>,
+
+        /// Rotate log files once they reach this size (e.g., 10M, 1G)
+        #[arg(long)]
+        log_max_size: Option<String>,
+
+        /// Rotate log files at the start of every period (hourly, daily, weekly)
+        #[arg(long)]
+        log_rotate_interval: Option<String>,
+
+        /// Number of rotated log files to keep per log (default: 10)
+        #[arg(long)]
+        log_retain: Option<usize>,
+
+        /// Compress rotated log files with gzip
+        #[arg(long)]
+        log_compress: bool,
+
+        /// Prefix each log line with a timestamp in this format (e.g., "%Y-%m-%d %H:%M:%S")
+        #[arg(long)]
+        log_date_format: Option<String>,
+
+        /// Format of log lines: text (default) or json, one JSON object per line
+        #[arg(long)]
+        log_format: Option<String>,
+
         /// Command line arguments
         #[arg(last = true)]
         args: Vec<String>,
@@ -78,6 +221,23 @@ enum Commands {
         /// Port or port range (overrides config default)
         #[arg(short, long)]
         port: Option<String>,
+
+        /// Wait until the health check passes before returning (exits nonzero on failure)
+        #[arg(long)]
+        wait_ready: bool,
+    },
+
+    /// Send a signal to a process, every instance of a cluster, or a namespace
+    Signal {
+        /// Process name or ID, cluster name, or namespace (with --namespace)
+        identifier: String,
+
+        /// Signal name or number (e.g., HUP, SIGUSR2, WINCH, 10)
+        signal: String,
+
+        /// Signal every process in the namespace named by the identifier
+        #[arg(short, long)]
+        namespace: bool,
     },
 
     /// Reload a process (graceful restart)
@@ -116,7 +276,8 @@ enum Commands {
 
     /// Show process logs
     Logs {
-        /// Process name or ID
+        /// Process name or ID, cluster name, namespace or glob such as "api-*" (all
+        /// processes when omitted)
         identifier: Option<String>,
 
         /// Number of lines to show
@@ -126,6 +287,32 @@ enum Commands {
         /// Follow log output
         #[arg(short, long)]
         follow: bool,
+
+        /// Only show output (stdout) lines
+        #[arg(long, conflicts_with = "err")]
+        out: bool,
+
+        /// Only show error (stderr) lines
+        #[arg(long)]
+        err: bool,
+
+        /// Show all lines since a time instead of the last lines (e.g., 10m, 2h or
+        /// 2025-06-01T12:00:00Z); needs timestamped logs
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Show all lines up to a time (e.g., 5m or 2025-06-01T12:00:00Z); needs
+        /// timestamped logs
+        #[arg(long)]
+        until: Option<String>,
+
+        /// Only show lines matching a regular expression
+        #[arg(long)]
+        grep: Option<String>,
+
+        /// Only show lines of at least this level (trace, debug, info, warn, error, fatal)
+        #[arg(long)]
+        level: Option<String>,
     },
 
     /// Show process information
@@ -134,6 +321,30 @@ enum Commands {
         identifier: String,
     },
 
+    /// Show lifecycle events (starts, exits, restarts, health changes, ...)
+    Events {
+        /// Process name or ID, or cluster name (all processes when omitted)
+        identifier: Option<String>,
+
+        /// Only show events since a time (e.g., 30m, 2h, 7d or 2025-06-01T12:00:00Z)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Maximum number of events to show
+        #[arg(short, long, default_value = "50")]
+        lines: usize,
+    },
+
+    /// List crash records, or show one with its log tails and configuration
+    Crashes {
+        /// Process name or ID, or cluster name (all processes when omitted)
+        identifier: Option<String>,
+
+        /// Show the crash record with this ID
+        #[arg(long)]
+        show: Option<String>,
+    },
+
     /// Start web monitoring server
     Web {
         /// Port to bind to
@@ -149,17 +360,38 @@ enum Commands {
         api_key: Option<String>,
     },
 
+    /// Run the supervisor daemon in the foreground
+    ///
+    /// While the daemon is running, the process commands (`start`, `stop`,
+    /// `restart`, `reload`, `delete`, `signal`, `list`, `info`, `monit`, `logs`,
+    /// `events` and `crashes`) are forwarded to it over a local socket instead of
+    /// loading the processes in the CLI. `web` always runs its own process manager.
+    Daemon,
+
     /// Regenerate the API key
     RegenerateApiKey,
 
     /// Show the current API key
     ShowApiKey,
+
+    /// Pump the output of a managed process into its logs (started by pmdaemon)
+    #[command(hide = true)]
+    LogShim {
+        /// JSON-encoded shim arguments
+        spec: String,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    // Log shims only pump output, without loading any state
+    if let Commands::LogShim { spec } = &cli.command {
+        let shim: LogShim = serde_json::from_str(spec)?;
+        return shim.run().await;
+    }
+
     // Initialize logging
     let log_level = if cli.verbose { "debug" } else { "info" };
     tracing_subscriber::fmt()
@@ -168,8 +400,13 @@ async fn main() -> Result<()> {
 
     info!("PMDaemon v{} starting", pmdaemon::VERSION);
 
-    // Initialize process manager
-    let mut manager = ProcessManager::new().await?;
+    // Forward commands to a running daemon, if any; the process manager is only
+    // loaded in-process when no daemon answers, so that it never runs next to one
+    let mut daemon = match cli.command {
+        Commands::Daemon | Commands::Web { .. } => None,
+        _ => DaemonClient::connect_default().await?,
+    };
+    let mut manager = None;
 
     match cli.command {
         Commands::Start {
@@ -180,17 +417,71 @@ async fn main() -> Result<()> {
             env,
             max_memory,
             port,
+            watch,
+            ignore_watch,
+            watch_delay,
+            user,
+            group,
+            health_check_url,
+            health_check_script,
+            health_check_timeout,
+            health_check_interval,
+            health_check_retries,
+            on_unhealthy,
+            wait_ready,
+            wait_timeout,
+            max_restarts,
+            min_uptime,
+            restart_delay,
+            exp_backoff_restart_delay,
+            max_exp_backoff_restart_delay,
+            restart_mode,
+            stop_exit_codes,
+            kill_timeout,
+            kill_signal,
+            kill_chain,
+            out_file,
+            error_file,
+            log_file,
+            pid_file,
+            log_max_size,
+            log_rotate_interval,
+            log_retain,
+            log_compress,
+            log_date_format,
+            log_format,
             args,
         } => {
+            let wait_timeout = wait_timeout
+                .map(|timeout| pmdaemon::config::parse_duration_string(&timeout))
+                .transpose()?;
+
             // Handle config file vs CLI arguments
             if let Some(config_path) = &cli.config {
                 // Load from config file
-                let ecosystem = EcosystemConfig::from_file(config_path).await?;
+                let mut ecosystem = EcosystemConfig::from_file(config_path).await?;
+
+                // `--wait-ready` applies to every app that has a health check to wait for
+                if wait_ready {
+                    for app_config in ecosystem.apps.iter_mut() {
+                        if app_config
+                            .health_check
+                            .as_ref()
+                            .is_some_and(|health_check| health_check.enabled)
+                        {
+                            app_config.wait_ready = true;
+                            if let Some(timeout) = wait_timeout {
+                                app_config.wait_timeout = timeout.as_millis() as u64;
+                            }
+                        }
+                    }
+                }
 
                 if let Some(app_name) = &name {
                     // Start specific app from config
                     if let Some(app_config) = ecosystem.get_app(app_name) {
-                        let process_id = manager.start(app_config.clone()).await?;
+                        let process_id =
+                            start_process(&mut manager, &mut daemon, app_config.clone()).await?;
                         println!(
                             "Started process '{}' from config file with ID: {}",
                             app_name, process_id
@@ -206,8 +497,9 @@ async fn main() -> Result<()> {
                 } else if script.is_none() {
                     // Start all apps from config
                     let mut started_count = 0;
+                    let mut failed_count = 0;
                     for app_config in &ecosystem.apps {
-                        match manager.start(app_config.clone()).await {
+                        match start_process(&mut manager, &mut daemon, app_config.clone()).await {
                             Ok(process_id) => {
                                 println!(
                                     "Started process '{}' with ID: {}",
@@ -217,10 +509,14 @@ async fn main() -> Result<()> {
                             }
                             Err(e) => {
                                 error!("Failed to start process '{}': {}", app_config.name, e);
+                                failed_count += 1;
                             }
                         }
                     }
                     println!("Started {} processes from config file", started_count);
+                    if failed_count > 0 {
+                        std::process::exit(1);
+                    }
                 } else {
                     error!("Cannot specify both config file and script. Use config file OR CLI arguments, not both.");
                     std::process::exit(1);
@@ -249,6 +545,156 @@ async fn main() -> Result<()> {
                     config_builder = config_builder.cwd(cwd);
                 }
 
+                if let Some(user) = user {
+                    config_builder = config_builder.user(user);
+                }
+                if let Some(group) = group {
+                    config_builder = config_builder.group(group);
+                }
+
+                // Health checks
+                let health_check = match (health_check_url, health_check_script) {
+                    (Some(url), _) => Some(HealthCheckConfig::http(url)),
+                    (None, Some(script)) => Some(HealthCheckConfig::script(script)),
+                    (None, None) => None,
+                };
+                if let Some(mut health_check) = health_check {
+                    if let Some(timeout) = health_check_timeout {
+                        health_check = health_check
+                            .timeout(pmdaemon::config::parse_duration_string(&timeout)?);
+                    }
+                    if let Some(interval) = health_check_interval {
+                        health_check = health_check
+                            .interval(pmdaemon::config::parse_duration_string(&interval)?);
+                    }
+                    if let Some(retries) = health_check_retries {
+                        health_check = health_check.retries(retries);
+                    }
+                    config_builder = config_builder.health_check(
+                        health_check
+                            .on_unhealthy(on_unhealthy.parse()?)
+                            .enabled(true),
+                    );
+                    if daemon.is_none() {
+                        println!("Note: health checks run only while `pmdaemon daemon` is running");
+                    }
+                }
+
+                if wait_ready {
+                    config_builder = config_builder.wait_ready(true);
+                    if let Some(timeout) = wait_timeout {
+                        config_builder = config_builder.wait_timeout(timeout.as_millis() as u64);
+                    }
+                }
+
+                // Restart policy
+                if let Some(max_restarts) = max_restarts {
+                    config_builder = config_builder.max_restarts(max_restarts);
+                }
+                if let Some(min_uptime) = min_uptime {
+                    let min_uptime = pmdaemon::config::parse_duration_string(&min_uptime)?;
+                    config_builder = config_builder.min_uptime(min_uptime.as_millis() as u64);
+                }
+                if let Some(delay) = restart_delay {
+                    let delay = pmdaemon::config::parse_duration_string(&delay)?;
+                    config_builder = config_builder.restart_delay(delay.as_millis() as u64);
+                }
+                if let Some(delay) = exp_backoff_restart_delay {
+                    let delay = pmdaemon::config::parse_duration_string(&delay)?;
+                    config_builder =
+                        config_builder.exp_backoff_restart_delay(delay.as_millis() as u64);
+                }
+                if let Some(delay) = max_exp_backoff_restart_delay {
+                    let delay = pmdaemon::config::parse_duration_string(&delay)?;
+                    config_builder =
+                        config_builder.max_exp_backoff_restart_delay(delay.as_millis() as u64);
+                }
+                if let Some(mode) = restart_mode {
+                    config_builder = config_builder.restart_mode(mode.parse()?);
+                }
+                if !stop_exit_codes.is_empty() {
+                    config_builder = config_builder.stop_exit_codes(stop_exit_codes);
+                }
+
+                // Stop signals
+                if let Some(timeout) = kill_timeout {
+                    let timeout = pmdaemon::config::parse_duration_string(&timeout)?;
+                    config_builder = config_builder.kill_timeout(timeout.as_millis() as u64);
+                }
+                if let Some(signal) = kill_signal {
+                    let signal = signal.parse().map_err(pmdaemon::Error::config)?;
+                    config_builder = config_builder.kill_signal(signal);
+                }
+                if !kill_chain.is_empty() {
+                    let steps = kill_chain
+                        .iter()
+                        .map(|step| step.parse())
+                        .collect::<Result<Vec<_>>>()?;
+                    config_builder = config_builder.kill_chain(steps);
+                }
+
+                // Log and PID files; relative paths are taken from the current directory
+                let current_dir = std::env::current_dir()?;
+                if let Some(path) = out_file {
+                    config_builder = config_builder.out_file(current_dir.join(path));
+                }
+                if let Some(path) = error_file {
+                    config_builder = config_builder.error_file(current_dir.join(path));
+                }
+                if let Some(path) = log_file {
+                    config_builder = config_builder.log_file(current_dir.join(path));
+                }
+                if let Some(path) = pid_file {
+                    config_builder = config_builder.pid_file(current_dir.join(path));
+                }
+
+                // Log rotation
+                if log_max_size.is_some() || log_rotate_interval.is_some() {
+                    let mut log_rotation = LogRotationConfig::default().compress(log_compress);
+                    if let Some(max_size) = log_max_size {
+                        log_rotation = log_rotation
+                            .max_size(pmdaemon::config::parse_memory_string(&max_size)?);
+                    }
+                    if let Some(interval) = log_rotate_interval {
+                        log_rotation = log_rotation.interval(interval.parse()?);
+                    }
+                    if let Some(retain) = log_retain {
+                        log_rotation = log_rotation.retain(retain);
+                    }
+                    config_builder = config_builder.log_rotation(log_rotation);
+                    if daemon.is_none() {
+                        println!(
+                            "Note: log files are rotated only while `pmdaemon daemon` is running"
+                        );
+                    }
+                } else if log_retain.is_some() || log_compress {
+                    return Err(pmdaemon::Error::config(
+                        "--log-retain and --log-compress require --log-max-size or --log-rotate-interval",
+                    ));
+                }
+                if let Some(date_format) = log_date_format {
+                    config_builder = config_builder.log_date_format(date_format);
+                }
+                if let Some(format) = log_format {
+                    config_builder = config_builder.log_format(format.parse()?);
+                }
+
+                // File watching; each value may hold several space-separated patterns
+                if watch {
+                    config_builder = config_builder.watch(true).ignore_watch(
+                        ignore_watch
+                            .iter()
+                            .flat_map(|patterns| patterns.split_whitespace())
+                            .map(str::to_string),
+                    );
+                    if let Some(delay) = watch_delay {
+                        config_builder = config_builder.watch_delay(delay);
+                    }
+                    if daemon.is_none() {
+                        println!("Note: file watching stays active only while `pmdaemon daemon` is running");
+                    }
+                }
+
                 // Parse memory limit
                 if let Some(memory_str) = max_memory {
                     match pmdaemon::config::parse_memory_string(&memory_str) {
@@ -293,18 +739,62 @@ async fn main() -> Result<()> {
                 }
 
                 let config = config_builder.build()?;
-                let process_id = manager.start(config).await?;
+                let process_id = start_process(&mut manager, &mut daemon, config).await?;
 
                 println!("Started process with ID: {}", process_id);
             }
         }
 
         Commands::Stop { identifier } => {
-            manager.stop(&identifier).await?;
+            let report = match daemon.as_mut() {
+                Some(client) => client.stop(&identifier).await?,
+                None => local(&mut manager).await?.stop(&identifier).await?,
+            };
             println!("Stopped process: {}", identifier);
+            if !report.reaped.is_empty() {
+                println!(
+                    "Killed {} leftover child process(es): {}",
+                    report.reaped.len(),
+                    report
+                        .reaped
+                        .iter()
+                        .map(|pid| pid.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                );
+            }
+        }
+
+        Commands::Signal {
+            identifier,
+            signal,
+            namespace,
+        } => {
+            let signal: pmdaemon::ProcessSignal =
+                signal.parse().map_err(pmdaemon::Error::invalid_argument)?;
+            let signalled = match daemon.as_mut() {
+                Some(client) => client.signal(&identifier, signal, namespace).await?,
+                None if namespace => {
+                    local(&mut manager)
+                        .await?
+                        .signal_namespace(&identifier, signal)
+                        .await?
+                }
+                None => {
+                    local(&mut manager)
+                        .await?
+                        .signal(&identifier, signal)
+                        .await?
+                }
+            };
+            println!("Sent {} to: {}", signal, signalled.join(", "));
         }
 
-        Commands::Restart { identifier, port } => {
+        Commands::Restart {
+            identifier,
+            port,
+            wait_ready,
+        } => {
             let port_override = if let Some(port_str) = port {
                 match pmdaemon::config::PortConfig::parse(&port_str) {
                     Ok(port_config) => {
@@ -320,9 +810,29 @@ async fn main() -> Result<()> {
                 None
             };
 
-            manager
-                .restart_with_port(&identifier, port_override)
-                .await?;
+            match daemon.as_mut() {
+                Some(client) => {
+                    if wait_ready {
+                        println!("Waiting for {} to become healthy...", identifier);
+                    }
+                    client
+                        .restart(&identifier, port_override, wait_ready)
+                        .await?
+                }
+                None => {
+                    let manager = local(&mut manager).await?;
+                    manager
+                        .restart_with_port(&identifier, port_override)
+                        .await?;
+
+                    // Processes configured with `wait_ready` come back in the starting state
+                    let info = manager.get_process_info(&identifier).await?;
+                    if wait_ready || info.state == ProcessState::Starting {
+                        println!("Waiting for {} to become healthy...", identifier);
+                        manager.wait_ready(info.id).await?;
+                    }
+                }
+            }
             println!("Restarted process: {}", identifier);
         }
 
@@ -342,7 +852,15 @@ async fn main() -> Result<()> {
                 None
             };
 
-            manager.reload_with_port(&identifier, port_override).await?;
+            match daemon.as_mut() {
+                Some(client) => client.reload(&identifier, port_override).await?,
+                None => {
+                    local(&mut manager)
+                        .await?
+                        .reload_with_port(&identifier, port_override)
+                        .await?
+                }
+            }
             println!("Reloaded process: {}", identifier);
         }
 
@@ -363,7 +881,10 @@ async fn main() -> Result<()> {
                         return Ok(());
                     }
                 }
-                let deleted_count = manager.delete_all().await?;
+                let deleted_count = match daemon.as_mut() {
+                    Some(client) => client.delete_all().await?,
+                    None => local(&mut manager).await?.delete_all().await?,
+                };
                 println!("Stopped and deleted {} processes", deleted_count);
             } else if status {
                 // Delete by status
@@ -380,20 +901,34 @@ async fn main() -> Result<()> {
                         return Ok(());
                     }
                 }
-                let deleted_count = manager.delete_by_status(&identifier).await?;
+                let deleted_count = match daemon.as_mut() {
+                    Some(client) => client.delete_by_status(&identifier).await?,
+                    None => {
+                        local(&mut manager)
+                            .await?
+                            .delete_by_status(&identifier)
+                            .await?
+                    }
+                };
                 println!(
                     "Stopped and deleted {} processes with status '{}'",
                     deleted_count, identifier
                 );
             } else {
                 // Delete single process by name/ID
-                manager.delete(&identifier).await?;
+                match daemon.as_mut() {
+                    Some(client) => client.delete(&identifier).await?,
+                    None => local(&mut manager).await?.delete(&identifier).await?,
+                }
                 println!("Stopped and deleted process: {}", identifier);
             }
         }
 
         Commands::List => {
-            let processes = manager.list().await?;
+            let processes = match daemon.as_mut() {
+                Some(client) => client.list().await?,
+                None => local(&mut manager).await?.list().await?,
+            };
 
             if processes.is_empty() {
                 println!("No processes running");
@@ -414,6 +949,8 @@ async fn main() -> Result<()> {
                     Cell::new("CPU %").add_attribute(Attribute::Bold),
                     Cell::new("Memory").add_attribute(Attribute::Bold),
                     Cell::new("Port").add_attribute(Attribute::Bold),
+                    Cell::new("Watch").add_attribute(Attribute::Bold),
+                    Cell::new("Health").add_attribute(Attribute::Bold),
                 ]);
 
             // Add processes to table
@@ -434,7 +971,7 @@ async fn main() -> Result<()> {
                 let status_str = process.state.to_string();
                 let status_cell = match status_str.as_str() {
                     "online" => Cell::new(&status_str).fg(Color::Green),
-                    "stopped" => Cell::new(&status_str).fg(Color::Red),
+                    "stopped" | "errored" => Cell::new(&status_str).fg(Color::Red),
                     "stopping" => Cell::new(&status_str).fg(Color::Yellow),
                     "starting" => Cell::new(&status_str).fg(Color::Blue),
                     _ => Cell::new(&status_str),
@@ -464,6 +1001,12 @@ async fn main() -> Result<()> {
                     cpu_cell,
                     Cell::new(memory_display),
                     Cell::new(port_display),
+                    if process.watching {
+                        Cell::new("enabled").fg(Color::Cyan)
+                    } else {
+                        Cell::new("disabled")
+                    },
+                    health_cell(process.health.as_ref()),
                 ]);
             }
 
@@ -480,14 +1023,19 @@ async fn main() -> Result<()> {
             println!("Starting real-time monitoring with {}-second intervals... (Press Ctrl+C to exit)\n", interval_secs);
 
             let mut ticker = interval(Duration::from_secs(interval_secs));
+            let mut monitor = pmdaemon::monitoring::Monitor::new();
 
             loop {
                 // Clear screen
                 print!("\x1B[2J\x1B[1;1H");
                 io::stdout().flush()?;
 
-                let system_info = manager.get_system_info().await?;
-                let processes = manager.list().await?;
+                // System metrics are those of this host either way
+                let system_info = monitor.get_system_metrics().await;
+                let processes = match daemon.as_mut() {
+                    Some(client) => client.list().await?,
+                    None => local(&mut manager).await?.list().await?,
+                };
 
                 // System header
                 println!(
@@ -517,6 +1065,8 @@ async fn main() -> Result<()> {
                         Cell::new("PID").add_attribute(Attribute::Bold),
                         Cell::new("Uptime").add_attribute(Attribute::Bold),
                         Cell::new("Restarts").add_attribute(Attribute::Bold),
+                        Cell::new("Last Restart").add_attribute(Attribute::Bold),
+                        Cell::new("Next Restart").add_attribute(Attribute::Bold),
                         Cell::new("CPU %").add_attribute(Attribute::Bold),
                         Cell::new("Memory").add_attribute(Attribute::Bold),
                         Cell::new("Port").add_attribute(Attribute::Bold),
@@ -539,7 +1089,7 @@ async fn main() -> Result<()> {
                         let status_str = process.state.to_string();
                         let status_cell = match status_str.as_str() {
                             "online" => Cell::new(&status_str).fg(Color::Green),
-                            "stopped" => Cell::new(&status_str).fg(Color::Red),
+                            "stopped" | "errored" => Cell::new(&status_str).fg(Color::Red),
                             "stopping" => Cell::new(&status_str).fg(Color::Yellow),
                             "starting" => Cell::new(&status_str).fg(Color::Blue),
                             _ => Cell::new(&status_str),
@@ -566,6 +1116,8 @@ async fn main() -> Result<()> {
                             ),
                             Cell::new(uptime),
                             Cell::new(process.restarts),
+                            last_restart_cell(&process, Utc::now()),
+                            next_restart_cell(&process, Utc::now()),
                             cpu_cell,
                             Cell::new(memory_display),
                             Cell::new(port_display),
@@ -585,20 +1137,225 @@ async fn main() -> Result<()> {
             identifier,
             lines,
             follow,
+            out,
+            err,
+            since,
+            until,
+            grep,
+            level,
         } => {
-            if let Some(id) = identifier {
-                manager.read_logs(&id, Some(lines), follow).await?;
-            } else {
-                println!("Please specify a process identifier");
-                std::process::exit(1);
+            let filtered = out
+                || err
+                || since.is_some()
+                || until.is_some()
+                || grep.is_some()
+                || level.is_some();
+            let single = match &identifier {
+                Some(id) if !filtered => match daemon.as_mut() {
+                    Some(client) => client.info(id).await.is_ok(),
+                    None => local(&mut manager)
+                        .await?
+                        .get_process_info(id)
+                        .await
+                        .is_ok(),
+                },
+                _ => false,
+            };
+            match identifier {
+                Some(id) if single => match daemon.as_mut() {
+                    Some(client) if follow => follow_daemon_logs(client, &id).await?,
+                    Some(client) => print!("{}", client.logs(&id, lines).await?),
+                    None => {
+                        local(&mut manager)
+                            .await?
+                            .read_logs(&id, Some(lines), follow)
+                            .await?
+                    }
+                },
+                pattern => {
+                    let streams = match (out, err) {
+                        (true, _) => vec![LogStream::Out],
+                        (_, true) => vec![LogStream::Err],
+                        _ => vec![LogStream::Out, LogStream::Err],
+                    };
+                    let now = Utc::now();
+                    let since = since
+                        .map(|since| pmdaemon::events::parse_since(&since, now))
+                        .transpose()?;
+                    let until = until
+                        .map(|until| pmdaemon::events::parse_since(&until, now))
+                        .transpose()?;
+                    let search = LogSearch {
+                        pattern: grep
+                            .map(|grep| regex::Regex::new(&grep))
+                            .transpose()
+                            .map_err(|e| {
+                                pmdaemon::Error::invalid_argument(format!(
+                                    "Invalid --grep pattern: {}",
+                                    e
+                                ))
+                            })?,
+                        level: level.map(|level| level.parse()).transpose()?,
+                        // A time window shows all of its lines
+                        limit: (since.is_none() && until.is_none()).then_some(lines),
+                        since,
+                        until,
+                        streams,
+                    };
+                    match daemon.as_mut() {
+                        Some(client) => {
+                            let (processes, entries) = client
+                                .tail_logs(pattern.as_deref(), &search, follow)
+                                .await?;
+                            print_log_tail(&processes, entries, daemon_log_lines(client, follow))
+                                .await?;
+                        }
+                        None => {
+                            let manager = local(&mut manager).await?;
+                            tail_processes(manager, pattern.as_deref(), follow, &search).await?;
+                        }
+                    }
+                }
             }
         }
 
         Commands::Info { identifier } => {
-            let info = manager.get_process_info(&identifier).await?;
+            let info = match daemon.as_mut() {
+                Some(client) => client.info(&identifier).await?,
+                None => {
+                    local(&mut manager)
+                        .await?
+                        .get_process_info(&identifier)
+                        .await?
+                }
+            };
             println!("{:#?}", info);
         }
 
+        Commands::Events {
+            identifier,
+            since,
+            lines,
+        } => {
+            let since = since
+                .map(|since| pmdaemon::events::parse_since(&since, Utc::now()))
+                .transpose()?;
+            let filter = pmdaemon::events::EventFilter {
+                process: identifier,
+                since,
+                limit: Some(lines),
+            };
+            let events = match daemon.as_mut() {
+                Some(client) => client.events(&filter).await?,
+                None => local(&mut manager).await?.events(&filter).await?,
+            };
+
+            if events.is_empty() {
+                println!("No events recorded");
+                return Ok(());
+            }
+
+            let mut table = Table::new();
+            table
+                .load_preset(comfy_table::presets::UTF8_FULL)
+                .set_header(vec![
+                    Cell::new("Time").add_attribute(Attribute::Bold),
+                    Cell::new("Name").add_attribute(Attribute::Bold),
+                    Cell::new("ID").add_attribute(Attribute::Bold),
+                    Cell::new("Event").add_attribute(Attribute::Bold),
+                ]);
+            for event in events {
+                table.add_row(vec![
+                    Cell::new(
+                        event
+                            .at
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M:%S")
+                            .to_string(),
+                    ),
+                    Cell::new(truncate_string(&event.process_name, 20)),
+                    Cell::new(&event.process_id.to_string()[..8]),
+                    event_cell(&event.kind),
+                ]);
+            }
+
+            println!("{}", table);
+        }
+
+        Commands::Crashes { identifier, show } => {
+            if let Some(crash_id) = show {
+                let crash = match daemon.as_mut() {
+                    Some(client) => client.crash(&crash_id).await?,
+                    None => local(&mut manager).await?.crash(&crash_id).await?,
+                };
+                print_crash(&crash)?;
+                return Ok(());
+            }
+
+            let crashes = match daemon.as_mut() {
+                Some(client) => client.crashes(identifier.as_deref()).await?,
+                None => {
+                    local(&mut manager)
+                        .await?
+                        .crashes(identifier.as_deref())
+                        .await?
+                }
+            };
+            if crashes.is_empty() {
+                println!("No crashes recorded");
+                return Ok(());
+            }
+
+            let mut table = Table::new();
+            table
+                .load_preset(comfy_table::presets::UTF8_FULL)
+                .set_header(vec![
+                    Cell::new("Crash ID").add_attribute(Attribute::Bold),
+                    Cell::new("Time").add_attribute(Attribute::Bold),
+                    Cell::new("Name").add_attribute(Attribute::Bold),
+                    Cell::new("Exit").add_attribute(Attribute::Bold),
+                    Cell::new("Uptime").add_attribute(Attribute::Bold),
+                    Cell::new("CPU %").add_attribute(Attribute::Bold),
+                    Cell::new("Memory").add_attribute(Attribute::Bold),
+                ]);
+            for crash in crashes {
+                table.add_row(vec![
+                    Cell::new(&crash.id),
+                    Cell::new(
+                        crash
+                            .at
+                            .with_timezone(&Local)
+                            .format("%Y-%m-%d %H:%M:%S")
+                            .to_string(),
+                    ),
+                    Cell::new(truncate_string(&crash.process_name, 20)),
+                    Cell::new(crash.exit.to_string()).fg(Color::Red),
+                    Cell::new(format_duration(chrono::Duration::milliseconds(
+                        crash.uptime as i64,
+                    ))),
+                    Cell::new(format!("{:.1}", crash.cpu_usage)),
+                    Cell::new(format_memory(crash.memory_usage)),
+                ]);
+            }
+
+            println!("{}", table);
+            println!("Use 'pmdaemon crashes --show <CRASH ID>' for log tails and configuration");
+        }
+
+        Commands::Daemon => {
+            let socket_path = ProcessManager::get_socket_path()?;
+            println!(
+                "Starting PMDaemon daemon (socket: {})",
+                socket_path.display()
+            );
+            Daemon::new(ProcessManager::new().await?, socket_path)
+                .run()
+                .await?;
+            println!("Daemon stopped");
+        }
+
+        Commands::LogShim { .. } => unreachable!("log shims run before the manager is loaded"),
+
         Commands::RegenerateApiKey => {
             let api_key = generate_api_key()?;
             let api_key_path = ProcessManager::get_api_key_path()?;
@@ -672,7 +1429,8 @@ async fn main() -> Result<()> {
                 println!("Starting web server without authentication");
             }
 
-            manager
+            ProcessManager::new()
+                .await?
                 .start_web_server_with_api_key(&host, port, final_api_key)
                 .await?;
         }
@@ -681,18 +1439,308 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-/// Format duration in human-readable format
-fn format_duration(duration: chrono::Duration) -> String {
-    let total_seconds = duration.num_seconds();
-
-    if total_seconds < 60 {
-        format!("{}s", total_seconds)
-    } else if total_seconds < 3600 {
-        format!("{}m", total_seconds / 60)
-    } else if total_seconds < 86400 {
-        format!("{}h", total_seconds / 3600)
-    } else {
-        format!("{}d", total_seconds / 86400)
+/// The in-process manager, loaded on first use
+async fn local(manager: &mut Option<ProcessManager>) -> Result<&mut ProcessManager> {
+    if manager.is_none() {
+        *manager = Some(ProcessManager::new().await?);
+    }
+    Ok(manager.as_mut().expect("process manager was just loaded"))
+}
+
+/// Start a process through the daemon when one is running, otherwise in-process
+async fn start_process(
+    manager: &mut Option<ProcessManager>,
+    daemon: &mut Option<DaemonClient>,
+    config: ProcessConfig,
+) -> Result<ProcessId> {
+    match daemon.as_mut() {
+        Some(client) => client.start(config).await,
+        None => local(manager).await?.start(config).await,
+    }
+}
+
+/// Print the lines of every process matching `pattern` that match `search`,
+/// interleaved by time with a colored `name|instance` prefix, and with `follow` keep
+/// printing new matching lines in the order they arrive
+async fn tail_processes(
+    manager: &ProcessManager,
+    pattern: Option<&str>,
+    follow: bool,
+    search: &LogSearch,
+) -> Result<()> {
+    use futures_util::stream::{self, StreamExt};
+
+    let processes = manager.match_processes(pattern).await?;
+
+    // Subscribe before reading the history so that no line falls in between
+    let mut subscriptions = Vec::new();
+    if follow {
+        for process in &processes {
+            let id = process.id.to_string();
+            subscriptions.push(manager.subscribe_process_logs(&id).await?);
+        }
+    }
+    let entries = manager.search_processes_logs(&processes, search).await?;
+
+    let formats: std::collections::HashMap<ProcessId, LogFormat> = subscriptions
+        .iter()
+        .map(|subscription| (subscription.process_id(), subscription.format()))
+        .collect();
+    let lines = stream::select_all(subscriptions.into_iter().map(|subscription| {
+        stream::unfold(subscription, |mut subscription| async move {
+            let line = subscription.next().await?;
+            Some((line, subscription))
+        })
+        .boxed()
+    }))
+    .filter_map(|mut line| {
+        let format = formats[&line.process_id];
+        let found = search.matches(&line, format);
+        if found && format == LogFormat::Json {
+            line.line = humanize_json_line(&line.line);
+        }
+        std::future::ready(found.then_some(Ok(line)))
+    });
+    print_log_tail(&processes, entries, lines).await
+}
+
+/// New log lines followed through the daemon after [`DaemonClient::tail_logs`];
+/// none unless `follow` is set
+fn daemon_log_lines(
+    client: &mut DaemonClient,
+    follow: bool,
+) -> impl futures_util::Stream<Item = Result<LogLine>> + '_ {
+    futures_util::stream::try_unfold(follow.then_some(client), |client| async move {
+        let Some(client) = client else {
+            return Ok(None);
+        };
+        Ok(client
+            .next_log_line()
+            .await?
+            .map(|line| (line, Some(client))))
+    })
+}
+
+/// Print the lines found in the logs of `processes`, which are interleaved by time,
+/// with a colored `name|instance` prefix, then each line of `lines` as it arrives
+async fn print_log_tail(
+    processes: &[ProcessStatus],
+    entries: Vec<LogEntry>,
+    lines: impl futures_util::Stream<Item = Result<LogLine>>,
+) -> Result<()> {
+    use futures_util::StreamExt;
+    use std::io::IsTerminal;
+
+    let prefixes = log_prefixes(processes, std::io::stdout().is_terminal());
+    for entry in entries {
+        println!("{} {}", prefixes[&entry.process_id], entry.line);
+    }
+
+    let mut lines = std::pin::pin!(lines);
+    while let Some(line) = lines.next().await {
+        let line = line?;
+        println!("{} {}", prefixes[&line.process_id], line.line);
+    }
+    Ok(())
+}
+
+/// Follow the logs of a single process through the daemon, as
+/// [`ProcessManager::follow_logs`] does in-process
+async fn follow_daemon_logs(client: &mut DaemonClient, identifier: &str) -> Result<()> {
+    // Only new lines, like following in-process
+    let search = LogSearch {
+        limit: Some(0),
+        ..Default::default()
+    };
+    let (processes, _) = client.tail_logs(Some(identifier), &search, true).await?;
+    let name = processes
+        .first()
+        .map_or(identifier, |process| process.name.as_str());
+    println!("==> Following logs for {} <==", name);
+    println!("Press Ctrl+C to stop following");
+    println!();
+
+    while let Some(line) = client.next_log_line().await? {
+        let stream = match line.stream {
+            LogStream::Out => "stdout",
+            LogStream::Err => "stderr",
+        };
+        println!("[{}] {}", stream, line.line);
+    }
+    Ok(())
+}
+
+/// `name|instance` prefix of each process' log lines, padded to the same width and
+/// colored per app when `color` is set
+fn log_prefixes(
+    processes: &[ProcessStatus],
+    color: bool,
+) -> std::collections::HashMap<ProcessId, String> {
+    const COLORS: [u8; 6] = [36, 32, 33, 35, 34, 31];
+
+    let labels: Vec<(String, String)> = processes
+        .iter()
+        .map(|process| {
+            let instance = process.instance.unwrap_or(0);
+            let app = process
+                .name
+                .strip_suffix(&format!("-{}", instance))
+                .filter(|_| process.instance.is_some())
+                .unwrap_or(&process.name);
+            (app.to_string(), format!("{}|{}", app, instance))
+        })
+        .collect();
+    let width = labels
+        .iter()
+        .map(|(_, label)| label.len())
+        .max()
+        .unwrap_or(0);
+
+    let mut apps: Vec<&str> = Vec::new();
+    for (app, _) in &labels {
+        if !apps.contains(&app.as_str()) {
+            apps.push(app);
+        }
+    }
+    processes
+        .iter()
+        .zip(&labels)
+        .map(|(process, (app, label))| {
+            let label = format!("{:<width$}", label, width = width);
+            let prefix = if color {
+                let index = apps.iter().position(|other| other == app).unwrap_or(0);
+                format!("\x1b[{}m{}\x1b[0m", COLORS[index % COLORS.len()], label)
+            } else {
+                label
+            };
+            (process.id, prefix)
+        })
+        .collect()
+}
+
+/// Colored table cell for a process' health
+fn health_cell(health: Option<&HealthStatus>) -> Cell {
+    match health.map(|h| h.state) {
+        Some(HealthState::Healthy) => Cell::new("healthy").fg(Color::Green),
+        Some(HealthState::Unhealthy) => Cell::new("unhealthy").fg(Color::Red),
+        Some(HealthState::Unknown) => Cell::new("pending").fg(Color::Yellow),
+        None => Cell::new("-"),
+    }
+}
+
+/// Create a table cell describing a lifecycle event, colored by severity
+fn event_cell(kind: &pmdaemon::events::EventKind) -> Cell {
+    use pmdaemon::events::EventKind;
+
+    let cell = Cell::new(kind.to_string());
+    match kind {
+        EventKind::Errored { .. } | EventKind::Exited { .. } => cell.fg(Color::Red),
+        EventKind::HealthChanged {
+            to: HealthState::Unhealthy,
+            ..
+        } => cell.fg(Color::Red),
+        EventKind::Restarted { .. } | EventKind::Stopped { forced: true } => cell.fg(Color::Yellow),
+        EventKind::Started { .. }
+        | EventKind::HealthChanged {
+            to: HealthState::Healthy,
+            ..
+        } => cell.fg(Color::Green),
+        _ => cell,
+    }
+}
+
+/// Print a crash record with its log tails and effective configuration
+fn print_crash(crash: &pmdaemon::crashes::CrashRecord) -> Result<()> {
+    println!("Crash:   {}", crash.id);
+    println!("Process: {} ({})", crash.process_name, crash.process_id);
+    println!(
+        "Time:    {}",
+        crash.at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+    );
+    println!("Exit:    {}", crash.exit);
+    println!(
+        "Uptime:  {}",
+        format_duration(chrono::Duration::milliseconds(crash.uptime as i64))
+    );
+    match crash.sampled_at {
+        Some(sampled_at) => println!(
+            "Usage:   {:.1}% CPU, {} memory (sampled {})",
+            crash.cpu_usage,
+            format_memory(crash.memory_usage),
+            sampled_at.with_timezone(&Local).format("%H:%M:%S")
+        ),
+        None => println!("Usage:   not sampled"),
+    }
+
+    for (title, lines) in [
+        ("Error log", &crash.stderr_tail),
+        ("Output log", &crash.stdout_tail),
+    ] {
+        println!("\n{} (last {} lines):", title, lines.len());
+        for line in lines {
+            println!("  {}", line);
+        }
+    }
+
+    println!("\nConfiguration:");
+    println!("{}", serde_json::to_string_pretty(&crash.config)?);
+    Ok(())
+}
+
+/// Table cell describing a scheduled automatic restart and the backoff delay
+fn next_restart_cell(process: &ProcessStatus, now: chrono::DateTime<Utc>) -> Cell {
+    let backoff = process
+        .backoff_delay
+        .map(|delay| format!(" ({}ms backoff)", delay))
+        .unwrap_or_default();
+    match process.next_restart {
+        Some(at) if at > now => {
+            Cell::new(format!("in {}{}", format_duration(at - now), backoff)).fg(Color::Yellow)
+        }
+        Some(_) => Cell::new(format!("due{}", backoff)).fg(Color::Yellow),
+        None => match process.backoff_delay {
+            Some(delay) => Cell::new(format!("{}ms backoff", delay)),
+            None => Cell::new("-"),
+        },
+    }
+}
+
+/// Describe the most recent restart of a process and how the run before it ended
+fn last_restart_cell(process: &ProcessStatus, now: chrono::DateTime<Utc>) -> Cell {
+    let Some(last) = process.restart_history.last() else {
+        return Cell::new("-");
+    };
+    let exit = match (last.exit_code, last.exit_signal) {
+        (_, Some(signal)) => format!(", {}", signal),
+        (Some(code), None) => format!(", exit {}", code),
+        (None, None) => String::new(),
+    };
+    let cell = Cell::new(format!(
+        "{} {} ago{}",
+        last.reason,
+        format_duration(now - last.at),
+        exit
+    ));
+    match last.reason {
+        RestartReason::Crash | RestartReason::MemoryLimit | RestartReason::Unhealthy => {
+            cell.fg(Color::Yellow)
+        }
+        _ => cell,
+    }
+}
+
+/// Format duration in human-readable format
+fn format_duration(duration: chrono::Duration) -> String {
+    let total_seconds = duration.num_seconds();
+
+    if total_seconds < 60 {
+        format!("{}s", total_seconds)
+    } else if total_seconds < 3600 {
+        format!("{}m", total_seconds / 60)
+    } else if total_seconds < 86400 {
+        format!("{}h", total_seconds / 3600)
+    } else {
+        format!("{}d", total_seconds / 86400)
     }
 }
 
@@ -807,6 +1855,12 @@ mod tests {
         assert!(matches!(cli.command, Commands::List));
     }
 
+    #[test]
+    fn test_cli_parsing_daemon_command() {
+        let cli = Cli::try_parse_from(["pmdaemon", "daemon"]).unwrap();
+        assert!(matches!(cli.command, Commands::Daemon));
+    }
+
     #[test]
     fn test_cli_parsing_verbose_flag() {
         let cli = Cli::try_parse_from(["pmdaemon", "--verbose", "list"]).unwrap();
@@ -832,6 +1886,7 @@ mod tests {
             max_memory,
             port,
             args,
+            ..
         } = cli.command
         {
             assert_eq!(script, Some("script.js".to_string()));
@@ -882,6 +1937,7 @@ mod tests {
             max_memory,
             port,
             args,
+            ..
         } = cli.command
         {
             assert_eq!(script, Some("app.js".to_string()));
@@ -897,6 +1953,340 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_start_command_with_watch() {
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--watch",
+            "--ignore-watch",
+            "node_modules *.log",
+            "--ignore-watch",
+            "tmp",
+            "--watch-delay",
+            "500",
+        ])
+        .unwrap();
+
+        if let Commands::Start {
+            watch,
+            ignore_watch,
+            watch_delay,
+            ..
+        } = cli.command
+        {
+            assert!(watch);
+            assert_eq!(ignore_watch, vec!["node_modules *.log", "tmp"]);
+            assert_eq!(watch_delay, Some(500));
+        } else {
+            panic!("Expected Start command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_start_command_with_user() {
+        let cli = Cli::try_parse_from([
+            "pmdaemon", "start", "app.js", "--user", "www-data", "--group", "1001",
+        ])
+        .unwrap();
+
+        if let Commands::Start { user, group, .. } = cli.command {
+            assert_eq!(user, Some("www-data".to_string()));
+            assert_eq!(group, Some("1001".to_string()));
+        } else {
+            panic!("Expected Start command");
+        }
+    }
+
+    #[test]
+    fn test_cli_parsing_start_command_with_health_check() {
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--health-check-url",
+            "http://localhost:3000/health",
+            "--health-check-interval",
+            "10s",
+            "--health-check-retries",
+            "2",
+            "--on-unhealthy",
+            "stop",
+        ])
+        .unwrap();
+
+        if let Commands::Start {
+            health_check_url,
+            health_check_interval,
+            health_check_retries,
+            on_unhealthy,
+            ..
+        } = cli.command
+        {
+            assert_eq!(
+                health_check_url,
+                Some("http://localhost:3000/health".to_string())
+            );
+            assert_eq!(health_check_interval, Some("10s".to_string()));
+            assert_eq!(health_check_retries, Some(2));
+            assert_eq!(on_unhealthy, "stop");
+        } else {
+            panic!("Expected Start command");
+        }
+
+        // URL and script checks are mutually exclusive
+        assert!(Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--health-check-url",
+            "http://localhost:3000/health",
+            "--health-check-script",
+            "./health.sh",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_start_command_with_wait_ready() {
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--health-check-url",
+            "http://localhost:3000/health",
+            "--wait-ready",
+            "--wait-timeout",
+            "60s",
+        ])
+        .unwrap();
+
+        if let Commands::Start {
+            wait_ready,
+            wait_timeout,
+            ..
+        } = cli.command
+        {
+            assert!(wait_ready);
+            assert_eq!(wait_timeout, Some("60s".to_string()));
+        } else {
+            panic!("Expected Start command");
+        }
+
+        // A timeout only makes sense when waiting
+        assert!(
+            Cli::try_parse_from(["pmdaemon", "start", "app.js", "--wait-timeout", "60s"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parsing_start_command_with_restart_policy() {
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--max-restarts",
+            "10",
+            "--min-uptime",
+            "5s",
+            "--restart-delay",
+            "500ms",
+        ])
+        .unwrap();
+
+        if let Commands::Start {
+            max_restarts,
+            min_uptime,
+            restart_delay,
+            ..
+        } = cli.command
+        {
+            assert_eq!(max_restarts, Some(10));
+            assert_eq!(min_uptime, Some("5s".to_string()));
+            assert_eq!(restart_delay, Some("500ms".to_string()));
+        } else {
+            panic!("Expected Start command");
+        }
+
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--exp-backoff-restart-delay",
+            "100ms",
+            "--max-exp-backoff-restart-delay",
+            "1m",
+        ])
+        .unwrap();
+        if let Commands::Start {
+            exp_backoff_restart_delay,
+            max_exp_backoff_restart_delay,
+            ..
+        } = cli.command
+        {
+            assert_eq!(exp_backoff_restart_delay, Some("100ms".to_string()));
+            assert_eq!(max_exp_backoff_restart_delay, Some("1m".to_string()));
+        } else {
+            panic!("Expected Start command");
+        }
+
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "job.sh",
+            "--restart-mode",
+            "on-failure",
+            "--stop-exit-codes",
+            "0,3",
+        ])
+        .unwrap();
+        if let Commands::Start {
+            restart_mode,
+            stop_exit_codes,
+            ..
+        } = cli.command
+        {
+            assert_eq!(restart_mode, Some("on-failure".to_string()));
+            assert_eq!(stop_exit_codes, vec![0, 3]);
+        } else {
+            panic!("Expected Start command");
+        }
+
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "nginx",
+            "--kill-timeout",
+            "30s",
+            "--kill-chain",
+            "QUIT:10s,TERM,KILL",
+        ])
+        .unwrap();
+        if let Commands::Start {
+            kill_timeout,
+            kill_chain,
+            ..
+        } = cli.command
+        {
+            assert_eq!(kill_timeout, Some("30s".to_string()));
+            assert_eq!(kill_chain, vec!["QUIT:10s", "TERM", "KILL"]);
+        } else {
+            panic!("Expected Start command");
+        }
+
+        // A single stop signal and an escalation chain are mutually exclusive
+        assert!(Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "nginx",
+            "--kill-signal",
+            "QUIT",
+            "--kill-chain",
+            "TERM,KILL",
+        ])
+        .is_err());
+
+        // The cap only applies to a backoff
+        assert!(Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--max-exp-backoff-restart-delay",
+            "1m",
+        ])
+        .is_err());
+
+        // A fixed delay and a backoff are mutually exclusive
+        assert!(Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--restart-delay",
+            "1s",
+            "--exp-backoff-restart-delay",
+            "100ms",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parsing_start_command_with_log_rotation() {
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "start",
+            "app.js",
+            "--log-max-size",
+            "10M",
+            "--log-rotate-interval",
+            "daily",
+            "--log-retain",
+            "5",
+            "--log-compress",
+            "--log-date-format",
+            "%H:%M:%S",
+            "--log-format",
+            "json",
+        ])
+        .unwrap();
+
+        if let Commands::Start {
+            log_max_size,
+            log_rotate_interval,
+            log_retain,
+            log_compress,
+            log_date_format,
+            log_format,
+            ..
+        } = cli.command
+        {
+            assert_eq!(log_max_size, Some("10M".to_string()));
+            assert_eq!(log_rotate_interval, Some("daily".to_string()));
+            assert_eq!(log_retain, Some(5));
+            assert!(log_compress);
+            assert_eq!(log_date_format, Some("%H:%M:%S".to_string()));
+            assert_eq!(log_format, Some("json".to_string()));
+        } else {
+            panic!("Expected Start command");
+        }
+    }
+
+    #[test]
+    fn test_next_restart_cell() {
+        let now = Utc::now();
+        let mut status = ProcessConfig::builder()
+            .name("api")
+            .script("node")
+            .build()
+            .map(pmdaemon::Process::new)
+            .unwrap()
+            .status();
+        assert_eq!(next_restart_cell(&status, now).content(), "-");
+
+        status.backoff_delay = Some(400);
+        assert_eq!(next_restart_cell(&status, now).content(), "400ms backoff");
+
+        status.next_restart = Some(now + chrono::Duration::seconds(3));
+        assert_eq!(
+            next_restart_cell(&status, now).content(),
+            "in 3s (400ms backoff)"
+        );
+        assert_eq!(
+            next_restart_cell(&status, now + chrono::Duration::seconds(5)).content(),
+            "due (400ms backoff)"
+        );
+    }
+
+    #[test]
+    fn test_health_cell() {
+        assert_eq!(health_cell(None).content(), "-");
+        let status = HealthStatus {
+            state: HealthState::Healthy,
+            ..Default::default()
+        };
+        assert_eq!(health_cell(Some(&status)).content(), "healthy");
+    }
+
     #[test]
     fn test_cli_parsing_stop_command() {
         let cli = Cli::try_parse_from(["pmdaemon", "stop", "my-app"]).unwrap();
@@ -907,12 +2297,46 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_signal_command() {
+        let cli = Cli::try_parse_from(["pmdaemon", "signal", "web", "HUP"]).unwrap();
+        if let Commands::Signal {
+            identifier,
+            signal,
+            namespace,
+        } = cli.command
+        {
+            assert_eq!(identifier, "web");
+            assert_eq!(signal, "HUP");
+            assert!(!namespace);
+        } else {
+            panic!("Expected Signal command");
+        }
+
+        let cli =
+            Cli::try_parse_from(["pmdaemon", "signal", "--namespace", "prod", "USR2"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Signal {
+                namespace: true,
+                ..
+            }
+        ));
+        assert!(Cli::try_parse_from(["pmdaemon", "signal", "web"]).is_err());
+    }
+
     #[test]
     fn test_cli_parsing_restart_command() {
         let cli = Cli::try_parse_from(["pmdaemon", "restart", "my-app", "--port", "4000"]).unwrap();
-        if let Commands::Restart { identifier, port } = cli.command {
+        if let Commands::Restart {
+            identifier,
+            port,
+            wait_ready,
+        } = cli.command
+        {
             assert_eq!(identifier, "my-app");
             assert_eq!(port, Some("4000".to_string()));
+            assert!(!wait_ready);
         } else {
             panic!("Expected Restart command");
         }
@@ -992,6 +2416,7 @@ mod tests {
             identifier,
             lines,
             follow,
+            ..
         } = cli.command
         {
             assert_eq!(identifier, Some("my-app".to_string()));
@@ -1009,6 +2434,7 @@ mod tests {
             identifier,
             lines,
             follow,
+            ..
         } = cli.command
         {
             assert_eq!(identifier, None);
@@ -1019,6 +2445,77 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_logs_command_with_filters() {
+        let cli = Cli::try_parse_from([
+            "pmdaemon",
+            "logs",
+            "api-*",
+            "--err",
+            "--since",
+            "10m",
+            "--until",
+            "5m",
+            "--grep",
+            "time(out|d out)",
+            "--level",
+            "error",
+        ])
+        .unwrap();
+        if let Commands::Logs {
+            identifier,
+            out,
+            err,
+            since,
+            until,
+            grep,
+            level,
+            ..
+        } = cli.command
+        {
+            assert_eq!(identifier, Some("api-*".to_string()));
+            assert!(!out);
+            assert!(err);
+            assert_eq!(since, Some("10m".to_string()));
+            assert_eq!(until, Some("5m".to_string()));
+            assert_eq!(grep, Some("time(out|d out)".to_string()));
+            assert_eq!(level, Some("error".to_string()));
+        } else {
+            panic!("Expected Logs command");
+        }
+
+        assert!(Cli::try_parse_from(["pmdaemon", "logs", "--out", "--err"]).is_err());
+    }
+
+    #[test]
+    fn test_log_prefixes() {
+        let process = |name: &str, instance| {
+            let mut status = ProcessConfig::builder()
+                .name(name)
+                .script("node")
+                .build()
+                .map(pmdaemon::Process::new)
+                .unwrap()
+                .status();
+            status.instance = instance;
+            status
+        };
+        let processes = vec![
+            process("web-0", Some(0)),
+            process("web-1", Some(1)),
+            process("worker", None),
+        ];
+
+        let plain = log_prefixes(&processes, false);
+        assert_eq!(plain[&processes[0].id], "web|0   ");
+        assert_eq!(plain[&processes[1].id], "web|1   ");
+        assert_eq!(plain[&processes[2].id], "worker|0");
+
+        let colored = log_prefixes(&processes, true);
+        assert_eq!(colored[&processes[1].id], "\x1b[36mweb|1   \x1b[0m");
+        assert_eq!(colored[&processes[2].id], "\x1b[32mworker|0\x1b[0m");
+    }
+
     #[test]
     fn test_cli_parsing_info_command() {
         let cli = Cli::try_parse_from(["pmdaemon", "info", "my-app"]).unwrap();
@@ -1029,6 +2526,55 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parsing_events_command() {
+        let cli = Cli::try_parse_from(["pmdaemon", "events", "web", "--since", "2h"]).unwrap();
+        if let Commands::Events {
+            identifier,
+            since,
+            lines,
+        } = cli.command
+        {
+            assert_eq!(identifier, Some("web".to_string()));
+            assert_eq!(since, Some("2h".to_string()));
+            assert_eq!(lines, 50); // Default value
+        } else {
+            panic!("Expected Events command");
+        }
+
+        let cli = Cli::try_parse_from(["pmdaemon", "events", "--lines", "5"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Events {
+                identifier: None,
+                since: None,
+                lines: 5
+            }
+        ));
+    }
+
+    #[test]
+    fn test_cli_parsing_crashes_command() {
+        let cli = Cli::try_parse_from(["pmdaemon", "crashes", "web"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Crashes {
+                identifier: Some(ref identifier),
+                show: None
+            } if identifier == "web"
+        ));
+
+        let cli =
+            Cli::try_parse_from(["pmdaemon", "crashes", "--show", "web-20250601T120000.000Z"])
+                .unwrap();
+        if let Commands::Crashes { identifier, show } = cli.command {
+            assert_eq!(identifier, None);
+            assert_eq!(show, Some("web-20250601T120000.000Z".to_string()));
+        } else {
+            panic!("Expected Crashes command");
+        }
+    }
+
     #[test]
     fn test_cli_parsing_web_command() {
         let cli = Cli::try_parse_from(["pmdaemon", "web", "--port", "8080", "--host", "0.0.0.0"])
@@ -46,7 +46,8 @@
 use crate::error::{Error, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// Parse memory string (e.g., "100M", "1G", "512K") to bytes
 pub fn parse_memory_string(memory_str: &str) -> Result<u64> {
@@ -89,6 +90,51 @@ pub fn parse_memory_string(memory_str: &str) -> Result<u64> {
     Ok(bytes_f64.round() as u64)
 }
 
+/// Parse duration string (e.g., "500ms", "30s", "2m", "1h") to a `Duration`
+///
+/// A bare number is interpreted as seconds.
+pub fn parse_duration_string(duration_str: &str) -> Result<Duration> {
+    let duration_str = duration_str.trim().to_lowercase();
+
+    if duration_str.is_empty() {
+        return Err(Error::config("Duration string cannot be empty"));
+    }
+
+    let (number_part, unit_ms) = if let Some(number_str) = duration_str.strip_suffix("ms") {
+        (number_str, 1u64)
+    } else if let Some(number_str) = duration_str.strip_suffix('s') {
+        (number_str, 1000u64)
+    } else if let Some(number_str) = duration_str.strip_suffix('m') {
+        (number_str, 60 * 1000u64)
+    } else if let Some(number_str) = duration_str.strip_suffix('h') {
+        (number_str, 60 * 60 * 1000u64)
+    } else {
+        // Assume seconds if no unit specified
+        (duration_str.as_str(), 1000u64)
+    };
+
+    let number: u64 = number_part
+        .trim()
+        .parse()
+        .map_err(|_| Error::config(format!("Invalid duration: {}", duration_str)))?;
+
+    number
+        .checked_mul(unit_ms)
+        .map(Duration::from_millis)
+        .ok_or_else(|| Error::config("Duration is too large"))
+}
+
+/// Path of a cluster instance's copy of a configured file: `logs/app.log` becomes
+/// `logs/app-0.log` for instance 0
+pub fn instance_path(path: &Path, instance: u32) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(extension) => format!("{}-{}.{}", stem, instance, extension.to_string_lossy()),
+        None => format!("{}-{}", stem, instance),
+    };
+    path.with_file_name(file_name)
+}
+
 /// Format memory in human-readable format
 pub fn format_memory(bytes: u64) -> String {
     if bytes == 0 {
@@ -261,14 +307,16 @@ pub struct ProcessConfig {
     /// When enabled, processes are automatically restarted when they exit unexpectedly.
     pub autorestart: bool,
 
-    /// Maximum number of restart attempts (default: 16)
+    /// Maximum number of consecutive unstable restarts (default: 16)
     ///
-    /// After this many restarts, auto-restart is disabled for the process.
+    /// A restart is unstable when the process exited within `min_uptime`. Once this
+    /// many unstable restarts happen in a row, the process is marked errored instead
+    /// of being restarted again.
     pub max_restarts: u32,
 
     /// Minimum uptime before considering stable (ms, default: 1000)
     ///
-    /// Process must run for this duration before restart counter is reset.
+    /// Process must run for this duration before the unstable restart counter is reset.
     pub min_uptime: u64,
 
     /// Restart delay (ms, default: 0)
@@ -276,11 +324,44 @@ pub struct ProcessConfig {
     /// Delay between process exit and restart attempt.
     pub restart_delay: u64,
 
+    /// Initial exponential backoff restart delay (ms, default: none)
+    ///
+    /// When set, it replaces `restart_delay`: the delay starts at this value and
+    /// doubles after each consecutive crash, up to `max_exp_backoff_restart_delay`. It
+    /// goes back to the initial value once the process stays up past `min_uptime`.
+    pub exp_backoff_restart_delay: Option<u64>,
+
+    /// Upper bound of the exponential backoff restart delay (ms, default: 15000)
+    ///
+    /// A cap below `exp_backoff_restart_delay` keeps the delay at its initial value.
+    pub max_exp_backoff_restart_delay: u64,
+
+    /// When to restart the process after it exits (default: always)
+    ///
+    /// Only applies while `autorestart` is enabled.
+    pub restart_mode: crate::restart::RestartMode,
+
+    /// Exit codes that count as a clean stop and never trigger a restart
+    pub stop_exit_codes: Vec<i32>,
+
     /// Kill timeout (ms, default: 1600)
     ///
-    /// Time to wait for graceful shutdown (SIGTERM) before force kill (SIGKILL).
+    /// Time to wait for graceful shutdown after `kill_signal` before force kill
+    /// (SIGKILL), and the default wait for steps of `kill_chain`.
     pub kill_timeout: u64,
 
+    /// Signal sent to stop the process (default: SIGTERM)
+    ///
+    /// Ignored when `kill_chain` is set.
+    pub kill_signal: crate::signals::ProcessSignal,
+
+    /// Signals to escalate through when stopping the process (default: none)
+    ///
+    /// Each step's signal is sent and the process gets the step's timeout to exit
+    /// before the next step. SIGKILL is sent after the last step if the process is
+    /// still running.
+    pub kill_chain: Vec<crate::signals::KillStep>,
+
     /// Maximum memory before restart (bytes, optional)
     ///
     /// When set, process is automatically restarted if memory usage exceeds this limit.
@@ -290,27 +371,48 @@ pub struct ProcessConfig {
     pub max_memory_restart: Option<u64>,
 
     /// Output log file path (auto-generated if not specified)
+    ///
+    /// Relative paths are resolved against `cwd` when it is set. Cluster instances
+    /// append their instance number, e.g. `app-out-0.log`.
     pub out_file: Option<PathBuf>,
 
     /// Error log file path (auto-generated if not specified)
     pub error_file: Option<PathBuf>,
 
-    /// Combined log file path (auto-generated if not specified)
+    /// Combined stdout and stderr log file path (auto-generated if not specified)
     pub log_file: Option<PathBuf>,
 
     /// PID file path (auto-generated if not specified)
     pub pid_file: Option<PathBuf>,
 
-    /// Watch for file changes and restart (not yet implemented)
+    /// Watch for file changes and restart (default: false)
+    ///
+    /// Watches `watch_paths`, or the working directory when none are given.
     pub watch: bool,
 
-    /// Files/directories to ignore when watching (not yet implemented)
+    /// Paths to watch when `watch` is enabled (default: the working directory)
+    ///
+    /// Relative paths are resolved against the working directory.
+    pub watch_paths: Vec<PathBuf>,
+
+    /// Glob patterns for files/directories to ignore when watching
+    ///
+    /// Patterns match the path relative to the watched directory or any single
+    /// path component, e.g. `node_modules`, `*.log` or `build/**/*.js`.
     pub ignore_watch: Vec<String>,
 
-    /// User to run the process as (not yet implemented)
+    /// Quiet period before restarting after file changes (ms, default: 1000)
+    ///
+    /// Bursts of changes are collected into a single restart.
+    pub watch_delay: u64,
+
+    /// User to run the process as (name or numeric UID, Unix only)
+    ///
+    /// Requires pmdaemon to run as root. The group defaults to the user's
+    /// primary group and supplementary groups are set from the group database.
     pub user: Option<String>,
 
-    /// Group to run the process as (not yet implemented)
+    /// Group to run the process as (name or numeric GID, Unix only)
     pub group: Option<String>,
 
     /// Namespace for process grouping (default: "default")
@@ -330,6 +432,34 @@ pub struct ProcessConfig {
     /// Enables monitoring of process health through HTTP endpoints or custom scripts.
     /// Health checks can trigger automatic restarts when processes become unhealthy.
     pub health_check: Option<crate::health::HealthCheckConfig>,
+
+    /// Wait for the first successful health check on start (default: false)
+    ///
+    /// The process stays in the `starting` state until its health check passes, and
+    /// starting fails if that does not happen within `wait_timeout`. Requires an
+    /// enabled `health_check`.
+    pub wait_ready: bool,
+
+    /// Time to wait for the process to become healthy (ms, default: 30000)
+    pub wait_timeout: u64,
+
+    /// Log rotation settings (default: logs grow without rotation)
+    pub log_rotation: Option<crate::logs::LogRotationConfig>,
+
+    /// Timestamp format prefixed to each log line, e.g. `%Y-%m-%d %H:%M:%S%.3f`
+    /// (strftime syntax, local time; default: no timestamps)
+    ///
+    /// Lines are timestamped by the process' [`LogShim`](crate::logs::LogShim). Where
+    /// no shim can be started (on Windows, or when no `pmdaemon` executable is found),
+    /// output is written as printed and a warning is logged when the process starts.
+    pub log_date_format: Option<String>,
+
+    /// Format of log lines: `text` (default) or `json`, one JSON object per line
+    ///
+    /// JSON lines are written by the process' [`LogShim`](crate::logs::LogShim). Where
+    /// no shim can be started, processes with `json` fail to start instead of writing
+    /// raw output to their logs.
+    pub log_format: crate::logs::LogFormat,
 }
 
 /// Port configuration strategies for process management.
@@ -518,21 +648,16 @@ impl<'de> Deserialize<'de> for PortConfig {
 }
 
 /// Execution mode for processes
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum ExecMode {
     /// Fork mode - single process
+    #[default]
     Fork,
     /// Cluster mode - multiple processes with load balancing
     Cluster,
 }
 
-impl Default for ExecMode {
-    fn default() -> Self {
-        Self::Fork
-    }
-}
-
 impl Default for ProcessConfig {
     fn default() -> Self {
         Self {
@@ -547,19 +672,32 @@ impl Default for ProcessConfig {
             max_restarts: crate::DEFAULT_MAX_RESTARTS,
             min_uptime: crate::DEFAULT_MIN_UPTIME,
             restart_delay: crate::DEFAULT_RESTART_DELAY,
+            exp_backoff_restart_delay: None,
+            max_exp_backoff_restart_delay: crate::DEFAULT_MAX_EXP_BACKOFF_RESTART_DELAY,
+            restart_mode: crate::restart::RestartMode::default(),
+            stop_exit_codes: Vec::new(),
             kill_timeout: crate::DEFAULT_KILL_TIMEOUT,
+            kill_signal: crate::signals::ProcessSignal::Term,
+            kill_chain: Vec::new(),
             max_memory_restart: None,
             out_file: None,
             error_file: None,
             log_file: None,
             pid_file: None,
             watch: false,
+            watch_paths: Vec::new(),
             ignore_watch: Vec::new(),
+            watch_delay: crate::DEFAULT_WATCH_DELAY,
             user: None,
             group: None,
             namespace: "default".to_string(),
             port: None,
             health_check: None,
+            wait_ready: false,
+            wait_timeout: crate::DEFAULT_WAIT_TIMEOUT,
+            log_rotation: None,
+            log_date_format: None,
+            log_format: crate::logs::LogFormat::default(),
         }
     }
 }
@@ -629,18 +767,182 @@ impl ProcessConfigBuilder {
         self
     }
 
+    /// Enable or disable automatic restarts after the process exits
+    pub fn autorestart(mut self, autorestart: bool) -> Self {
+        self.config.autorestart = autorestart;
+        self
+    }
+
+    /// Set how many consecutive unstable restarts are allowed before giving up
+    pub fn max_restarts(mut self, max_restarts: u32) -> Self {
+        self.config.max_restarts = max_restarts;
+        self
+    }
+
+    /// Set how long a run must last to count as stable (in milliseconds)
+    pub fn min_uptime(mut self, min_uptime_ms: u64) -> Self {
+        self.config.min_uptime = min_uptime_ms;
+        self
+    }
+
+    /// Set the delay between an exit and the automatic restart (in milliseconds)
+    pub fn restart_delay(mut self, delay_ms: u64) -> Self {
+        self.config.restart_delay = delay_ms;
+        self
+    }
+
+    /// Restart with an exponential backoff starting at this delay (in milliseconds)
+    pub fn exp_backoff_restart_delay(mut self, initial_delay_ms: u64) -> Self {
+        self.config.exp_backoff_restart_delay = Some(initial_delay_ms);
+        self
+    }
+
+    /// Set the upper bound of the exponential backoff restart delay (in milliseconds)
+    pub fn max_exp_backoff_restart_delay(mut self, max_delay_ms: u64) -> Self {
+        self.config.max_exp_backoff_restart_delay = max_delay_ms;
+        self
+    }
+
+    /// Set when the process is restarted after it exits
+    pub fn restart_mode(mut self, mode: crate::restart::RestartMode) -> Self {
+        self.config.restart_mode = mode;
+        self
+    }
+
+    /// Set exit codes that count as a clean stop and never trigger a restart
+    pub fn stop_exit_codes(mut self, codes: Vec<i32>) -> Self {
+        self.config.stop_exit_codes = codes;
+        self
+    }
+
+    /// Set the time to wait for a graceful stop before force killing (in milliseconds)
+    pub fn kill_timeout(mut self, timeout_ms: u64) -> Self {
+        self.config.kill_timeout = timeout_ms;
+        self
+    }
+
+    /// Set the signal sent to stop the process
+    pub fn kill_signal(mut self, signal: crate::signals::ProcessSignal) -> Self {
+        self.config.kill_signal = signal;
+        self
+    }
+
+    /// Set the signals to escalate through when stopping the process
+    pub fn kill_chain(mut self, steps: Vec<crate::signals::KillStep>) -> Self {
+        self.config.kill_chain = steps;
+        self
+    }
+
     /// Set port configuration
     pub fn port(mut self, port_config: PortConfig) -> Self {
         self.config.port = Some(port_config);
         self
     }
 
+    /// Enable or disable restarting on file changes
+    pub fn watch(mut self, watch: bool) -> Self {
+        self.config.watch = watch;
+        self
+    }
+
+    /// Set the paths to watch (defaults to the working directory)
+    pub fn watch_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.config.watch_paths = paths.into_iter().map(|p| p.into()).collect();
+        self
+    }
+
+    /// Set glob patterns to ignore when watching
+    pub fn ignore_watch<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.config.ignore_watch = patterns.into_iter().map(|s| s.into()).collect();
+        self
+    }
+
+    /// Set the watch debounce delay (in milliseconds)
+    pub fn watch_delay(mut self, delay_ms: u64) -> Self {
+        self.config.watch_delay = delay_ms;
+        self
+    }
+
+    /// Set the user to run the process as
+    pub fn user<S: Into<String>>(mut self, user: S) -> Self {
+        self.config.user = Some(user.into());
+        self
+    }
+
+    /// Set the group to run the process as
+    pub fn group<S: Into<String>>(mut self, group: S) -> Self {
+        self.config.group = Some(group.into());
+        self
+    }
+
     /// Set health check configuration
     pub fn health_check(mut self, health_check_config: crate::health::HealthCheckConfig) -> Self {
         self.config.health_check = Some(health_check_config);
         self
     }
 
+    /// Wait for the first successful health check when starting
+    pub fn wait_ready(mut self, wait_ready: bool) -> Self {
+        self.config.wait_ready = wait_ready;
+        self
+    }
+
+    /// Set how long to wait for the process to become healthy (in milliseconds)
+    pub fn wait_timeout(mut self, timeout_ms: u64) -> Self {
+        self.config.wait_timeout = timeout_ms;
+        self
+    }
+
+    /// Set the stdout log file path
+    pub fn out_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.out_file = Some(path.into());
+        self
+    }
+
+    /// Set the stderr log file path
+    pub fn error_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.error_file = Some(path.into());
+        self
+    }
+
+    /// Set the combined log file path
+    pub fn log_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.log_file = Some(path.into());
+        self
+    }
+
+    /// Set the PID file path
+    pub fn pid_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.pid_file = Some(path.into());
+        self
+    }
+
+    /// Set log rotation configuration
+    pub fn log_rotation(mut self, log_rotation: crate::logs::LogRotationConfig) -> Self {
+        self.config.log_rotation = Some(log_rotation);
+        self
+    }
+
+    /// Set the timestamp format prefixed to each log line
+    pub fn log_date_format<S: Into<String>>(mut self, format: S) -> Self {
+        self.config.log_date_format = Some(format.into());
+        self
+    }
+
+    /// Set the format of log lines
+    pub fn log_format(mut self, format: crate::logs::LogFormat) -> Self {
+        self.config.log_format = format;
+        self
+    }
+
     /// Build the configuration
     pub fn build(self) -> Result<ProcessConfig> {
         if self.config.name.is_empty() {
@@ -670,9 +972,92 @@ impl ProcessConfig {
         if self.instances == 0 {
             return Err(Error::config("Number of instances must be greater than 0"));
         }
+        if self.watch {
+            crate::watcher::IgnoreRules::new(&self.ignore_watch)?;
+        }
+        if let Some(health_check) = &self.health_check {
+            if health_check.enabled && health_check.interval.is_zero() {
+                return Err(Error::config(
+                    "Health check interval must be greater than 0",
+                ));
+            }
+        }
+        if self.exp_backoff_restart_delay == Some(0) {
+            return Err(Error::config(
+                "exp_backoff_restart_delay must be greater than 0",
+            ));
+        }
+        if self.max_exp_backoff_restart_delay == 0 {
+            return Err(Error::config(
+                "max_exp_backoff_restart_delay must be greater than 0",
+            ));
+        }
+        if self.wait_ready && self.ready_check().is_none() {
+            return Err(Error::config("wait_ready requires an enabled health_check"));
+        }
+        if let Some(log_rotation) = &self.log_rotation {
+            log_rotation.validate()?;
+        }
+        if let Some(date_format) = &self.log_date_format {
+            crate::logs::validate_date_format(date_format)?;
+        }
+        if let Some(position) = self
+            .kill_chain
+            .iter()
+            .position(|step| step.signal == crate::signals::ProcessSignal::Kill)
+        {
+            if position + 1 < self.kill_chain.len() {
+                return Err(Error::config("SIGKILL must be the last step of kill_chain"));
+            }
+        }
         Ok(())
     }
 
+    /// Signals sent to stop the process, each with the time to wait for it to exit
+    ///
+    /// This is `kill_chain` with unset timeouts filled in from `kill_timeout`, or
+    /// `kill_signal` alone. SIGKILL always follows when the process outlives it.
+    pub fn stop_sequence(&self) -> Vec<(crate::signals::ProcessSignal, Duration)> {
+        let kill_timeout = Duration::from_millis(self.kill_timeout);
+        if self.kill_chain.is_empty() {
+            return vec![(self.kill_signal, kill_timeout)];
+        }
+        self.kill_chain
+            .iter()
+            .map(|step| {
+                let timeout = step.timeout.map(Duration::from_millis);
+                (step.signal, timeout.unwrap_or(kill_timeout))
+            })
+            .collect()
+    }
+
+    /// Health check that must pass before the process counts as online
+    ///
+    /// Returns `Some` only when `wait_ready` is set and the health check is enabled.
+    pub fn ready_check(&self) -> Option<&crate::health::HealthCheckConfig> {
+        self.health_check
+            .as_ref()
+            .filter(|health_check| self.wait_ready && health_check.enabled)
+    }
+
+    /// Whether `exit` calls for an automatic restart under `autorestart`,
+    /// `stop_exit_codes` and `restart_mode`
+    pub fn should_restart_after(&self, exit: crate::process::ProcessExit) -> bool {
+        let clean_stop = exit
+            .code
+            .is_some_and(|code| self.stop_exit_codes.contains(&code));
+        self.autorestart && !clean_stop && self.restart_mode.should_restart(exit)
+    }
+
+    /// Resolve a configured file path, treating relative paths as relative to `cwd`
+    /// when it is set
+    pub fn resolve_path(&self, path: Option<&PathBuf>) -> Option<PathBuf> {
+        path.map(|path| match &self.cwd {
+            Some(cwd) if path.is_relative() => cwd.join(path),
+            _ => path.clone(),
+        })
+    }
+
     /// Get the effective working directory
     pub fn effective_cwd(&self) -> PathBuf {
         self.cwd
@@ -680,6 +1065,16 @@ impl ProcessConfig {
             .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")))
     }
 
+    /// Get the directories watched for changes when `watch` is enabled
+    pub fn watch_roots(&self) -> Vec<PathBuf> {
+        let cwd = self.effective_cwd();
+        if self.watch_paths.is_empty() {
+            vec![cwd]
+        } else {
+            self.watch_paths.iter().map(|path| cwd.join(path)).collect()
+        }
+    }
+
     /// Check if this configuration uses clustering
     pub fn is_cluster_mode(&self) -> bool {
         self.instances > 1 || self.exec_mode == ExecMode::Cluster
@@ -894,6 +1289,236 @@ mod tests {
         config.exec_mode = ExecMode::Cluster;
         assert!(config.is_cluster_mode());
     }
+
+    #[test]
+    fn test_process_config_builder_with_watch() {
+        let config = ProcessConfig::builder()
+            .name("test-app")
+            .script("node")
+            .watch(true)
+            .watch_paths(vec!["src", "config"])
+            .ignore_watch(vec!["node_modules", "*.log"])
+            .watch_delay(250)
+            .build()
+            .unwrap();
+
+        assert!(config.watch);
+        assert_eq!(
+            config.watch_paths,
+            vec![PathBuf::from("src"), PathBuf::from("config")]
+        );
+        assert_eq!(config.ignore_watch, vec!["node_modules", "*.log"]);
+        assert_eq!(config.watch_delay, 250);
+        assert_eq!(
+            ProcessConfig::default().watch_delay,
+            crate::DEFAULT_WATCH_DELAY
+        );
+    }
+
+    #[test]
+    fn test_process_config_watch_roots() {
+        let mut config = ProcessConfig {
+            cwd: Some(PathBuf::from("/srv/app")),
+            ..ProcessConfig::default()
+        };
+        assert_eq!(config.watch_roots(), vec![PathBuf::from("/srv/app")]);
+
+        config.watch_paths = vec![PathBuf::from("src"), PathBuf::from("/etc/app")];
+        assert_eq!(
+            config.watch_roots(),
+            vec![PathBuf::from("/srv/app/src"), PathBuf::from("/etc/app")]
+        );
+    }
+
+    #[test]
+    fn test_process_config_validate_ignore_watch() {
+        let mut config = ProcessConfig::builder()
+            .name("test-app")
+            .script("node")
+            .ignore_watch(vec!["["])
+            .build()
+            .unwrap();
+
+        // Patterns are only compiled when watching is enabled
+        assert!(config.validate().is_ok());
+
+        config.watch = true;
+        assert!(config.validate().unwrap_err().is_config_error());
+    }
+
+    #[test]
+    fn test_process_config_validate_health_check_interval() {
+        let health_check = crate::health::HealthCheckConfig::http("http://localhost:3000/health")
+            .interval(Duration::ZERO)
+            .enabled(true);
+        let config = ProcessConfig::builder()
+            .name("test-app")
+            .script("node")
+            .health_check(health_check)
+            .build()
+            .unwrap();
+        assert!(config.validate().unwrap_err().is_config_error());
+    }
+
+    #[test]
+    fn test_process_config_stop_sequence() {
+        use crate::signals::{KillStep, ProcessSignal};
+
+        let mut config = ProcessConfig::builder()
+            .name("test-app")
+            .script("nginx")
+            .kill_signal(ProcessSignal::Quit)
+            .kill_timeout(30_000)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.stop_sequence(),
+            vec![(ProcessSignal::Quit, Duration::from_secs(30))]
+        );
+
+        config.kill_chain = vec![
+            KillStep::new(ProcessSignal::Term).timeout(5000),
+            KillStep::new(ProcessSignal::Int),
+        ];
+        assert_eq!(
+            config.stop_sequence(),
+            vec![
+                (ProcessSignal::Term, Duration::from_secs(5)),
+                (ProcessSignal::Int, Duration::from_secs(30)),
+            ]
+        );
+        assert!(config.validate().is_ok());
+
+        config
+            .kill_chain
+            .insert(0, KillStep::new(ProcessSignal::Kill));
+        assert!(config.validate().unwrap_err().is_config_error());
+
+        let config: ProcessConfig = serde_json::from_str(
+            r#"{"name": "app", "script": "nginx", "kill_signal": "QUIT",
+                "kill_chain": [{"signal": "TERM", "timeout": 100}, {"signal": "SIGKILL"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(config.kill_signal, ProcessSignal::Quit);
+        assert_eq!(config.kill_chain.len(), 2);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_process_config_wait_ready() {
+        let mut config = ProcessConfig::builder()
+            .name("test-app")
+            .script("node")
+            .wait_ready(true)
+            .build()
+            .unwrap();
+        assert_eq!(config.wait_timeout, crate::DEFAULT_WAIT_TIMEOUT);
+        assert!(config.ready_check().is_none());
+        assert!(config.validate().unwrap_err().is_config_error());
+
+        config.health_check =
+            Some(crate::health::HealthCheckConfig::http("http://localhost:3000").enabled(true));
+        assert!(config.ready_check().is_some());
+        assert!(config.validate().is_ok());
+
+        config.wait_ready = false;
+        assert!(config.ready_check().is_none());
+    }
+
+    #[test]
+    fn test_process_config_exp_backoff_restart_delay() {
+        let config: ProcessConfig = serde_json::from_str(
+            r#"{"name": "api", "script": "node", "exp_backoff_restart_delay": 100}"#,
+        )
+        .unwrap();
+        assert_eq!(config.exp_backoff_restart_delay, Some(100));
+        assert_eq!(
+            config.max_exp_backoff_restart_delay,
+            crate::DEFAULT_MAX_EXP_BACKOFF_RESTART_DELAY
+        );
+        assert!(config.validate().is_ok());
+
+        let config: ProcessConfig = serde_json::from_str(
+            r#"{"name": "api", "script": "node", "exp_backoff_restart_delay": 100,
+                "max_exp_backoff_restart_delay": 60000}"#,
+        )
+        .unwrap();
+        assert_eq!(config.max_exp_backoff_restart_delay, 60000);
+
+        let config = ProcessConfig::builder()
+            .name("api")
+            .script("node")
+            .max_exp_backoff_restart_delay(0)
+            .build()
+            .unwrap();
+        assert!(config.validate().unwrap_err().is_config_error());
+
+        let config = ProcessConfig::builder()
+            .name("api")
+            .script("node")
+            .exp_backoff_restart_delay(0)
+            .build()
+            .unwrap();
+        assert!(config.validate().unwrap_err().is_config_error());
+    }
+
+    #[test]
+    fn test_resolve_and_instance_paths() {
+        let config = ProcessConfig::builder()
+            .name("api")
+            .script("node")
+            .cwd("/srv/api")
+            .out_file("logs/out.log")
+            .pid_file("/run/api.pid")
+            .build()
+            .unwrap();
+        assert_eq!(
+            config.resolve_path(config.out_file.as_ref()),
+            Some(PathBuf::from("/srv/api/logs/out.log"))
+        );
+        assert_eq!(
+            config.resolve_path(config.pid_file.as_ref()),
+            Some(PathBuf::from("/run/api.pid"))
+        );
+        assert_eq!(config.resolve_path(config.log_file.as_ref()), None);
+
+        assert_eq!(
+            instance_path(Path::new("/var/log/api.out.log"), 2),
+            PathBuf::from("/var/log/api.out-2.log")
+        );
+        assert_eq!(
+            instance_path(Path::new("/run/api"), 0),
+            PathBuf::from("/run/api-0")
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_string() {
+        assert_eq!(
+            parse_duration_string("500ms").unwrap(),
+            Duration::from_millis(500)
+        );
+        assert_eq!(
+            parse_duration_string("30s").unwrap(),
+            Duration::from_secs(30)
+        );
+        assert_eq!(
+            parse_duration_string("2m").unwrap(),
+            Duration::from_secs(120)
+        );
+        assert_eq!(
+            parse_duration_string("1H").unwrap(),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(
+            parse_duration_string("15").unwrap(),
+            Duration::from_secs(15)
+        );
+
+        assert!(parse_duration_string("").is_err());
+        assert!(parse_duration_string("fast").is_err());
+        assert!(parse_duration_string("-5s").is_err());
+    }
 }
 
 /// Ecosystem configuration for loading multiple apps from a config file.
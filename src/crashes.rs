@@ -0,0 +1,358 @@
+//! Crash records for processes that exit abnormally.
+//!
+//! When a managed process dies with a nonzero exit code, is killed by a signal, or
+//! disappears without an exit status (processes restored from PID files), the
+//! process manager writes a [`CrashRecord`] to `~/.pmdaemon/crashes/`. A record
+//! holds the last lines of the process' error and output logs, how it exited, how
+//! long it had been up, the last CPU and memory sample and the effective
+//! configuration, so the cause of a crash can still be found after the next restart
+//! has moved the logs on. Exit codes listed in `stop_exit_codes` are clean stops and
+//! are not recorded.
+//!
+//! Records are kept per process up to [`CRASH_RECORDS_LIMIT`](crate::CRASH_RECORDS_LIMIT),
+//! and are read back through `pmdaemon crashes` and
+//! `GET /api/processes/:id/crashes`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmdaemon::crashes::{CrashRecord, CrashStore};
+//! use pmdaemon::{Process, ProcessConfig, ProcessExit};
+//!
+//! # async fn example() -> pmdaemon::Result<()> {
+//! let dir = tempfile::tempdir()?;
+//! let store = CrashStore::new(dir.path().join("crashes"));
+//!
+//! let config = ProcessConfig::builder()
+//!     .name("worker")
+//!     .script("worker.sh")
+//!     .build()?;
+//! let process = Process::new(config);
+//! let exit = ProcessExit {
+//!     code: Some(1),
+//!     ..Default::default()
+//! };
+//! let mut record = CrashRecord::new(&process, exit, chrono::Duration::seconds(3));
+//! record.stderr_tail = vec!["panicked at src/main.rs:12".to_string()];
+//! store.save(&record).await?;
+//!
+//! let crashes = store.list(Some("worker")).await?;
+//! assert_eq!(crashes.len(), 1);
+//! assert_eq!(store.get(&record.id).await?.exit.code, Some(1));
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::ProcessConfig;
+use crate::error::{Error, Result};
+use crate::process::{Process, ProcessExit, ProcessId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// What was known about a process when it crashed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrashRecord {
+    /// Identifier of the record, which also names its file
+    pub id: String,
+    /// When the crash was recorded
+    pub at: DateTime<Utc>,
+    /// Process that crashed
+    pub process_id: ProcessId,
+    /// Name of the process at the time of the crash
+    pub process_name: String,
+    /// Exit code or terminating signal; both are empty when the exit status of a
+    /// process restored from disk is unknown
+    pub exit: ProcessExit,
+    /// How long the process had been running, in milliseconds
+    pub uptime: u64,
+    /// Last CPU usage sample, in percent
+    pub cpu_usage: f32,
+    /// Last memory usage sample, in bytes
+    pub memory_usage: u64,
+    /// When the CPU and memory sample was taken, if the process was ever sampled
+    pub sampled_at: Option<DateTime<Utc>>,
+    /// Last lines of the error log, oldest first
+    pub stderr_tail: Vec<String>,
+    /// Last lines of the output log, oldest first
+    pub stdout_tail: Vec<String>,
+    /// Configuration the process was running with, including the assigned `PORT`
+    pub config: ProcessConfig,
+}
+
+impl CrashRecord {
+    /// Record the crash of `process` after a run of `uptime`, without log tails
+    pub fn new(process: &Process, exit: ProcessExit, uptime: chrono::Duration) -> Self {
+        let at = Utc::now();
+        Self {
+            id: format!(
+                "{}-{}",
+                process.config.name,
+                at.format("%Y%m%dT%H%M%S%.3fZ")
+            ),
+            at,
+            process_id: process.id,
+            process_name: process.config.name.clone(),
+            exit,
+            uptime: uptime.num_milliseconds().max(0) as u64,
+            cpu_usage: process.monitoring.cpu_usage,
+            memory_usage: process.monitoring.memory_usage,
+            sampled_at: process.monitoring.last_update,
+            stderr_tail: Vec::new(),
+            stdout_tail: Vec::new(),
+            config: process.config.clone(),
+        }
+    }
+
+    /// Whether `exit` is a crash for a process running with `config`: anything but
+    /// exit code 0 or one of its `stop_exit_codes`, unless how it exited is unknown
+    pub fn is_crash(config: &ProcessConfig, exit: ProcessExit) -> bool {
+        match exit.code {
+            Some(code) => code != 0 && !config.stop_exit_codes.contains(&code),
+            None => !exit.unknown,
+        }
+    }
+}
+
+/// Directory of crash records, one JSON file per crash
+#[derive(Debug)]
+pub struct CrashStore {
+    /// Directory holding the records
+    dir: PathBuf,
+    /// Keeps concurrent saves from pruning each other's records
+    write_lock: Mutex<()>,
+}
+
+impl CrashStore {
+    /// Store kept in `dir`; the directory is created with the first record
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Directory holding the records
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Write a crash record, removing the oldest records of the same process beyond
+    /// [`CRASH_RECORDS_LIMIT`](crate::CRASH_RECORDS_LIMIT)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crash directory or record file cannot be written.
+    pub async fn save(&self, record: &CrashRecord) -> Result<PathBuf> {
+        let _guard = self.write_lock.lock().await;
+        fs::create_dir_all(&self.dir).await.map_err(|e| {
+            Error::config(format!(
+                "Failed to create crash directory {:?}: {}",
+                self.dir, e
+            ))
+        })?;
+
+        let path = self.dir.join(format!("{}.json", record.id));
+        let json = serde_json::to_string_pretty(record)?;
+        fs::write(&path, json).await.map_err(|e| {
+            Error::config(format!("Failed to write crash record {:?}: {}", path, e))
+        })?;
+
+        let mut older: Vec<CrashRecord> = self
+            .read_all()
+            .await?
+            .into_iter()
+            .filter(|other| other.process_name == record.process_name)
+            .collect();
+        let excess = older.len().saturating_sub(crate::CRASH_RECORDS_LIMIT);
+        for stale in older.drain(..excess) {
+            let stale_path = self.dir.join(format!("{}.json", stale.id));
+            if let Err(e) = fs::remove_file(&stale_path).await {
+                warn!("Failed to remove old crash record {:?}: {}", stale_path, e);
+            }
+        }
+        Ok(path)
+    }
+
+    /// Crash records, oldest first, optionally only those of `process` (a name, an
+    /// ID or the base name of a cluster)
+    ///
+    /// Records of deleted processes are included. Files that cannot be parsed are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the crash directory exists but cannot be read.
+    pub async fn list(&self, process: Option<&str>) -> Result<Vec<CrashRecord>> {
+        Ok(self
+            .read_all()
+            .await?
+            .into_iter()
+            .filter(|record| {
+                process.is_none_or(|process| {
+                    crate::events::matches_process(process, record.process_id, &record.process_name)
+                })
+            })
+            .collect())
+    }
+
+    /// Read a single crash record by its ID
+    ///
+    /// # Errors
+    ///
+    /// Returns an invalid argument error if there is no record with that ID.
+    pub async fn get(&self, id: &str) -> Result<CrashRecord> {
+        if id.is_empty() || id.contains(['/', '\\']) || id.starts_with('.') {
+            return Err(Error::invalid_argument(format!(
+                "Invalid crash record ID '{}'",
+                id
+            )));
+        }
+        let path = self.dir.join(format!("{}.json", id));
+        let content = match fs::read_to_string(&path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Err(Error::invalid_argument(format!("No crash record '{}'", id)))
+            }
+            Err(e) => {
+                return Err(Error::config(format!(
+                    "Failed to read crash record {:?}: {}",
+                    path, e
+                )))
+            }
+        };
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Every readable record, oldest first
+    async fn read_all(&self) -> Result<Vec<CrashRecord>> {
+        let mut entries = match fs::read_dir(&self.dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => {
+                return Err(Error::config(format!(
+                    "Failed to read crash directory {:?}: {}",
+                    self.dir, e
+                )))
+            }
+        };
+
+        let mut records = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(&path).await else {
+                continue;
+            };
+            match serde_json::from_str::<CrashRecord>(&content) {
+                Ok(record) => records.push(record),
+                Err(e) => warn!("Skipping unreadable crash record {:?}: {}", path, e),
+            }
+        }
+        records.sort_by(|a, b| a.at.cmp(&b.at).then_with(|| a.id.cmp(&b.id)));
+        Ok(records)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn crashed(name: &str, code: i32) -> CrashRecord {
+        let config = ProcessConfig::builder()
+            .name(name)
+            .script("false")
+            .build()
+            .unwrap();
+        let process = Process::new(config);
+        CrashRecord::new(
+            &process,
+            ProcessExit {
+                code: Some(code),
+                ..Default::default()
+            },
+            chrono::Duration::milliseconds(1500),
+        )
+    }
+
+    #[test]
+    fn test_is_crash() {
+        let mut config = ProcessConfig::builder()
+            .name("job")
+            .script("job.sh")
+            .build()
+            .unwrap();
+        config.stop_exit_codes = vec![3];
+
+        let exit = |code, signal| ProcessExit {
+            code,
+            signal,
+            unknown: false,
+        };
+        assert!(!CrashRecord::is_crash(&config, exit(Some(0), None)));
+        assert!(!CrashRecord::is_crash(&config, exit(Some(3), None)));
+        assert!(CrashRecord::is_crash(&config, exit(Some(1), None)));
+        assert!(CrashRecord::is_crash(
+            &config,
+            exit(None, Some(crate::ProcessSignal::Kill))
+        ));
+        assert!(CrashRecord::is_crash(&config, exit(None, None)));
+        assert!(!CrashRecord::is_crash(&config, ProcessExit::unknown()));
+    }
+
+    #[tokio::test]
+    async fn test_save_list_and_get() {
+        let dir = TempDir::new().unwrap();
+        let store = CrashStore::new(dir.path().join("crashes"));
+        assert!(store.list(None).await.unwrap().is_empty());
+
+        let mut web = crashed("web-0", 1);
+        web.stderr_tail = vec!["boom".to_string()];
+        store.save(&web).await.unwrap();
+        store.save(&crashed("worker", 2)).await.unwrap();
+
+        assert_eq!(store.list(None).await.unwrap().len(), 2);
+        let listed = store.list(Some("web")).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].id, web.id);
+
+        let saved = store.get(&web.id).await.unwrap();
+        assert_eq!(saved.process_name, "web-0");
+        assert_eq!(saved.exit.code, Some(1));
+        assert_eq!(saved.uptime, 1500);
+        assert_eq!(saved.stderr_tail, vec!["boom".to_string()]);
+
+        assert!(store.get("missing").await.is_err());
+        assert!(store.get("../events").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_records_are_pruned_per_process() {
+        let dir = TempDir::new().unwrap();
+        let store = CrashStore::new(dir.path().to_path_buf());
+
+        store.save(&crashed("other", 1)).await.unwrap();
+        let mut first = None;
+        for i in 0..=crate::CRASH_RECORDS_LIMIT {
+            let mut record = crashed("flaky", 1);
+            record.id = format!("flaky-{:03}", i);
+            record.at = Utc::now() + chrono::Duration::seconds(i as i64);
+            store.save(&record).await.unwrap();
+            first.get_or_insert(record.id);
+        }
+
+        let flaky = store.list(Some("flaky")).await.unwrap();
+        assert_eq!(flaky.len(), crate::CRASH_RECORDS_LIMIT);
+        assert!(flaky
+            .iter()
+            .all(|record| Some(&record.id) != first.as_ref()));
+        assert_eq!(store.list(Some("other")).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,1325 @@
+//! Supervisor daemon and local control socket.
+//!
+//! Without a daemon, every `pmdaemon` CLI invocation builds its own [`ProcessManager`],
+//! performs one operation and exits, so nothing watches the managed processes in between.
+//! `pmdaemon daemon` instead keeps a single manager alive, runs the monitoring loop
+//! continuously, restarts watched processes on file changes and serves requests from
+//! other CLI invocations over a Unix domain socket in the configuration directory.
+//!
+//! ## Protocol
+//!
+//! The protocol is deliberately simple: each request and each response is a single
+//! line of JSON. A connection may carry any number of request/response pairs.
+//!
+//! ```text
+//! -> {"command":"list"}
+//! <- {"type":"processes","processes":[...]}
+//! ```
+//!
+//! The one exception is following logs: after the reply to a `tail_logs` request
+//! with `follow` set, the daemon keeps sending `log_line` messages on the connection
+//! until the client closes it.
+//!
+//! ## Examples
+//!
+//! ```rust,no_run
+//! use pmdaemon::daemon::DaemonClient;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! // Talk to a running daemon, if there is one
+//! if let Some(mut client) = DaemonClient::connect_default().await? {
+//!     for process in client.list().await? {
+//!         println!("{} ({})", process.name, process.state);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::{PortConfig, ProcessConfig};
+use crate::crashes::CrashRecord;
+use crate::error::{Error, Result};
+use crate::events::{Event, EventFilter};
+use crate::logs::{LogEntry, LogLine, LogSearch};
+use crate::manager::ProcessManager;
+use crate::process::{ProcessId, ProcessStatus, StopReport};
+use crate::signals::ProcessSignal;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+#[cfg(unix)]
+use tracing::{debug, error, info, warn};
+
+/// Default interval between monitoring passes in the daemon
+pub const DEFAULT_MONITOR_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Request sent from a CLI client to the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum DaemonRequest {
+    /// Check that the daemon is alive
+    Ping,
+    /// Start a new process (or cluster) from a configuration
+    Start {
+        /// Process configuration to start
+        config: Box<ProcessConfig>,
+    },
+    /// Stop a process by name or ID
+    Stop {
+        /// Process name or ID
+        identifier: String,
+    },
+    /// Restart a process by name or ID
+    Restart {
+        /// Process name or ID
+        identifier: String,
+        /// Port to restart the process on instead of its current one
+        #[serde(default)]
+        port: Option<PortConfig>,
+        /// Wait for the health check even if the process does not use `wait_ready`
+        #[serde(default)]
+        wait_ready: bool,
+    },
+    /// Reload a process or cluster by name or ID
+    Reload {
+        /// Process name or ID, or cluster base name
+        identifier: String,
+        /// Port to reload a single process on instead of its current one
+        #[serde(default)]
+        port: Option<PortConfig>,
+    },
+    /// Stop and delete a process by name or ID
+    Delete {
+        /// Process name or ID
+        identifier: String,
+    },
+    /// Stop and delete every process
+    DeleteAll,
+    /// Stop and delete every process in a state
+    DeleteByStatus {
+        /// State name, such as `stopped` or `errored`
+        status: String,
+    },
+    /// List all managed processes
+    List,
+    /// Get detailed status of a single process
+    Info {
+        /// Process name or ID
+        identifier: String,
+    },
+    /// Read the last lines of a process' logs
+    Logs {
+        /// Process name or ID
+        identifier: String,
+        /// Number of lines to return per stream
+        lines: usize,
+    },
+    /// Send a signal to a process, a cluster or a namespace
+    Signal {
+        /// Process name or ID, cluster base name, or namespace
+        identifier: String,
+        /// Signal to send
+        signal: ProcessSignal,
+        /// Whether `identifier` names a namespace
+        #[serde(default)]
+        namespace: bool,
+    },
+    /// Read lifecycle events from the journal
+    Events {
+        /// Which events to read
+        #[serde(default)]
+        filter: EventFilter,
+    },
+    /// Search the logs of the processes matching a pattern, optionally following them
+    TailLogs {
+        /// Process name or ID, cluster base name, namespace or glob; all processes
+        /// when absent
+        #[serde(default)]
+        pattern: Option<String>,
+        /// Which lines to return
+        #[serde(default)]
+        search: LogSearch,
+        /// Keep sending new matching lines after the reply
+        #[serde(default)]
+        follow: bool,
+    },
+    /// Read crash records
+    Crashes {
+        /// Only the records of this process name or ID, or cluster base name
+        #[serde(default)]
+        process: Option<String>,
+    },
+    /// Read a single crash record
+    Crash {
+        /// Crash ID
+        id: String,
+    },
+}
+
+/// Response sent from the daemon back to a CLI client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DaemonResponse {
+    /// Reply to [`DaemonRequest::Ping`]
+    Pong {
+        /// Daemon version
+        version: String,
+        /// System PID of the daemon
+        pid: u32,
+    },
+    /// A process was started
+    Started {
+        /// ID of the started process (first instance for clusters)
+        id: ProcessId,
+    },
+    /// A process was stopped
+    Stopped {
+        /// Identifier that was stopped
+        identifier: String,
+        /// What happened while stopping it
+        #[serde(default)]
+        report: StopReport,
+    },
+    /// A process was restarted (and is healthy, if it was waited for)
+    Restarted {
+        /// Identifier that was restarted
+        identifier: String,
+    },
+    /// A process or cluster was reloaded
+    Reloaded {
+        /// Identifier that was reloaded
+        identifier: String,
+    },
+    /// Processes were stopped and deleted
+    Deleted {
+        /// Number of deleted processes
+        count: usize,
+    },
+    /// Process listing
+    Processes {
+        /// Status of every managed process
+        processes: Vec<ProcessStatus>,
+    },
+    /// Status of a single process
+    Process {
+        /// Process status
+        process: Box<ProcessStatus>,
+    },
+    /// Log output
+    Logs {
+        /// Formatted log content
+        content: String,
+    },
+    /// A signal was sent
+    Signalled {
+        /// Names of the processes that received the signal
+        processes: Vec<String>,
+    },
+    /// Lifecycle events, oldest first
+    Events {
+        /// Events matching the filter
+        events: Vec<Event>,
+    },
+    /// Log lines found by a search, oldest first
+    LogEntries {
+        /// Processes whose logs were searched, sorted by name
+        processes: Vec<ProcessStatus>,
+        /// Matching lines of all processes, interleaved by time
+        entries: Vec<LogEntry>,
+    },
+    /// A new line of a followed log, with JSON rendered as text
+    LogLine {
+        /// The line
+        line: LogLine,
+    },
+    /// Crash records, oldest first
+    Crashes {
+        /// Matching crash records
+        crashes: Vec<CrashRecord>,
+    },
+    /// A single crash record
+    Crash {
+        /// The crash record
+        crash: Box<CrashRecord>,
+    },
+    /// The request failed
+    Error {
+        /// Error message
+        message: String,
+    },
+}
+
+/// Long-running supervisor that owns a [`ProcessManager`] and serves the control socket.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use pmdaemon::{daemon::Daemon, ProcessManager};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let manager = ProcessManager::new().await?;
+/// let socket_path = ProcessManager::get_socket_path()?;
+///
+/// // Runs until SIGINT/SIGTERM
+/// Daemon::new(manager, socket_path).run().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Daemon {
+    /// Shared process manager
+    manager: Arc<RwLock<ProcessManager>>,
+    /// Path of the control socket
+    socket_path: PathBuf,
+    /// Interval between monitoring passes
+    monitor_interval: Duration,
+}
+
+impl Daemon {
+    /// Create a new daemon for the given manager and socket path
+    pub fn new(manager: ProcessManager, socket_path: PathBuf) -> Self {
+        Self {
+            manager: Arc::new(RwLock::new(manager)),
+            socket_path,
+            monitor_interval: DEFAULT_MONITOR_INTERVAL,
+        }
+    }
+
+    /// Set the interval between monitoring passes
+    pub fn monitor_interval(mut self, interval: Duration) -> Self {
+        self.monitor_interval = interval;
+        self
+    }
+
+    /// Get the shared process manager
+    pub fn manager(&self) -> Arc<RwLock<ProcessManager>> {
+        Arc::clone(&self.manager)
+    }
+
+    /// Get the control socket path
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Run the daemon until SIGINT or SIGTERM is received.
+    ///
+    /// Binds the control socket (replacing a stale socket file left behind by a
+    /// previous daemon), starts the monitoring loop and serves client connections.
+    /// The socket file is removed on shutdown. Managed processes keep running after
+    /// the daemon exits, as they do after a regular CLI invocation.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if another daemon is already listening on the socket or
+    /// if the socket cannot be bound.
+    #[cfg(unix)]
+    pub async fn run(self) -> Result<()> {
+        use tokio::net::UnixListener;
+        use tokio::signal::unix::{signal, SignalKind};
+
+        if self.socket_path.exists() {
+            if DaemonClient::connect(&self.socket_path).await?.is_some() {
+                return Err(Error::resource_not_available(format!(
+                    "daemon socket {} (another daemon is already running)",
+                    self.socket_path.display()
+                )));
+            }
+            debug!("Removing stale daemon socket {:?}", self.socket_path);
+            std::fs::remove_file(&self.socket_path)?;
+        }
+
+        let listener = UnixListener::bind(&self.socket_path).map_err(|e| {
+            Error::resource_not_available(format!(
+                "daemon socket {}: {}",
+                self.socket_path.display(),
+                e
+            ))
+        })?;
+
+        // Only the owning user may control the daemon
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&self.socket_path, std::fs::Permissions::from_mode(0o600))?;
+        }
+
+        info!("Daemon listening on {:?}", self.socket_path);
+
+        let monitor_manager = self.manager();
+        let monitor_interval = self.monitor_interval;
+        let monitor_task = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(monitor_interval);
+            loop {
+                ticker.tick().await;
+                monitor_manager.read().await.run_monitoring_cycle().await;
+            }
+        });
+
+        // Restart crashed processes as soon as they exit
+        let exit_task = self
+            .manager
+            .read()
+            .await
+            .take_exit_events()
+            .map(|events| tokio::spawn(crate::manager::run_exit_loop(self.manager(), events)));
+
+        // Restart processes whose watched files change
+        let watch_task = {
+            let manager = self.manager.read().await;
+            manager.resume_watching().await;
+            manager.take_watch_events().map(|events| {
+                tokio::spawn(crate::watcher::run_restart_loop(self.manager(), events))
+            })
+        };
+
+        // Run health checks and act on unhealthy processes
+        let health_task = {
+            let manager = self.manager.read().await;
+            manager.resume_health_checks().await;
+            manager
+                .take_health_events()
+                .map(|events| tokio::spawn(crate::health::run_health_loop(self.manager(), events)))
+        };
+
+        let mut sigterm = signal(SignalKind::terminate())?;
+
+        loop {
+            tokio::select! {
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => {
+                        let manager = self.manager();
+                        tokio::spawn(async move {
+                            if let Err(e) = handle_connection(stream, manager).await {
+                                warn!("Daemon client connection error: {}", e);
+                            }
+                        });
+                    }
+                    Err(e) => error!("Failed to accept daemon connection: {}", e),
+                },
+                _ = tokio::signal::ctrl_c() => {
+                    info!("Received SIGINT, shutting down daemon");
+                    break;
+                }
+                _ = sigterm.recv() => {
+                    info!("Received SIGTERM, shutting down daemon");
+                    break;
+                }
+            }
+        }
+
+        monitor_task.abort();
+        if let Some(exit_task) = exit_task {
+            exit_task.abort();
+        }
+        if let Some(watch_task) = watch_task {
+            watch_task.abort();
+        }
+        if let Some(health_task) = health_task {
+            health_task.abort();
+        }
+        if let Err(e) = std::fs::remove_file(&self.socket_path) {
+            warn!("Failed to remove daemon socket: {}", e);
+        }
+
+        Ok(())
+    }
+
+    /// Run the daemon (unsupported on this platform)
+    #[cfg(not(unix))]
+    pub async fn run(self) -> Result<()> {
+        Err(Error::resource_not_available(
+            "daemon mode (requires Unix domain sockets)",
+        ))
+    }
+}
+
+/// Serve requests on a single client connection until it is closed
+#[cfg(unix)]
+async fn handle_connection(
+    stream: tokio::net::UnixStream,
+    manager: Arc<RwLock<ProcessManager>>,
+) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match serde_json::from_str::<DaemonRequest>(&line) {
+            Ok(DaemonRequest::TailLogs {
+                pattern,
+                search,
+                follow: true,
+            }) => {
+                // Following takes over the connection until the client closes it
+                return follow_logs(&manager, pattern, search, &mut writer, lines).await;
+            }
+            Ok(request) => {
+                debug!("Daemon request: {:?}", request);
+                dispatch(&manager, request).await
+            }
+            Err(e) => DaemonResponse::Error {
+                message: format!("Invalid request: {}", e),
+            },
+        };
+        send(&mut writer, &response).await?;
+    }
+
+    Ok(())
+}
+
+/// Write a single response line
+#[cfg(unix)]
+async fn send(
+    writer: &mut tokio::net::unix::OwnedWriteHalf,
+    response: &DaemonResponse,
+) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut payload = serde_json::to_string(response)?;
+    payload.push('\n');
+    writer.write_all(payload.as_bytes()).await?;
+    Ok(())
+}
+
+/// Reply to a `tail_logs` request with `follow` set: send the matching lines so far,
+/// then each new matching line until the client closes the connection
+#[cfg(unix)]
+async fn follow_logs(
+    manager: &RwLock<ProcessManager>,
+    pattern: Option<String>,
+    search: LogSearch,
+    writer: &mut tokio::net::unix::OwnedWriteHalf,
+    mut requests: tokio::io::Lines<tokio::io::BufReader<tokio::net::unix::OwnedReadHalf>>,
+) -> Result<()> {
+    use crate::logs::{humanize_json_line, LogFormat};
+    use futures_util::stream::{self, StreamExt};
+
+    // Subscribe before reading the history so that no line falls in between
+    let tail = async {
+        let manager = manager.read().await;
+        let processes = manager.match_processes(pattern.as_deref()).await?;
+        let mut subscriptions = Vec::new();
+        for process in &processes {
+            subscriptions.push(
+                manager
+                    .subscribe_process_logs(&process.id.to_string())
+                    .await?,
+            );
+        }
+        let entries = manager.search_processes_logs(&processes, &search).await?;
+        Ok::<_, Error>((processes, entries, subscriptions))
+    };
+    let (processes, entries, subscriptions) = match tail.await {
+        Ok(tail) => tail,
+        Err(e) => {
+            let response = DaemonResponse::Error {
+                message: e.to_string(),
+            };
+            return send(writer, &response).await;
+        }
+    };
+    send(writer, &DaemonResponse::LogEntries { processes, entries }).await?;
+
+    let formats: std::collections::HashMap<ProcessId, LogFormat> = subscriptions
+        .iter()
+        .map(|subscription| (subscription.process_id(), subscription.format()))
+        .collect();
+    let mut lines = stream::select_all(subscriptions.into_iter().map(|subscription| {
+        stream::unfold(subscription, |mut subscription| async move {
+            let line = subscription.next().await?;
+            Some((line, subscription))
+        })
+        .boxed()
+    }));
+    loop {
+        tokio::select! {
+            line = lines.next() => {
+                let Some(mut line) = line else {
+                    return Ok(());
+                };
+                let format = formats[&line.process_id];
+                if !search.matches(&line, format) {
+                    continue;
+                }
+                if format == LogFormat::Json {
+                    line.line = humanize_json_line(&line.line);
+                }
+                send(writer, &DaemonResponse::LogLine { line }).await?;
+            }
+            // Anything the client sends is ignored; it is done when it hangs up
+            request = requests.next_line() => {
+                if !matches!(request, Ok(Some(_))) {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+/// Execute a request against the shared manager
+#[cfg(unix)]
+async fn dispatch(manager: &RwLock<ProcessManager>, request: DaemonRequest) -> DaemonResponse {
+    let result = match request {
+        DaemonRequest::Ping => Ok(DaemonResponse::Pong {
+            version: crate::VERSION.to_string(),
+            pid: std::process::id(),
+        }),
+        DaemonRequest::Start { config } => start(manager, *config).await,
+        DaemonRequest::Stop { identifier } => manager
+            .read()
+            .await
+            .stop(&identifier)
+            .await
+            .map(|report| DaemonResponse::Stopped { identifier, report }),
+        DaemonRequest::Restart {
+            identifier,
+            port,
+            wait_ready,
+        } => restart(manager, identifier, port, wait_ready).await,
+        DaemonRequest::Reload { identifier, port } => manager
+            .read()
+            .await
+            .reload_with_port(&identifier, port)
+            .await
+            .map(|()| DaemonResponse::Reloaded { identifier }),
+        DaemonRequest::Delete { identifier } => manager
+            .read()
+            .await
+            .delete(&identifier)
+            .await
+            .map(|()| DaemonResponse::Deleted { count: 1 }),
+        DaemonRequest::DeleteAll => manager
+            .read()
+            .await
+            .delete_all()
+            .await
+            .map(|count| DaemonResponse::Deleted { count }),
+        DaemonRequest::DeleteByStatus { status } => manager
+            .read()
+            .await
+            .delete_by_status(&status)
+            .await
+            .map(|count| DaemonResponse::Deleted { count }),
+        DaemonRequest::List => manager
+            .read()
+            .await
+            .list()
+            .await
+            .map(|processes| DaemonResponse::Processes { processes }),
+        DaemonRequest::Info { identifier } => manager
+            .read()
+            .await
+            .get_process_info(&identifier)
+            .await
+            .map(|process| DaemonResponse::Process {
+                process: Box::new(process),
+            }),
+        DaemonRequest::Logs { identifier, lines } => manager
+            .read()
+            .await
+            .get_logs(&identifier, lines)
+            .await
+            .map(|content| DaemonResponse::Logs { content }),
+        DaemonRequest::Signal {
+            identifier,
+            signal,
+            namespace,
+        } => {
+            let manager = manager.read().await;
+            let signalled = if namespace {
+                manager.signal_namespace(&identifier, signal).await
+            } else {
+                manager.signal(&identifier, signal).await
+            };
+            signalled.map(|processes| DaemonResponse::Signalled { processes })
+        }
+        DaemonRequest::Events { filter } => manager
+            .read()
+            .await
+            .events(&filter)
+            .await
+            .map(|events| DaemonResponse::Events { events }),
+        DaemonRequest::TailLogs {
+            pattern, search, ..
+        } => {
+            let manager = manager.read().await;
+            match manager.match_processes(pattern.as_deref()).await {
+                Ok(processes) => manager
+                    .search_processes_logs(&processes, &search)
+                    .await
+                    .map(|entries| DaemonResponse::LogEntries { processes, entries }),
+                Err(e) => Err(e),
+            }
+        }
+        DaemonRequest::Crashes { process } => manager
+            .read()
+            .await
+            .crashes(process.as_deref())
+            .await
+            .map(|crashes| DaemonResponse::Crashes { crashes }),
+        DaemonRequest::Crash { id } => {
+            manager
+                .read()
+                .await
+                .crash(&id)
+                .await
+                .map(|crash| DaemonResponse::Crash {
+                    crash: Box::new(crash),
+                })
+        }
+    };
+
+    result.unwrap_or_else(|e| DaemonResponse::Error {
+        message: e.to_string(),
+    })
+}
+
+/// Start a process, waiting for readiness without holding the manager lock
+#[cfg(unix)]
+async fn start(manager: &RwLock<ProcessManager>, config: ProcessConfig) -> Result<DaemonResponse> {
+    let wait_ready = config.wait_ready;
+    let process_ids = manager.read().await.launch(config).await?;
+
+    if wait_ready {
+        for &process_id in &process_ids {
+            wait_until_ready(manager, process_id).await?;
+        }
+    }
+
+    Ok(DaemonResponse::Started { id: process_ids[0] })
+}
+
+/// Restart a process, waiting for readiness without holding the manager lock when
+/// asked to or when the process uses `wait_ready`
+#[cfg(unix)]
+async fn restart(
+    manager: &RwLock<ProcessManager>,
+    identifier: String,
+    port: Option<PortConfig>,
+    wait_ready: bool,
+) -> Result<DaemonResponse> {
+    let info = {
+        let manager = manager.read().await;
+        manager.restart_with_port(&identifier, port).await?;
+        manager.get_process_info(&identifier).await?
+    };
+
+    // Processes configured with `wait_ready` come back in the starting state
+    if wait_ready || info.state == crate::process::ProcessState::Starting {
+        wait_until_ready(manager, info.id).await?;
+    }
+
+    Ok(DaemonResponse::Restarted { identifier })
+}
+
+/// Wait for a process to become healthy, taking the manager lock only briefly
+#[cfg(unix)]
+async fn wait_until_ready(manager: &RwLock<ProcessManager>, process_id: ProcessId) -> Result<()> {
+    let (health_check, timeout) = manager.read().await.begin_wait_ready(process_id).await?;
+    let result = crate::health::wait_until_healthy(health_check, timeout, || async move {
+        manager.read().await.is_starting(process_id).await
+    })
+    .await;
+    manager
+        .read()
+        .await
+        .finish_wait_ready(process_id, result)
+        .await
+}
+
+/// Client side of the daemon control socket.
+///
+/// Used by the CLI to forward commands to a running daemon. Connecting returns
+/// `None` when no daemon is listening, so callers can fall back to an in-process
+/// [`ProcessManager`].
+pub struct DaemonClient {
+    #[cfg(unix)]
+    stream: tokio::io::BufReader<tokio::net::UnixStream>,
+}
+
+impl DaemonClient {
+    /// Connect to the daemon listening on the default socket path
+    pub async fn connect_default() -> Result<Option<Self>> {
+        Self::connect(&ProcessManager::get_socket_path()?).await
+    }
+
+    /// Connect to a daemon listening on `socket_path`.
+    ///
+    /// Returns `Ok(None)` if the socket does not exist or nothing is listening on it
+    /// (for example a stale socket left behind by a daemon that was killed).
+    #[cfg(unix)]
+    pub async fn connect(socket_path: &Path) -> Result<Option<Self>> {
+        if !socket_path.exists() {
+            return Ok(None);
+        }
+
+        match tokio::net::UnixStream::connect(socket_path).await {
+            Ok(stream) => Ok(Some(Self {
+                stream: tokio::io::BufReader::new(stream),
+            })),
+            Err(e) => {
+                debug!("No daemon listening on {:?}: {}", socket_path, e);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Connect to a daemon (never available on this platform)
+    #[cfg(not(unix))]
+    pub async fn connect(_socket_path: &Path) -> Result<Option<Self>> {
+        Ok(None)
+    }
+
+    /// Send a request and wait for the response.
+    ///
+    /// [`DaemonResponse::Error`] replies are returned as-is; the typed helpers
+    /// below convert them into [`Error::Daemon`].
+    #[cfg(unix)]
+    pub async fn request(&mut self, request: &DaemonRequest) -> Result<DaemonResponse> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut payload = serde_json::to_string(request)?;
+        payload.push('\n');
+        self.stream.get_mut().write_all(payload.as_bytes()).await?;
+
+        self.receive()
+            .await?
+            .ok_or_else(|| Error::daemon("connection closed by daemon"))
+    }
+
+    /// Read the next message from the daemon, or `None` once it closed the connection
+    #[cfg(unix)]
+    async fn receive(&mut self) -> Result<Option<DaemonResponse>> {
+        use tokio::io::AsyncBufReadExt;
+
+        let mut line = String::new();
+        if self.stream.read_line(&mut line).await? == 0 {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&line)?))
+    }
+
+    /// Send a request (never available on this platform)
+    #[cfg(not(unix))]
+    pub async fn request(&mut self, _request: &DaemonRequest) -> Result<DaemonResponse> {
+        Err(Error::daemon(
+            "daemon mode is not supported on this platform",
+        ))
+    }
+
+    /// Read the next message (never available on this platform)
+    #[cfg(not(unix))]
+    async fn receive(&mut self) -> Result<Option<DaemonResponse>> {
+        Err(Error::daemon(
+            "daemon mode is not supported on this platform",
+        ))
+    }
+
+    /// Check that the daemon is alive, returning its version and PID
+    pub async fn ping(&mut self) -> Result<(String, u32)> {
+        match self.request(&DaemonRequest::Ping).await? {
+            DaemonResponse::Pong { version, pid } => Ok((version, pid)),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Start a process through the daemon
+    pub async fn start(&mut self, config: ProcessConfig) -> Result<ProcessId> {
+        let request = DaemonRequest::Start {
+            config: Box::new(config),
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Started { id } => Ok(id),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Stop a process through the daemon
+    pub async fn stop(&mut self, identifier: &str) -> Result<StopReport> {
+        let request = DaemonRequest::Stop {
+            identifier: identifier.to_string(),
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Stopped { report, .. } => Ok(report),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Restart a process through the daemon, waiting for it to become healthy when
+    /// `wait_ready` is set or the process uses `wait_ready`
+    pub async fn restart(
+        &mut self,
+        identifier: &str,
+        port: Option<PortConfig>,
+        wait_ready: bool,
+    ) -> Result<()> {
+        let request = DaemonRequest::Restart {
+            identifier: identifier.to_string(),
+            port,
+            wait_ready,
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Restarted { .. } => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Reload a process or cluster through the daemon
+    pub async fn reload(&mut self, identifier: &str, port: Option<PortConfig>) -> Result<()> {
+        let request = DaemonRequest::Reload {
+            identifier: identifier.to_string(),
+            port,
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Reloaded { .. } => Ok(()),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Stop and delete a process through the daemon
+    pub async fn delete(&mut self, identifier: &str) -> Result<()> {
+        let request = DaemonRequest::Delete {
+            identifier: identifier.to_string(),
+        };
+        self.deleted(&request).await.map(|_| ())
+    }
+
+    /// Stop and delete every process through the daemon, returning how many were deleted
+    pub async fn delete_all(&mut self) -> Result<usize> {
+        self.deleted(&DaemonRequest::DeleteAll).await
+    }
+
+    /// Stop and delete every process in state `status` through the daemon, returning
+    /// how many were deleted
+    pub async fn delete_by_status(&mut self, status: &str) -> Result<usize> {
+        let request = DaemonRequest::DeleteByStatus {
+            status: status.to_string(),
+        };
+        self.deleted(&request).await
+    }
+
+    /// Send a delete request, returning how many processes were deleted
+    async fn deleted(&mut self, request: &DaemonRequest) -> Result<usize> {
+        match self.request(request).await? {
+            DaemonResponse::Deleted { count } => Ok(count),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Read lifecycle events from the daemon's journal
+    pub async fn events(&mut self, filter: &EventFilter) -> Result<Vec<Event>> {
+        let request = DaemonRequest::Events {
+            filter: filter.clone(),
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Events { events } => Ok(events),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Send a signal through the daemon
+    ///
+    /// Returns the names of the processes that received it.
+    pub async fn signal(
+        &mut self,
+        identifier: &str,
+        signal: ProcessSignal,
+        namespace: bool,
+    ) -> Result<Vec<String>> {
+        let request = DaemonRequest::Signal {
+            identifier: identifier.to_string(),
+            signal,
+            namespace,
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Signalled { processes } => Ok(processes),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// List processes managed by the daemon
+    pub async fn list(&mut self) -> Result<Vec<ProcessStatus>> {
+        match self.request(&DaemonRequest::List).await? {
+            DaemonResponse::Processes { processes } => Ok(processes),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Get the status of a single process managed by the daemon
+    pub async fn info(&mut self, identifier: &str) -> Result<ProcessStatus> {
+        let request = DaemonRequest::Info {
+            identifier: identifier.to_string(),
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Process { process } => Ok(*process),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Get the last `lines` log lines of a process through the daemon
+    pub async fn logs(&mut self, identifier: &str, lines: usize) -> Result<String> {
+        let request = DaemonRequest::Logs {
+            identifier: identifier.to_string(),
+            lines,
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Logs { content } => Ok(content),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Search the logs of the processes matching `pattern` through the daemon,
+    /// returning those processes and the matching lines.
+    ///
+    /// With `follow`, the connection then carries the new matching lines; read them
+    /// with [`next_log_line`](Self::next_log_line).
+    pub async fn tail_logs(
+        &mut self,
+        pattern: Option<&str>,
+        search: &LogSearch,
+        follow: bool,
+    ) -> Result<(Vec<ProcessStatus>, Vec<LogEntry>)> {
+        let request = DaemonRequest::TailLogs {
+            pattern: pattern.map(str::to_string),
+            search: search.clone(),
+            follow,
+        };
+        match self.request(&request).await? {
+            DaemonResponse::LogEntries { processes, entries } => Ok((processes, entries)),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Wait for the next line of logs followed with [`tail_logs`](Self::tail_logs),
+    /// or `None` once the daemon closed the connection
+    pub async fn next_log_line(&mut self) -> Result<Option<LogLine>> {
+        match self.receive().await? {
+            Some(DaemonResponse::LogLine { line }) => Ok(Some(line)),
+            Some(other) => Err(unexpected(other)),
+            None => Ok(None),
+        }
+    }
+
+    /// Read crash records from the daemon, optionally only those of `process`
+    pub async fn crashes(&mut self, process: Option<&str>) -> Result<Vec<CrashRecord>> {
+        let request = DaemonRequest::Crashes {
+            process: process.map(str::to_string),
+        };
+        match self.request(&request).await? {
+            DaemonResponse::Crashes { crashes } => Ok(crashes),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    /// Read a single crash record from the daemon
+    pub async fn crash(&mut self, id: &str) -> Result<CrashRecord> {
+        let request = DaemonRequest::Crash { id: id.to_string() };
+        match self.request(&request).await? {
+            DaemonResponse::Crash { crash } => Ok(*crash),
+            other => Err(unexpected(other)),
+        }
+    }
+}
+
+/// Convert an error or mismatched response into an [`Error`]
+fn unexpected(response: DaemonResponse) -> Error {
+    match response {
+        DaemonResponse::Error { message } => Error::daemon(message),
+        other => Error::daemon(format!("unexpected response: {:?}", other)),
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    async fn spawn_test_daemon() -> (PathBuf, tokio::task::JoinHandle<Result<()>>, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let socket_path = temp_dir.path().join(crate::DAEMON_SOCKET);
+
+        let daemon = Daemon::new(manager, socket_path.clone());
+        let handle = tokio::spawn(daemon.run());
+
+        // Wait for the socket to appear
+        for _ in 0..50 {
+            if socket_path.exists() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        (socket_path, handle, temp_dir)
+    }
+
+    #[test]
+    fn test_request_serialization() {
+        let request = DaemonRequest::Stop {
+            identifier: "web".to_string(),
+        };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(json, r#"{"command":"stop","identifier":"web"}"#);
+
+        let parsed: DaemonRequest = serde_json::from_str(r#"{"command":"list"}"#).unwrap();
+        assert!(matches!(parsed, DaemonRequest::List));
+    }
+
+    #[test]
+    fn test_start_request_round_trip() {
+        let config = ProcessConfig::builder()
+            .name("round-trip")
+            .script("echo")
+            .args(vec!["hello"])
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&DaemonRequest::Start {
+            config: Box::new(config),
+        })
+        .unwrap();
+        match serde_json::from_str::<DaemonRequest>(&json).unwrap() {
+            DaemonRequest::Start { config } => {
+                assert_eq!(config.name, "round-trip");
+                assert_eq!(config.args, vec!["hello"]);
+            }
+            other => panic!("unexpected request: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unexpected_response_maps_to_daemon_error() {
+        let err = unexpected(DaemonResponse::Error {
+            message: "Process not found: web".to_string(),
+        });
+        assert!(matches!(err, Error::Daemon { .. }));
+        assert_eq!(err.to_string(), "Daemon error: Process not found: web");
+    }
+
+    #[tokio::test]
+    async fn test_connect_without_daemon() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket_path = temp_dir.path().join(crate::DAEMON_SOCKET);
+        assert!(DaemonClient::connect(&socket_path).await.unwrap().is_none());
+
+        // A stale socket file with nobody listening is treated as "no daemon"
+        drop(std::os::unix::net::UnixListener::bind(&socket_path).unwrap());
+        assert!(socket_path.exists());
+        assert!(DaemonClient::connect(&socket_path).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_daemon_round_trip() {
+        let (socket_path, handle, _temp_dir) = spawn_test_daemon().await;
+
+        let mut client = DaemonClient::connect(&socket_path)
+            .await
+            .unwrap()
+            .expect("daemon should be listening");
+
+        let (version, pid) = client.ping().await.unwrap();
+        assert_eq!(version, crate::VERSION);
+        assert_eq!(pid, std::process::id());
+
+        assert!(client.list().await.unwrap().is_empty());
+
+        let err = client.stop("missing").await.unwrap_err();
+        assert!(err.to_string().contains("Process not found: missing"));
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_daemon_start_and_stop_process() {
+        let (socket_path, handle, _temp_dir) = spawn_test_daemon().await;
+        let mut client = DaemonClient::connect(&socket_path).await.unwrap().unwrap();
+
+        let config = ProcessConfig::builder()
+            .name("daemon-sleeper")
+            .script("sleep")
+            .args(vec!["30"])
+            .build()
+            .unwrap();
+        let id = client.start(config).await.unwrap();
+
+        let processes = client.list().await.unwrap();
+        assert_eq!(processes.len(), 1);
+        assert_eq!(processes[0].id, id);
+        assert!(processes[0].pid.is_some());
+
+        let info = client.info("daemon-sleeper").await.unwrap();
+        assert_eq!(info.id, id);
+        assert!(info.health.is_none());
+
+        // sleep ignores SIGWINCH
+        let signalled = client
+            .signal("default", ProcessSignal::Winch, true)
+            .await
+            .unwrap();
+        assert_eq!(signalled, vec!["daemon-sleeper"]);
+        let info = client.info("daemon-sleeper").await.unwrap();
+        assert_eq!(info.state, crate::ProcessState::Online);
+
+        client.stop("daemon-sleeper").await.unwrap();
+        let processes = client.list().await.unwrap();
+        assert_eq!(processes[0].state, crate::ProcessState::Stopped);
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_daemon_restart_reload_and_delete() {
+        let (socket_path, handle, _temp_dir) = spawn_test_daemon().await;
+        let mut client = DaemonClient::connect(&socket_path).await.unwrap().unwrap();
+
+        let config = ProcessConfig::builder()
+            .name("daemon-restarted")
+            .script("sleep")
+            .args(vec!["30"])
+            .build()
+            .unwrap();
+        client.start(config).await.unwrap();
+        let started = client.info("daemon-restarted").await.unwrap();
+
+        client
+            .restart("daemon-restarted", None, false)
+            .await
+            .unwrap();
+        let restarted = client.info("daemon-restarted").await.unwrap();
+        assert_eq!(restarted.restarts, 1);
+        assert_ne!(restarted.pid, started.pid);
+
+        client.reload("daemon-restarted", None).await.unwrap();
+        let reloaded = client.info("daemon-restarted").await.unwrap();
+        assert_ne!(reloaded.pid, restarted.pid);
+
+        // Events come from the daemon's journal
+        let filter = EventFilter {
+            process: Some("daemon-restarted".to_string()),
+            ..Default::default()
+        };
+        let events = client.events(&filter).await.unwrap();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event.kind, crate::events::EventKind::Restarted { .. })));
+
+        client.delete("daemon-restarted").await.unwrap();
+        assert!(client.list().await.unwrap().is_empty());
+        assert_eq!(client.delete_all().await.unwrap(), 0);
+        let err = client.delete_by_status("asleep").await.unwrap_err();
+        assert!(err.to_string().contains("Invalid status 'asleep'"));
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_daemon_tail_logs_and_crashes() {
+        let (socket_path, handle, _temp_dir) = spawn_test_daemon().await;
+        let mut client = DaemonClient::connect(&socket_path).await.unwrap().unwrap();
+
+        let config = ProcessConfig::builder()
+            .name("daemon-talker")
+            .script("sh")
+            .args(vec!["-c", "echo hello; sleep 1; echo later; sleep 30"])
+            .build()
+            .unwrap();
+        client.start(config).await.unwrap();
+
+        // Follow on a second connection, which the daemon then streams lines on
+        let mut follower = DaemonClient::connect(&socket_path).await.unwrap().unwrap();
+        let later = LogSearch {
+            pattern: Some(regex::Regex::new("later").unwrap()),
+            ..Default::default()
+        };
+        let (processes, entries) = follower
+            .tail_logs(Some("daemon-*"), &later, true)
+            .await
+            .unwrap();
+        assert_eq!(processes.len(), 1);
+        assert!(entries.is_empty());
+        let line = tokio::time::timeout(Duration::from_secs(5), follower.next_log_line())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(line.process_name, "daemon-talker");
+        assert_eq!(line.line, "later");
+
+        let hello = LogSearch {
+            pattern: Some(regex::Regex::new("hel+o").unwrap()),
+            ..Default::default()
+        };
+        let (_, entries) = client.tail_logs(None, &hello, false).await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].line, "hello");
+        let err = client
+            .tail_logs(Some("missing"), &hello, false)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Process not found: missing"));
+
+        assert!(client.crashes(None).await.unwrap().is_empty());
+        assert!(client.crash("missing").await.is_err());
+
+        client.delete("daemon-talker").await.unwrap();
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_daemon_start_wait_ready() {
+        let (socket_path, handle, _temp_dir) = spawn_test_daemon().await;
+        let mut client = DaemonClient::connect(&socket_path).await.unwrap().unwrap();
+
+        let config = |name: &str, check: &str| {
+            ProcessConfig::builder()
+                .name(name)
+                .script("sleep")
+                .args(vec!["30"])
+                .health_check(
+                    crate::health::HealthCheckConfig::script(check)
+                        .interval(Duration::from_millis(100))
+                        .enabled(true),
+                )
+                .wait_ready(true)
+                .wait_timeout(300)
+                .build()
+                .unwrap()
+        };
+
+        client.start(config("daemon-ready", "true")).await.unwrap();
+        let info = client.info("daemon-ready").await.unwrap();
+        assert_eq!(info.state, crate::ProcessState::Online);
+
+        let err = client
+            .start(config("daemon-not-ready", "false"))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("did not become healthy"));
+        let info = client.info("daemon-not-ready").await.unwrap();
+        assert_eq!(info.state, crate::ProcessState::Errored);
+
+        client.stop("daemon-ready").await.unwrap();
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_second_daemon_refuses_to_start() {
+        let (socket_path, handle, temp_dir) = spawn_test_daemon().await;
+
+        let manager = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let err = Daemon::new(manager, socket_path).run().await.unwrap_err();
+        assert!(matches!(err, Error::ResourceNotAvailable { .. }));
+
+        handle.abort();
+    }
+}
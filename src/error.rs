@@ -135,6 +135,13 @@ pub enum Error {
         /// Health check error message
         message: String,
     },
+
+    /// Daemon communication error
+    #[error("Daemon error: {message}")]
+    Daemon {
+        /// Daemon error message
+        message: String,
+    },
 }
 
 impl Error {
@@ -227,6 +234,13 @@ impl Error {
         }
     }
 
+    /// Create a new daemon error
+    pub fn daemon<S: Into<String>>(message: S) -> Self {
+        Self::Daemon {
+            message: message.into(),
+        }
+    }
+
     /// Check if this error is a process-related error
     pub fn is_process_error(&self) -> bool {
         matches!(
@@ -279,6 +293,7 @@ impl Error {
             Error::ResourceNotAvailable { .. } => "resource_not_available",
             Error::Internal { .. } => "internal",
             Error::HealthCheck { .. } => "health_check",
+            Error::Daemon { .. } => "daemon",
         }
     }
 }
@@ -342,6 +357,10 @@ mod tests {
         let err = Error::health_check("health check failed");
         assert!(matches!(err, Error::HealthCheck { .. }));
         assert_eq!(err.to_string(), "Health check error: health check failed");
+
+        let err = Error::daemon("connection closed");
+        assert!(matches!(err, Error::Daemon { .. }));
+        assert_eq!(err.to_string(), "Daemon error: connection closed");
     }
 
     #[test]
@@ -454,6 +473,7 @@ mod tests {
         );
         assert_eq!(Error::internal("test").category(), "internal");
         assert_eq!(Error::health_check("test").category(), "health_check");
+        assert_eq!(Error::daemon("test").category(), "daemon");
 
         let io_err = io::Error::new(io::ErrorKind::NotFound, "file not found");
         let err: Error = io_err.into();
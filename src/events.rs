@@ -0,0 +1,606 @@
+//! Persistent journal of process lifecycle events.
+//!
+//! The process manager appends an [`Event`] to `~/.pmdaemon/events.jsonl` whenever a
+//! process is created, started, exits, is restarted (with the [`RestartReason`]),
+//! stopped, given up on, deleted, changes health state or gets a port allocated or
+//! released. Each line of the journal is one JSON object, so the journal survives
+//! restarts of pmdaemon and can be read by other tools as well as through
+//! `pmdaemon events` and `GET /api/events`. Like the process logs, the journal is
+//! rotated once it reaches [`EVENTS_MAX_SIZE`](crate::EVENTS_MAX_SIZE), keeping
+//! [`EVENTS_RETAIN`](crate::EVENTS_RETAIN) segments (`events.jsonl.1` is the newest).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmdaemon::events::{Event, EventFilter, EventJournal, EventKind};
+//! use uuid::Uuid;
+//!
+//! # async fn example() -> pmdaemon::Result<()> {
+//! let dir = tempfile::tempdir()?;
+//! let journal = EventJournal::new(dir.path().join("events.jsonl"));
+//!
+//! let process_id = Uuid::new_v4();
+//! journal
+//!     .record(&Event::new(process_id, "web", EventKind::Started { pid: 4242 }))
+//!     .await?;
+//! journal
+//!     .record(&Event::new(process_id, "web", EventKind::Stopped { forced: false }))
+//!     .await?;
+//!
+//! let filter = EventFilter {
+//!     process: Some("web".to_string()),
+//!     ..EventFilter::default()
+//! };
+//! let events = journal.read(&filter).await?;
+//! assert_eq!(events.len(), 2);
+//! assert_eq!(events[1].kind.name(), "stopped");
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::health::HealthState;
+use crate::logs::LogRotationConfig;
+use crate::process::{ProcessExit, ProcessId};
+use crate::restart::RestartReason;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// A lifecycle event of a managed process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    /// When the event happened
+    pub at: DateTime<Utc>,
+    /// Process the event belongs to
+    pub process_id: ProcessId,
+    /// Name of the process at the time of the event
+    pub process_name: String,
+    /// What happened
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+impl Event {
+    /// Create an event that happened now
+    pub fn new(process_id: ProcessId, process_name: &str, kind: EventKind) -> Self {
+        Self {
+            at: Utc::now(),
+            process_id,
+            process_name: process_name.to_string(),
+            kind,
+        }
+    }
+}
+
+/// What happened to a process
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum EventKind {
+    /// The process was added with a new configuration
+    Created,
+    /// The saved configuration of the process changed
+    ConfigChanged {
+        /// Names of the settings that changed
+        changes: Vec<String>,
+    },
+    /// The process was started
+    Started {
+        /// System PID of the new process
+        pid: u32,
+    },
+    /// The process exited on its own
+    Exited {
+        /// Exit code or terminating signal
+        #[serde(flatten)]
+        exit: ProcessExit,
+    },
+    /// The process was restarted
+    Restarted {
+        /// Why it was restarted
+        reason: RestartReason,
+        /// System PID of the new process
+        pid: Option<u32>,
+    },
+    /// The process was stopped on request
+    Stopped {
+        /// Whether it had to be killed with SIGKILL
+        forced: bool,
+    },
+    /// The process was marked errored and is not restarted again
+    Errored {
+        /// Why the process was given up on
+        message: String,
+    },
+    /// The health check result of the process changed
+    HealthChanged {
+        /// Previous health state
+        from: HealthState,
+        /// New health state
+        to: HealthState,
+        /// Error message of the last failed check
+        message: Option<String>,
+    },
+    /// A port was allocated to the process
+    PortAllocated {
+        /// The allocated port
+        port: u16,
+    },
+    /// A port of the process was released
+    PortReleased {
+        /// The released port
+        port: u16,
+    },
+    /// The process was deleted
+    Deleted,
+}
+
+impl EventKind {
+    /// Short name of the event, as stored in the journal
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventKind::Created => "created",
+            EventKind::ConfigChanged { .. } => "config_changed",
+            EventKind::Started { .. } => "started",
+            EventKind::Exited { .. } => "exited",
+            EventKind::Restarted { .. } => "restarted",
+            EventKind::Stopped { .. } => "stopped",
+            EventKind::Errored { .. } => "errored",
+            EventKind::HealthChanged { .. } => "health_changed",
+            EventKind::PortAllocated { .. } => "port_allocated",
+            EventKind::PortReleased { .. } => "port_released",
+            EventKind::Deleted => "deleted",
+        }
+    }
+}
+
+/// Lowercase name of a health state, as used in the API
+fn health_state_name(state: HealthState) -> &'static str {
+    match state {
+        HealthState::Healthy => "healthy",
+        HealthState::Unhealthy => "unhealthy",
+        HealthState::Unknown => "unknown",
+    }
+}
+
+impl fmt::Display for EventKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventKind::Created | EventKind::Deleted => write!(f, "{}", self.name()),
+            EventKind::ConfigChanged { changes } => {
+                write!(f, "config changed: {}", changes.join(", "))
+            }
+            EventKind::Started { pid } => write!(f, "started with PID {}", pid),
+            EventKind::Exited { exit } => write!(f, "exited ({})", exit),
+            EventKind::Restarted { reason, pid } => match pid {
+                Some(pid) => write!(f, "restarted ({}) with PID {}", reason, pid),
+                None => write!(f, "restarted ({})", reason),
+            },
+            EventKind::Stopped { forced: false } => write!(f, "stopped"),
+            EventKind::Stopped { forced: true } => write!(f, "stopped (killed)"),
+            EventKind::Errored { message } => write!(f, "errored: {}", message),
+            EventKind::HealthChanged { from, to, message } => {
+                write!(
+                    f,
+                    "health {} -> {}",
+                    health_state_name(*from),
+                    health_state_name(*to)
+                )?;
+                match message {
+                    Some(message) => write!(f, ": {}", message),
+                    None => Ok(()),
+                }
+            }
+            EventKind::PortAllocated { port } => write!(f, "port {} allocated", port),
+            EventKind::PortReleased { port } => write!(f, "port {} released", port),
+        }
+    }
+}
+
+/// Which events to read from the journal
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventFilter {
+    /// Only events of the process with this name or ID, or of the instances of the
+    /// cluster with this base name
+    pub process: Option<String>,
+    /// Only events at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only the most recent events, at most this many
+    pub limit: Option<usize>,
+}
+
+impl EventFilter {
+    /// Whether `event` passes the process and time filters
+    pub fn matches(&self, event: &Event) -> bool {
+        if self.since.is_some_and(|since| event.at < since) {
+            return false;
+        }
+        match &self.process {
+            Some(process) => matches_process(process, event.process_id, &event.process_name),
+            None => true,
+        }
+    }
+}
+
+/// Whether `identifier` (a name, an ID or the base name of a cluster) refers to the
+/// process with `process_id` and `process_name`
+pub(crate) fn matches_process(identifier: &str, process_id: ProcessId, process_name: &str) -> bool {
+    process_name == identifier
+        || process_id.to_string() == identifier
+        || process_name
+            .strip_prefix(identifier)
+            .and_then(|rest| rest.strip_prefix('-'))
+            .is_some_and(|instance| instance.parse::<u32>().is_ok())
+}
+
+/// Parse the start of a time window, given as an RFC 3339 timestamp
+/// (`2025-06-01T12:00:00Z`) or as a duration before `now` (`30s`, `15m`, `2h`, `7d`)
+pub fn parse_since(since: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let since = since.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(since) {
+        return Ok(at.with_timezone(&Utc));
+    }
+
+    let ago = match since.strip_suffix('d') {
+        Some(days) => days
+            .trim()
+            .parse::<i64>()
+            .ok()
+            .and_then(Duration::try_days)
+            .ok_or_else(|| Error::invalid_argument(format!("Invalid duration: {}", since)))?,
+        None => crate::config::parse_duration_string(since)
+            .ok()
+            .and_then(|ago| Duration::from_std(ago).ok())
+            .ok_or_else(|| {
+                Error::invalid_argument(format!(
+                    "Invalid time '{}' (expected a duration like 30m, 2h or 7d, or an RFC 3339 timestamp)",
+                    since
+                ))
+            })?,
+    };
+    Ok(now - ago)
+}
+
+/// Append-only journal of lifecycle events, stored as JSON lines
+#[derive(Debug)]
+pub struct EventJournal {
+    /// Path of the journal file
+    path: PathBuf,
+    /// Size-based rotation of the journal file
+    rotation: LogRotationConfig,
+    /// Keeps events recorded concurrently from interleaving
+    write_lock: Mutex<()>,
+}
+
+impl EventJournal {
+    /// Journal stored at `path`; the file is created with the first event
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            rotation: LogRotationConfig::default()
+                .max_size(crate::EVENTS_MAX_SIZE)
+                .retain(crate::EVENTS_RETAIN),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    /// Rotate the journal once it reaches `max_size` bytes, keeping `retain` segments
+    pub fn rotation(mut self, max_size: u64, retain: usize) -> Self {
+        self.rotation = LogRotationConfig::default()
+            .max_size(max_size)
+            .retain(retain);
+        self
+    }
+
+    /// Path of the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an event to the journal, rotating it once it reaches its maximum size
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal file cannot be opened or written.
+    pub async fn record(&self, event: &Event) -> Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().await;
+        let mut file = crate::logs::open_append(&self.path).await.map_err(|e| {
+            Error::config(format!(
+                "Failed to open event journal {:?}: {}",
+                self.path, e
+            ))
+        })?;
+        file.write_all(line.as_bytes()).await.map_err(|e| {
+            Error::config(format!(
+                "Failed to write event journal {:?}: {}",
+                self.path, e
+            ))
+        })?;
+
+        let size = file
+            .metadata()
+            .await
+            .map(|metadata| metadata.len())
+            .unwrap_or(0);
+        drop(file);
+        let now = Utc::now();
+        if self.rotation.is_due(size, now, now) {
+            if let Err(e) = crate::logs::rotate(&self.path, &self.rotation).await {
+                warn!("Failed to rotate event journal {:?}: {}", self.path, e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the events that pass `filter`, oldest first.
+    ///
+    /// The rotated segments and the journal are streamed line by line, oldest first,
+    /// keeping only the events that pass the filter (at most `limit` of them). Lines
+    /// that cannot be parsed, such as a line cut short by a crash, are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the journal exists but cannot be read.
+    pub async fn read(&self, filter: &EventFilter) -> Result<Vec<Event>> {
+        let mut files = crate::logs::segments(&self.path);
+        files.reverse();
+        files.push(self.path.clone());
+
+        let mut events = VecDeque::new();
+        for path in files {
+            self.read_file(&path, filter, &mut events).await?;
+        }
+        Ok(events.into())
+    }
+
+    /// Add the events of the journal file or segment at `path` that pass `filter`
+    /// to `events`, dropping the oldest ones beyond the filter's limit
+    async fn read_file(
+        &self,
+        path: &Path,
+        filter: &EventFilter,
+        events: &mut VecDeque<Event>,
+    ) -> Result<()> {
+        let read_error = |e: std::io::Error| {
+            Error::config(format!("Failed to read event journal {:?}: {}", path, e))
+        };
+        let file = match fs::File::open(path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(read_error(e)),
+        };
+
+        let mut lines = BufReader::new(file).lines();
+        while let Some(line) = lines.next_line().await.map_err(read_error)? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event = match serde_json::from_str::<Event>(&line) {
+                Ok(event) => event,
+                Err(e) => {
+                    warn!("Skipping unreadable event journal entry: {}", e);
+                    continue;
+                }
+            };
+            if !filter.matches(&event) {
+                continue;
+            }
+            events.push_back(event);
+            if filter.limit.is_some_and(|limit| events.len() > limit) {
+                events.pop_front();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Names of the top-level settings that differ between two serialized configurations
+pub fn config_changes(old: &serde_json::Value, new: &serde_json::Value) -> Vec<String> {
+    let (Some(old), Some(new)) = (old.as_object(), new.as_object()) else {
+        return Vec::new();
+    };
+    let mut changes: Vec<String> = new
+        .iter()
+        .filter(|(key, value)| old.get(*key) != Some(*value))
+        .map(|(key, _)| key.clone())
+        .chain(old.keys().filter(|key| !new.contains_key(*key)).cloned())
+        .collect();
+    changes.sort();
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signals::ProcessSignal;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_event_serialization() {
+        let event = Event::new(
+            Uuid::new_v4(),
+            "web",
+            EventKind::Exited {
+                exit: ProcessExit {
+                    code: None,
+                    signal: Some(ProcessSignal::Kill),
+                    unknown: false,
+                },
+            },
+        );
+        let json: serde_json::Value = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "exited");
+        assert_eq!(json["process_name"], "web");
+        assert_eq!(json["signal"], "SIGKILL");
+
+        let parsed: Event = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, event);
+
+        let restarted = EventKind::Restarted {
+            reason: RestartReason::MemoryLimit,
+            pid: Some(42),
+        };
+        let json = serde_json::to_value(&restarted).unwrap();
+        assert_eq!(json["reason"], "memory-limit");
+        assert_eq!(
+            restarted.to_string(),
+            "restarted (memory-limit) with PID 42"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_and_read() {
+        let temp_dir = TempDir::new().unwrap();
+        let journal = EventJournal::new(temp_dir.path().join("events.jsonl"));
+        assert!(journal
+            .read(&EventFilter::default())
+            .await
+            .unwrap()
+            .is_empty());
+
+        let web = Uuid::new_v4();
+        let worker = Uuid::new_v4();
+        let mut old = Event::new(web, "web", EventKind::Created);
+        old.at -= Duration::hours(2);
+        journal.record(&old).await.unwrap();
+        for (id, name, pid) in [(web, "web", 1), (worker, "worker-0", 2), (web, "web", 3)] {
+            journal
+                .record(&Event::new(id, name, EventKind::Started { pid }))
+                .await
+                .unwrap();
+        }
+
+        let all = journal.read(&EventFilter::default()).await.unwrap();
+        assert_eq!(all.len(), 4);
+
+        let recent = EventFilter {
+            since: Some(Utc::now() - Duration::hours(1)),
+            ..EventFilter::default()
+        };
+        assert_eq!(journal.read(&recent).await.unwrap().len(), 3);
+
+        let last_web = EventFilter {
+            process: Some(web.to_string()),
+            limit: Some(1),
+            ..EventFilter::default()
+        };
+        let events = journal.read(&last_web).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EventKind::Started { pid: 3 });
+
+        // Cluster instances match their base name
+        let cluster = EventFilter {
+            process: Some("worker".to_string()),
+            ..EventFilter::default()
+        };
+        assert_eq!(journal.read(&cluster).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_read_skips_truncated_lines() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("events.jsonl");
+        let journal = EventJournal::new(path.clone());
+        journal
+            .record(&Event::new(Uuid::new_v4(), "web", EventKind::Deleted))
+            .await
+            .unwrap();
+        let mut content = fs::read_to_string(&path).await.unwrap();
+        content.push_str("{\"at\":\"2025-");
+        fs::write(&path, content).await.unwrap();
+
+        let events = journal.read(&EventFilter::default()).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, EventKind::Deleted);
+    }
+
+    #[tokio::test]
+    async fn test_journal_rotation() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("events.jsonl");
+        let journal = EventJournal::new(path.clone()).rotation(1024, 2);
+
+        let id = Uuid::new_v4();
+        for pid in 0..100 {
+            journal
+                .record(&Event::new(id, "web", EventKind::Started { pid }))
+                .await
+                .unwrap();
+        }
+
+        // Only the retained segments are kept, none of them above the size limit
+        assert!(crate::logs::segment_path(&path, 2, false).exists());
+        assert!(!crate::logs::segment_path(&path, 3, false).exists());
+        for file in [path.clone(), crate::logs::segment_path(&path, 1, false)] {
+            assert!(fs::metadata(&file).await.unwrap().len() < 1024 + 200);
+        }
+
+        // Reading continues into the rotated segments, oldest first
+        let events = journal.read(&EventFilter::default()).await.unwrap();
+        assert!(events.len() > 10 && events.len() < 100);
+        let pids: Vec<u32> = events
+            .iter()
+            .map(|event| match event.kind {
+                EventKind::Started { pid } => pid,
+                _ => unreachable!(),
+            })
+            .collect();
+        assert!(pids.windows(2).all(|pair| pair[1] == pair[0] + 1));
+        assert_eq!(pids.last(), Some(&99));
+
+        let last = EventFilter {
+            limit: Some(3),
+            ..EventFilter::default()
+        };
+        let events = journal.read(&last).await.unwrap();
+        assert_eq!(
+            events.iter().map(|event| &event.kind).collect::<Vec<_>>(),
+            [97, 98, 99]
+                .map(|pid| EventKind::Started { pid })
+                .iter()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_since() {
+        let now = Utc::now();
+        assert_eq!(parse_since("2h", now).unwrap(), now - Duration::hours(2));
+        assert_eq!(
+            parse_since("30m", now).unwrap(),
+            now - Duration::minutes(30)
+        );
+        assert_eq!(parse_since("7d", now).unwrap(), now - Duration::days(7));
+        assert_eq!(
+            parse_since("2025-06-01T12:00:00Z", now)
+                .unwrap()
+                .to_rfc3339(),
+            "2025-06-01T12:00:00+00:00"
+        );
+        assert!(parse_since("yesterday", now).is_err());
+        assert!(parse_since("xd", now).is_err());
+    }
+
+    #[test]
+    fn test_config_changes() {
+        let old = serde_json::json!({"name": "web", "instances": 1, "cwd": "/srv"});
+        let new = serde_json::json!({"name": "web", "instances": 2, "watch": true});
+        assert_eq!(
+            config_changes(&old, &new),
+            vec!["cwd", "instances", "watch"]
+        );
+        assert!(config_changes(&old, &old).is_empty());
+    }
+}
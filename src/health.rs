@@ -12,6 +12,8 @@
 //! - **Configurable Parameters** - Timeout, interval, and retry settings
 //! - **Async Execution** - Non-blocking health check execution
 //! - **Status Tracking** - Detailed health status with timestamps and error information
+//! - **Background Monitoring** - [`HealthMonitor`] runs checks at the configured interval
+//!   and the process manager applies an [`UnhealthyAction`] once retries are exhausted
 //!
 //! ## Examples
 //!
@@ -55,12 +57,19 @@
 //! ```
 
 use crate::error::{Error, Result};
+use crate::manager::ProcessManager;
+use crate::process::ProcessId;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::process::Command;
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
 use tracing::{debug, info, warn};
 
 /// Health check configuration defining how health validation should be performed.
@@ -108,6 +117,38 @@ pub struct HealthCheckConfig {
 
     /// Whether health checks are enabled
     pub enabled: bool,
+
+    /// What the process manager does once the process becomes unhealthy
+    #[serde(default)]
+    pub on_unhealthy: UnhealthyAction,
+}
+
+/// Action taken by the process manager when a process becomes unhealthy.
+///
+/// The action is applied once per unhealthy episode, when the number of consecutive
+/// failed checks reaches the configured `retries`.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmdaemon::health::UnhealthyAction;
+///
+/// let action: UnhealthyAction = "stop".parse().unwrap();
+/// assert_eq!(action, UnhealthyAction::Stop);
+/// assert_eq!(UnhealthyAction::default(), UnhealthyAction::Restart);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnhealthyAction {
+    /// Restart the process
+    #[default]
+    Restart,
+
+    /// Only log an alert and report the unhealthy state
+    Alert,
+
+    /// Stop the process
+    Stop,
 }
 
 /// Type of health check to perform.
@@ -210,7 +251,7 @@ pub struct HealthStatus {
 ///
 /// assert!(matches!(healthy, HealthState::Healthy));
 /// ```
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthState {
     /// Process is healthy (health checks are passing)
@@ -220,6 +261,7 @@ pub enum HealthState {
     Unhealthy,
 
     /// Health state is unknown (no checks performed yet or checks disabled)
+    #[default]
     Unknown,
 }
 
@@ -279,6 +321,7 @@ impl Default for HealthCheckConfig {
             interval: Duration::from_secs(DEFAULT_INTERVAL_SECS),
             retries: DEFAULT_RETRIES,
             enabled: false,
+            on_unhealthy: UnhealthyAction::default(),
         }
     }
 }
@@ -296,12 +339,6 @@ impl Default for HealthStatus {
     }
 }
 
-impl Default for HealthState {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 impl HealthCheckConfig {
     /// Create a new HTTP health check configuration.
     ///
@@ -426,6 +463,47 @@ impl HealthCheckConfig {
         self.enabled = enabled;
         self
     }
+
+    /// Set the action taken when the process becomes unhealthy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmdaemon::health::{HealthCheckConfig, UnhealthyAction};
+    ///
+    /// let config = HealthCheckConfig::http("http://localhost:9615/health")
+    ///     .on_unhealthy(UnhealthyAction::Alert);
+    /// ```
+    pub fn on_unhealthy(mut self, action: UnhealthyAction) -> Self {
+        self.on_unhealthy = action;
+        self
+    }
+}
+
+impl fmt::Display for UnhealthyAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnhealthyAction::Restart => write!(f, "restart"),
+            UnhealthyAction::Alert => write!(f, "alert"),
+            UnhealthyAction::Stop => write!(f, "stop"),
+        }
+    }
+}
+
+impl FromStr for UnhealthyAction {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "restart" => Ok(UnhealthyAction::Restart),
+            "alert" => Ok(UnhealthyAction::Alert),
+            "stop" => Ok(UnhealthyAction::Stop),
+            _ => Err(Error::config(format!(
+                "Invalid unhealthy action '{}' (expected restart, alert or stop)",
+                s
+            ))),
+        }
+    }
 }
 
 impl HealthStatus {
@@ -589,6 +667,20 @@ impl HealthCheck {
         self.config.enabled
     }
 
+    /// Check if consecutive failures have reached the configured retry limit.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmdaemon::health::{HealthCheck, HealthCheckConfig};
+    ///
+    /// let health_check = HealthCheck::new(HealthCheckConfig::default());
+    /// assert!(!health_check.retries_exhausted());
+    /// ```
+    pub fn retries_exhausted(&self) -> bool {
+        self.status.is_unhealthy() && self.status.consecutive_failures >= self.config.retries.max(1)
+    }
+
     /// Perform a single health check.
     ///
     /// Executes the configured health check (HTTP or script) and updates the internal
@@ -759,6 +851,187 @@ impl HealthCheck {
     }
 }
 
+/// Result of a health check performed by a [`HealthMonitor`]
+#[derive(Debug, Clone)]
+pub struct HealthEvent {
+    /// Process that was checked
+    pub process_id: ProcessId,
+    /// Health status after the check
+    pub status: HealthStatus,
+    /// Whether this check just exhausted the configured retries
+    pub became_unhealthy: bool,
+}
+
+/// Background task running a process' health check at the configured interval.
+///
+/// Each check result is sent as a [`HealthEvent`]. The first check runs one interval
+/// after the monitor is created so the process has time to boot. Dropping the monitor
+/// stops the task.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use pmdaemon::health::{HealthCheckConfig, HealthMonitor};
+/// use std::time::Duration;
+/// use tokio::sync::mpsc;
+/// use uuid::Uuid;
+///
+/// # async fn example() {
+/// let (tx, mut rx) = mpsc::unbounded_channel();
+/// let config = HealthCheckConfig::http("http://localhost:3000/health")
+///     .interval(Duration::from_secs(10))
+///     .enabled(true);
+///
+/// let _monitor = HealthMonitor::spawn(Uuid::new_v4(), config, tx);
+/// while let Some(event) = rx.recv().await {
+///     println!("Health: {:?}", event.status.state);
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct HealthMonitor {
+    /// Background check loop
+    task: JoinHandle<()>,
+}
+
+// Lower bound for the check interval so a zero interval cannot spin
+const MIN_INTERVAL: Duration = Duration::from_millis(100);
+
+impl HealthMonitor {
+    /// Start checking a process' health in the background
+    pub fn spawn(
+        process_id: ProcessId,
+        config: HealthCheckConfig,
+        events: mpsc::UnboundedSender<HealthEvent>,
+    ) -> Self {
+        let task = tokio::spawn(async move {
+            let period = config.interval.max(MIN_INTERVAL);
+            let mut ticker = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut health_check = HealthCheck::new(config);
+
+            loop {
+                ticker.tick().await;
+
+                let was_exhausted = health_check.retries_exhausted();
+                if let Err(e) = health_check.check().await {
+                    warn!(
+                        "Health check for process {} could not run: {}",
+                        process_id, e
+                    );
+                    continue;
+                }
+
+                let event = HealthEvent {
+                    process_id,
+                    status: health_check.status().clone(),
+                    became_unhealthy: !was_exhausted && health_check.retries_exhausted(),
+                };
+                if events.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self { task }
+    }
+}
+
+impl Drop for HealthMonitor {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+// Upper bound for the spacing of checks while waiting for a process to become ready
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Run a health check until it passes, the process stops or `timeout` expires.
+///
+/// Checks are spaced by the configured interval, capped at 500ms so readiness is
+/// noticed quickly. `still_running` is consulted before every attempt so a process
+/// that exits during startup fails fast instead of running into the timeout.
+///
+/// # Errors
+///
+/// Returns [`Error::HealthCheck`] if the process exits or the timeout expires
+/// before a check passes.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use pmdaemon::health::{wait_until_healthy, HealthCheckConfig};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let config = HealthCheckConfig::http("http://localhost:3000/health");
+/// let status = wait_until_healthy(config, Duration::from_secs(30), || async { true }).await?;
+/// assert!(status.is_healthy());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn wait_until_healthy<F, Fut>(
+    config: HealthCheckConfig,
+    timeout: Duration,
+    mut still_running: F,
+) -> Result<HealthStatus>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    let poll_interval = config.interval.clamp(MIN_INTERVAL, READY_POLL_INTERVAL);
+    let mut health_check = HealthCheck::new(config.enabled(true));
+
+    loop {
+        if !still_running().await {
+            return Err(Error::health_check(
+                "process exited before becoming healthy",
+            ));
+        }
+
+        // A single slow check must not outlive the overall deadline
+        let status = match tokio::time::timeout_at(deadline, health_check.check()).await {
+            Ok(result) => result?.clone(),
+            Err(_) => health_check.status().clone(),
+        };
+        if status.is_healthy() {
+            return Ok(status);
+        }
+
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Err(Error::health_check(format!(
+                "process did not become healthy within {:?}{}",
+                timeout,
+                status
+                    .error_message
+                    .as_ref()
+                    .map(|e| format!(" (last error: {})", e))
+                    .unwrap_or_default()
+            )));
+        }
+        tokio::time::sleep(poll_interval.min(deadline - now)).await;
+    }
+}
+
+/// Record health check results and apply unhealthy actions as events arrive.
+///
+/// Long-running hosts of a [`ProcessManager`] (the daemon and the web server) spawn
+/// this with the receiver obtained from [`ProcessManager::take_health_events`].
+pub async fn run_health_loop(
+    manager: Arc<RwLock<ProcessManager>>,
+    mut events: mpsc::UnboundedReceiver<HealthEvent>,
+) {
+    while let Some(event) = events.recv().await {
+        let manager = manager.read().await;
+        if let Err(e) = manager.handle_health_event(event).await {
+            warn!("Failed to handle health check result: {}", e);
+        }
+    }
+    info!("Health check loop finished");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -988,9 +1261,63 @@ mod tests {
         assert_eq!(status.state, HealthState::Unhealthy);
 
         // Third failure - should exceed retry threshold
+        assert!(!health_check.retries_exhausted());
         let status = health_check.check().await.unwrap();
         assert_eq!(status.consecutive_failures, 3);
         assert_eq!(status.state, HealthState::Unhealthy);
+        assert!(health_check.retries_exhausted());
+    }
+
+    #[test]
+    fn test_unhealthy_action_parse_and_serde() {
+        assert_eq!(
+            "restart".parse::<UnhealthyAction>().unwrap(),
+            UnhealthyAction::Restart
+        );
+        assert_eq!(
+            "ALERT".parse::<UnhealthyAction>().unwrap(),
+            UnhealthyAction::Alert
+        );
+        assert!("reboot"
+            .parse::<UnhealthyAction>()
+            .unwrap_err()
+            .is_config_error());
+        assert_eq!(UnhealthyAction::Stop.to_string(), "stop");
+
+        // Older configurations without the field default to restarting
+        let mut value = serde_json::to_value(HealthCheckConfig::default()).unwrap();
+        value.as_object_mut().unwrap().remove("on_unhealthy");
+        let config: HealthCheckConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(config.on_unhealthy, UnhealthyAction::Restart);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_health_monitor_reports_transition_once() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let process_id = uuid::Uuid::new_v4();
+        let config = HealthCheckConfig::script("false")
+            .interval(Duration::from_millis(100))
+            .retries(2)
+            .enabled(true);
+        let monitor = HealthMonitor::spawn(process_id, config, tx);
+
+        let mut transitions = Vec::new();
+        for _ in 0..4 {
+            let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+                .await
+                .expect("expected a health event")
+                .unwrap();
+            assert_eq!(event.process_id, process_id);
+            transitions.push(event.became_unhealthy);
+        }
+        assert_eq!(transitions, vec![false, true, false, false]);
+
+        // Dropping the monitor stops the checks
+        drop(monitor);
+        assert!(tokio::time::timeout(Duration::from_millis(500), rx.recv())
+            .await
+            .map_or(true, |event| event.is_none()));
     }
 
     #[test]
@@ -1057,4 +1384,27 @@ mod tests {
         assert_eq!(unhealthy, unhealthy_deser);
         assert_eq!(unknown, unknown_deser);
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_wait_until_healthy() {
+        let config = HealthCheckConfig::script("true").interval(Duration::from_millis(100));
+        let status = wait_until_healthy(config, Duration::from_secs(5), || async { true })
+            .await
+            .unwrap();
+        assert!(status.is_healthy());
+
+        let config = HealthCheckConfig::script("false").interval(Duration::from_millis(100));
+        let err = wait_until_healthy(config.clone(), Duration::from_millis(300), || async {
+            true
+        })
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("did not become healthy"));
+
+        let err = wait_until_healthy(config, Duration::from_secs(30), || async { false })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("exited before becoming healthy"));
+    }
 }
@@ -10,8 +10,10 @@
 //! - **Process lifecycle management** - Start, stop, restart, reload, delete operations
 //! - **Clustering support** - Run multiple instances with automatic load balancing
 //! - **Auto-restart** - Automatic restart on crashes with configurable limits
+//! - **Watch mode** - Restart on file changes with glob ignore patterns and debouncing
 //! - **Signal handling** - Graceful shutdown with SIGTERM/SIGINT and custom signals
 //! - **Configuration persistence** - Process configs saved and restored between sessions
+//! - **Supervisor daemon** - Long-lived daemon that owns processes and serves CLI requests
 //!
 //! ### Advanced Monitoring
 //! - **Real-time monitoring** - CPU, memory, uptime tracking with system metrics
@@ -112,25 +114,40 @@
 //!
 //! # Web API server for remote monitoring
 //! pmdaemon web --port 9615 --host 127.0.0.1
+//!
+//! # Long-running supervisor; other commands talk to it over a local socket
+//! pmdaemon daemon
 //! ```
 
+pub mod actor;
 pub mod config;
+pub mod crashes;
+pub mod daemon;
 pub mod error;
+pub mod events;
 pub mod health;
+pub mod logs;
 pub mod manager;
 pub mod monitoring;
 pub mod process;
+pub mod restart;
 pub mod signals;
+pub mod watcher;
 pub mod web;
 
 // Re-export main types for convenience
 pub use config::{EcosystemConfig, ProcessConfig, ProcessConfigBuilder};
 pub use error::{Error, Result};
-pub use health::{HealthCheck, HealthCheckConfig, HealthCheckType, HealthState, HealthStatus};
+pub use health::{
+    HealthCheck, HealthCheckConfig, HealthCheckType, HealthState, HealthStatus, UnhealthyAction,
+};
 pub use manager::ProcessManager;
 pub use monitoring::{MonitoringData, SystemMetrics};
-pub use process::{Process, ProcessId, ProcessState, ProcessStatus};
-pub use signals::{ProcessSignal, SignalHandler};
+pub use process::{
+    ExitEvent, Process, ProcessExit, ProcessId, ProcessState, ProcessStatus, StopReport,
+};
+pub use restart::{RestartMode, RestartReason};
+pub use signals::{KillStep, ProcessSignal, SignalHandler};
 
 /// Version of the PMDaemon library
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -144,6 +161,18 @@ pub const LOG_DIR: &str = "logs";
 /// Default PID directory name
 pub const PID_DIR: &str = "pids";
 
+/// Lifecycle event journal file name (inside the configuration directory)
+pub const EVENTS_FILE: &str = "events.jsonl";
+
+/// Size in bytes at which the event journal is rotated (10 MiB)
+pub const EVENTS_MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Number of rotated event journal segments kept
+pub const EVENTS_RETAIN: usize = 3;
+
+/// Daemon control socket file name (inside the configuration directory)
+pub const DAEMON_SOCKET: &str = "pmdaemon.sock";
+
 /// Default web server port for monitoring API
 pub const DEFAULT_WEB_PORT: u16 = 9615;
 
@@ -158,3 +187,31 @@ pub const DEFAULT_MAX_RESTARTS: u32 = 16;
 
 /// Minimum uptime in milliseconds before considering a process stable
 pub const DEFAULT_MIN_UPTIME: u64 = 1000;
+
+/// Default upper bound in milliseconds for the exponential backoff restart delay (15 seconds)
+pub const DEFAULT_MAX_EXP_BACKOFF_RESTART_DELAY: u64 = 15000;
+
+/// Default quiet period in milliseconds before restarting after file changes
+pub const DEFAULT_WATCH_DELAY: u64 = 1000;
+
+/// Default time to wait for a process to become healthy with `wait_ready` (30 seconds)
+pub const DEFAULT_WAIT_TIMEOUT: u64 = 30000;
+
+/// Crash records directory name (inside the configuration directory)
+pub const CRASHES_DIR: &str = "crashes";
+
+/// Number of log lines of each stream saved in a crash record
+pub const CRASH_LOG_LINES: usize = 50;
+
+/// Number of crash records kept per process
+pub const CRASH_RECORDS_LIMIT: usize = 20;
+
+/// Environment variable naming the executable that runs log shims, see
+/// [`LogShim::program`](logs::LogShim::program)
+pub const LOG_SHIM_ENV: &str = "PMDAEMON_LOG_SHIM";
+
+/// Number of recent restarts kept in the restart history of a process
+pub const RESTART_HISTORY_LIMIT: usize = 10;
+
+/// Default number of rotated segments kept per log file
+pub const DEFAULT_LOG_RETAIN: usize = 10;
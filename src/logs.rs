@@ -0,0 +1,2213 @@
+//! Log file management for managed processes.
+//!
+//! Process output is appended to per-process log files, by default in
+//! `~/.pmdaemon/logs` (see [`LogFiles`]). When a combined log is requested, output is
+//! pumped through a [`LogShim`], a small `pmdaemon log-shim` process per managed
+//! process, so that each line lands in both the stream's own log and the combined
+//! log, prefixed with a timestamp when the process has a `log_date_format`. In the
+//! combined log each line is also tagged with its stream (`[out]` or `[err]`). The
+//! shim outlives the host that started the process, so the process keeps its output
+//! when the daemon or the CLI exits. With [`LogFormat::Json`], each line is written
+//! as a JSON object instead (see [`LogLine::to_json`]), and [`humanize_json_line`]
+//! turns it back into text for reading. With a [`LogRotationConfig`], a file is
+//! rotated once it grows past `max_size` or when a new rotation
+//! [`interval`](LogRotationConfig::interval) period begins. Rotation copies the file
+//! to a numbered segment (`app-out.log.1`, `app-out.log.2`, ..., newest first, or
+//! `app-out.log.1.gz` when compressed) and truncates the original in place. Processes
+//! write to their log files in append mode, so they keep logging to the same file
+//! without being restarted. Output written in the short window between copying and
+//! truncating is lost, as with logrotate's `copytruncate`.
+//!
+//! [`tail_lines`] reads the end of a log across the current file and its rotated
+//! segments, [`search_lines`] finds lines by pattern, time window and level (see
+//! [`LogSearch`]) and a [`LogSubscription`] delivers the new lines of one process as
+//! its shim pumps them. Logs are read line by line rather than loaded whole.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use pmdaemon::logs::{LogRotationConfig, RotationInterval};
+//!
+//! let rotation = LogRotationConfig::default()
+//!     .max_size(10 * 1024 * 1024)
+//!     .retain(5)
+//!     .compress(true)
+//!     .interval(RotationInterval::Daily);
+//! assert!(rotation.validate().is_ok());
+//! ```
+
+use crate::error::{Error, Result};
+use crate::process::ProcessId;
+use chrono::{DateTime, Datelike, Duration, Local, TimeZone, Timelike, Utc};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWriteExt, BufReader};
+use tracing::warn;
+
+/// Log files that a process writes its output to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogFiles {
+    /// Stdout log
+    pub out: PathBuf,
+
+    /// Stderr log
+    pub err: PathBuf,
+
+    /// Combined stdout and stderr log
+    ///
+    /// When set, the process writes to pipes that pmdaemon copies to the logs, instead
+    /// of writing to the log files directly.
+    pub combined: Option<PathBuf>,
+}
+
+/// Output stream of a process
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    /// Standard output
+    Out,
+    /// Standard error
+    Err,
+}
+
+impl fmt::Display for LogStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogStream::Out => write!(f, "out"),
+            LogStream::Err => write!(f, "err"),
+        }
+    }
+}
+
+/// A line of output pumped from a process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogLine {
+    /// When pmdaemon read the line
+    pub at: DateTime<Utc>,
+    /// Process that wrote the line
+    pub process_id: ProcessId,
+    /// Name of the process
+    pub process_name: String,
+    /// Stream the line was written to
+    pub stream: LogStream,
+    /// The line, without its line ending
+    pub line: String,
+}
+
+impl LogLine {
+    /// Format the line as written to a log file: prefixed with the local time in
+    /// `date_format` when given, and with the stream tag when `tagged`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmdaemon::logs::{LogLine, LogStream};
+    ///
+    /// let line = LogLine {
+    ///     at: chrono::Utc::now(),
+    ///     process_id: uuid::Uuid::new_v4(),
+    ///     process_name: "web".to_string(),
+    ///     stream: LogStream::Err,
+    ///     line: "listen EADDRINUSE".to_string(),
+    /// };
+    /// assert_eq!(line.format(None, true), "[err] listen EADDRINUSE");
+    /// assert!(line.format(Some("%Y"), false).ends_with(" listen EADDRINUSE"));
+    /// ```
+    pub fn format(&self, date_format: Option<&str>, tagged: bool) -> String {
+        use std::fmt::Write;
+
+        let mut formatted = String::new();
+        if let Some(date_format) = date_format {
+            let at = self.at.with_timezone(&Local);
+            if write!(formatted, "{} ", at.format(date_format)).is_err() {
+                // Configurations are validated, but fall back rather than drop the line
+                formatted = format!("{} ", at.to_rfc3339());
+            }
+        }
+        if tagged {
+            let _ = write!(formatted, "[{}] ", self.stream);
+        }
+        formatted.push_str(&self.line);
+        formatted
+    }
+
+    /// The line as a JSON log entry with `timestamp`, `app`, `instance`, `process_id`,
+    /// `stream` and `message` fields.
+    ///
+    /// `app` is the process name without the instance suffix of cluster instances.
+    /// When the line already is a JSON object, its fields are kept and the entry's
+    /// fields are only added where the application did not set them itself.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmdaemon::logs::{LogLine, LogStream};
+    ///
+    /// let line = LogLine {
+    ///     at: chrono::Utc::now(),
+    ///     process_id: uuid::Uuid::new_v4(),
+    ///     process_name: "web-1".to_string(),
+    ///     stream: LogStream::Out,
+    ///     line: r#"{"level":"info","message":"listening"}"#.to_string(),
+    /// };
+    /// let entry = line.to_json(Some(1));
+    /// assert_eq!(entry["app"], "web");
+    /// assert_eq!(entry["instance"], 1);
+    /// assert_eq!(entry["level"], "info");
+    /// assert_eq!(entry["message"], "listening");
+    /// ```
+    pub fn to_json(&self, instance: Option<u32>) -> serde_json::Value {
+        use serde_json::{Map, Value};
+
+        let mut entry = match serde_json::from_str(&self.line) {
+            Ok(Value::Object(fields)) => fields,
+            _ => {
+                let mut fields = Map::new();
+                fields.insert("message".to_string(), Value::String(self.line.clone()));
+                fields
+            }
+        };
+
+        let app = instance
+            .and_then(|instance| {
+                self.process_name
+                    .strip_suffix(&format!("-{}", instance))
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| self.process_name.clone());
+        for (key, value) in [
+            (
+                "timestamp",
+                Value::String(self.at.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)),
+            ),
+            ("app", Value::String(app)),
+            ("instance", instance.map_or(Value::Null, Value::from)),
+            ("process_id", Value::String(self.process_id.to_string())),
+            ("stream", Value::String(self.stream.to_string())),
+        ] {
+            entry.entry(key).or_insert(value);
+        }
+        Value::Object(entry)
+    }
+}
+
+/// Render a JSON log entry as a line of text for reading: the timestamp in local
+/// time, the message and any fields the application added as `key=value`.
+///
+/// Lines that are not JSON objects are returned unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmdaemon::logs::humanize_json_line;
+///
+/// let line = r#"{"app":"web","instance":null,"message":"listening","port":3000,"stream":"out"}"#;
+/// assert_eq!(humanize_json_line(line), "listening port=3000");
+/// assert_eq!(humanize_json_line("plain text"), "plain text");
+/// ```
+pub fn humanize_json_line(line: &str) -> String {
+    use serde_json::Value;
+
+    let Ok(Value::Object(mut entry)) = serde_json::from_str::<Value>(line) else {
+        return line.to_string();
+    };
+    for key in ["app", "instance", "process_id", "stream"] {
+        entry.remove(key);
+    }
+
+    let mut parts = Vec::new();
+    match entry.remove("timestamp") {
+        Some(Value::String(timestamp)) => {
+            parts.push(match DateTime::parse_from_rfc3339(&timestamp) {
+                Ok(at) => at
+                    .with_timezone(&Local)
+                    .format("%Y-%m-%d %H:%M:%S%.3f")
+                    .to_string(),
+                Err(_) => timestamp,
+            })
+        }
+        Some(timestamp) => parts.push(timestamp.to_string()),
+        None => {}
+    }
+    match entry.remove("message").or_else(|| entry.remove("msg")) {
+        Some(Value::String(message)) => parts.push(message),
+        Some(message) => parts.push(message.to_string()),
+        None => {}
+    }
+    for (key, value) in entry {
+        match value {
+            Value::String(value) => parts.push(format!("{}={}", key, value)),
+            value => parts.push(format!("{}={}", key, value)),
+        }
+    }
+    parts.join(" ")
+}
+
+/// Time at which a line read back from a log file was written, when the line
+/// records it: the `timestamp` field of JSON logs, or the `date_format` prefix of
+/// text logs
+///
+/// # Examples
+///
+/// ```rust
+/// use pmdaemon::logs::{line_timestamp, LogFormat};
+///
+/// let json = r#"{"message":"ready","timestamp":"2025-06-01T10:00:00.123Z"}"#;
+/// assert!(line_timestamp(json, LogFormat::Json, None).is_some());
+///
+/// let text = "2025-06-01 12:00:00 ready";
+/// assert!(line_timestamp(text, LogFormat::Text, Some("%Y-%m-%d %H:%M:%S")).is_some());
+/// assert!(line_timestamp("ready", LogFormat::Text, None).is_none());
+/// ```
+pub fn line_timestamp(
+    line: &str,
+    format: LogFormat,
+    date_format: Option<&str>,
+) -> Option<DateTime<Utc>> {
+    match format {
+        LogFormat::Json => {
+            let entry: serde_json::Value = serde_json::from_str(line).ok()?;
+            let timestamp = entry.get("timestamp")?.as_str()?;
+            DateTime::parse_from_rfc3339(timestamp)
+                .ok()
+                .map(|at| at.with_timezone(&Utc))
+        }
+        LogFormat::Text => {
+            let date_format = date_format?;
+            if let Ok((at, rest)) = DateTime::parse_and_remainder(line, date_format) {
+                return rest.starts_with(' ').then(|| at.with_timezone(&Utc));
+            }
+            let (at, rest) = chrono::NaiveDateTime::parse_and_remainder(line, date_format).ok()?;
+            if !rest.starts_with(' ') {
+                return None;
+            }
+            Local
+                .from_local_datetime(&at)
+                .earliest()
+                .map(|at| at.with_timezone(&Utc))
+        }
+    }
+}
+
+/// A line read back from a process' log files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogEntry {
+    /// When the line was written, as recorded in the log (see [`line_timestamp`]).
+    /// Lines without a timestamp get the time of the closest earlier line that has
+    /// one, and `None` when there is none.
+    pub at: Option<DateTime<Utc>>,
+    /// Process that wrote the line
+    pub process_id: ProcessId,
+    /// Name of the process
+    pub process_name: String,
+    /// Stream the line was written to
+    pub stream: LogStream,
+    /// The line as written to the log; JSON lines are rendered as text
+    pub line: String,
+}
+
+/// Format of the lines pmdaemon writes to log files
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Plain text, optionally timestamped and tagged with the stream
+    #[default]
+    Text,
+
+    /// One JSON object per line, see [`LogLine::to_json`]
+    Json,
+}
+
+impl fmt::Display for LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogFormat::Text => write!(f, "text"),
+            LogFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for LogFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(Error::config(format!(
+                "Invalid log format '{}' (expected text or json)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Check that `date_format` is a valid timestamp format for log lines
+pub fn validate_date_format(date_format: &str) -> Result<()> {
+    use chrono::format::{Item, StrftimeItems};
+
+    if StrftimeItems::new(date_format).any(|item| matches!(item, Item::Error)) {
+        return Err(Error::config(format!(
+            "Invalid log_date_format '{}' (expected a strftime format such as %Y-%m-%d %H:%M:%S)",
+            date_format
+        )));
+    }
+    Ok(())
+}
+
+/// Open a log file for appending, creating it if needed
+pub(crate) async fn open_append(path: &Path) -> io::Result<tokio::fs::File> {
+    tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await
+}
+
+/// Copies one output stream of a process line by line to its log and the combined log
+struct LogPump {
+    /// Process the stream belongs to
+    process_id: ProcessId,
+    /// Name of the process
+    process_name: String,
+    /// Which stream is pumped
+    stream: LogStream,
+    /// Timestamp format for lines written to the logs
+    date_format: Option<String>,
+    /// Format of the lines written to the logs
+    format: LogFormat,
+    /// Cluster instance number of the process
+    instance: Option<u32>,
+    /// The stream's own log
+    log: Option<tokio::fs::File>,
+    /// Combined log of both streams
+    combined: Option<tokio::fs::File>,
+    /// Subscribers to send each line to
+    hub: Option<Arc<ShimHub>>,
+}
+
+impl LogPump {
+    /// Pump `stream` until it is closed
+    async fn run<R>(mut self, stream: R)
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut reader = BufReader::new(stream);
+        let mut buffer = Vec::new();
+        loop {
+            buffer.clear();
+            match reader.read_until(b'\n', &mut buffer).await {
+                Ok(0) => break,
+                Ok(_) => {
+                    if buffer.ends_with(b"\n") {
+                        buffer.pop();
+                        if buffer.ends_with(b"\r") {
+                            buffer.pop();
+                        }
+                    }
+                    let line = LogLine {
+                        at: Utc::now(),
+                        process_id: self.process_id,
+                        process_name: self.process_name.clone(),
+                        stream: self.stream,
+                        line: String::from_utf8_lossy(&buffer).into_owned(),
+                    };
+                    self.write(line).await;
+                }
+                Err(e) => {
+                    warn!("Failed to read process output: {}", e);
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Append a line to the stream's own log and, tagged, to the combined log, then
+    /// send it to the subscribers as written to the stream's own log
+    async fn write(&mut self, mut line: LogLine) {
+        let hub = self.hub.clone();
+        let _gate = match &hub {
+            Some(hub) => Some(hub.gate.lock().await),
+            None => None,
+        };
+
+        let date_format = self.date_format.as_deref();
+        let json = match self.format {
+            LogFormat::Json => Some(line.to_json(self.instance).to_string()),
+            LogFormat::Text => None,
+        };
+        for (file, tagged) in [(&mut self.log, false), (&mut self.combined, true)] {
+            let Some(file) = file else {
+                continue;
+            };
+            let mut formatted = match &json {
+                Some(json) => json.clone(),
+                None => line.format(date_format, tagged),
+            };
+            formatted.push('\n');
+            let written = match file.write_all(formatted.as_bytes()).await {
+                Ok(()) => file.flush().await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = written {
+                warn!("Failed to write process output to log: {}", e);
+            }
+        }
+
+        if let Some(hub) = hub.as_ref().filter(|hub| hub.lines.receiver_count() > 0) {
+            let end = match &self.log {
+                Some(file) => file.metadata().await.ok().map(|metadata| metadata.len()),
+                None => None,
+            };
+            line.line = json.unwrap_or_else(|| line.format(date_format, false));
+            // Nobody listening is fine
+            let _ = hub.lines.send(ShimMessage::Line { line, end });
+        }
+    }
+}
+
+/// Message a [`LogShim`] sends to a subscriber, one JSON object per line
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ShimMessage {
+    /// First message: the sizes of the stdout and stderr logs when the subscription
+    /// started, so that lines written before it can be read from the logs
+    Subscribed {
+        /// Size of the stdout log
+        out: Option<u64>,
+        /// Size of the stderr log
+        err: Option<u64>,
+    },
+    /// A line as written to its stream's own log
+    Line {
+        /// The line
+        line: LogLine,
+        /// Size of the stream's own log after the line was written
+        end: Option<u64>,
+    },
+}
+
+/// Fans the lines pumped by a [`LogShim`] out to the subscribers on its socket
+struct ShimHub {
+    /// Held while a line is written and sent and while a subscriber joins, so that
+    /// each subscriber starts exactly after the last line it was not sent
+    gate: tokio::sync::Mutex<()>,
+    /// Lines for the subscribers
+    lines: tokio::sync::broadcast::Sender<ShimMessage>,
+    /// Stdout log
+    out: Option<PathBuf>,
+    /// Stderr log
+    err: Option<PathBuf>,
+}
+
+#[cfg(unix)]
+impl ShimHub {
+    /// Number of lines a subscriber may fall behind before it is disconnected
+    const CAPACITY: usize = 1024;
+
+    fn new(out: Option<PathBuf>, err: Option<PathBuf>) -> Self {
+        Self {
+            gate: tokio::sync::Mutex::new(()),
+            lines: tokio::sync::broadcast::channel(Self::CAPACITY).0,
+            out,
+            err,
+        }
+    }
+
+    /// Serve subscribers on `socket` until `done` fires, replacing the socket of a
+    /// previous shim of the process. The returned task then finishes once the
+    /// subscribers were sent the remaining lines.
+    fn serve(
+        self: Arc<Self>,
+        socket: &Path,
+        mut done: tokio::sync::oneshot::Receiver<()>,
+    ) -> io::Result<tokio::task::JoinHandle<()>> {
+        use std::os::unix::fs::PermissionsExt;
+
+        match fs::remove_file(socket) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        let listener = tokio::net::UnixListener::bind(socket)?;
+        // Only the owning user may read the output
+        fs::set_permissions(socket, fs::Permissions::from_mode(0o600))?;
+
+        Ok(tokio::spawn(async move {
+            let mut feeds = tokio::task::JoinSet::new();
+            loop {
+                let accepted = tokio::select! {
+                    accepted = listener.accept() => accepted,
+                    Some(_) = feeds.join_next(), if !feeds.is_empty() => continue,
+                    _ = &mut done => break,
+                };
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        warn!("Failed to accept log subscriber: {}", e);
+                        continue;
+                    }
+                };
+                let (subscribed, lines) = {
+                    let _gate = self.gate.lock().await;
+                    let size = |path: &Option<PathBuf>| {
+                        path.as_ref()
+                            .and_then(|path| fs::metadata(path).ok())
+                            .map(|metadata| metadata.len())
+                    };
+                    let subscribed = ShimMessage::Subscribed {
+                        out: size(&self.out),
+                        err: size(&self.err),
+                    };
+                    (subscribed, self.lines.subscribe())
+                };
+                feeds.spawn(Self::feed(stream, subscribed, lines));
+            }
+            // With the last sender gone, the feeds end after the lines still queued
+            drop(self);
+            while feeds.join_next().await.is_some() {}
+        }))
+    }
+
+    /// Send `first` and then each line to a subscriber until it hangs up. A
+    /// subscriber that falls behind is disconnected; it then catches up from the logs.
+    async fn feed(
+        mut stream: tokio::net::UnixStream,
+        first: ShimMessage,
+        mut lines: tokio::sync::broadcast::Receiver<ShimMessage>,
+    ) {
+        let mut message = first;
+        loop {
+            let Ok(mut payload) = serde_json::to_string(&message) else {
+                return;
+            };
+            payload.push('\n');
+            if stream.write_all(payload.as_bytes()).await.is_err() {
+                return;
+            }
+            message = match lines.recv().await {
+                Ok(message) => message,
+                Err(_) => return,
+            };
+        }
+    }
+}
+
+/// Socket on which the [`LogShim`] of the process with `combined_log` serves the lines
+/// it pumps: the combined log's path with `.sock` appended
+pub fn subscriber_socket(combined_log: &Path) -> PathBuf {
+    let mut socket = combined_log.as_os_str().to_os_string();
+    socket.push(".sock");
+    PathBuf::from(socket)
+}
+
+/// File descriptor a [`LogShim`] reads the process' stderr from; stdout is its stdin
+#[cfg(unix)]
+const SHIM_STDERR_FD: i32 = 3;
+
+/// How long an exiting [`LogShim`] keeps sending its last lines to subscribers
+#[cfg(unix)]
+const SHIM_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Output pump of one process, run as a separate `pmdaemon log-shim` process.
+///
+/// The shim owns the pipes the process writes its stdout and stderr to and copies
+/// each line to the stream's own log and to the combined log. It also sends each line
+/// to the [`LogSubscription`]s connected to its [`socket`](Self::socket). It runs in its own
+/// process group, so it outlives the host that started the process: stopping or
+/// restarting the daemon or the CLI leaves both the process and its logging
+/// untouched. The shim exits once the process (and any child still holding its
+/// output) has closed both streams.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogShim {
+    /// Process whose output is pumped
+    pub process_id: ProcessId,
+    /// Name of the process
+    pub process_name: String,
+    /// Cluster instance number of the process
+    pub instance: Option<u32>,
+    /// Timestamp format for lines written to the logs
+    pub date_format: Option<String>,
+    /// Format of the lines written to the logs
+    pub format: LogFormat,
+    /// Stdout log
+    pub out: Option<PathBuf>,
+    /// Stderr log
+    pub err: Option<PathBuf>,
+    /// Combined log of both streams
+    pub combined: Option<PathBuf>,
+    /// Socket to serve subscribers on, see [`subscriber_socket`]
+    #[serde(default)]
+    pub socket: Option<PathBuf>,
+}
+
+impl LogShim {
+    /// Executable that runs log shims.
+    ///
+    /// This is the `PMDAEMON_LOG_SHIM` environment variable when set, the running
+    /// executable when it is the `pmdaemon` binary, and otherwise a `pmdaemon` binary
+    /// next to the running executable or one directory up (where Cargo puts binaries
+    /// relative to test executables). Returns `None` if there is none.
+    pub fn program() -> Option<PathBuf> {
+        if let Some(program) = std::env::var_os(crate::LOG_SHIM_ENV) {
+            return Some(PathBuf::from(program));
+        }
+        let exe = std::env::current_exe().ok()?;
+        let binary = format!("pmdaemon{}", std::env::consts::EXE_SUFFIX);
+        if exe.file_name() == Some(binary.as_ref()) {
+            return Some(exe);
+        }
+        exe.ancestors()
+            .skip(1)
+            .take(2)
+            .map(|dir| dir.join(&binary))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Start the shim with `program` and return the ends of its pipes that the
+    /// process' stdout and stderr go to
+    #[cfg(unix)]
+    pub(crate) fn spawn(
+        &self,
+        program: &Path,
+    ) -> Result<(std::process::Stdio, std::process::Stdio)> {
+        use std::os::fd::AsRawFd;
+
+        let spec = serde_json::to_string(self)
+            .map_err(|e| Error::internal(format!("Failed to encode log shim arguments: {}", e)))?;
+        let (out_reader, out_writer) = io::pipe()?;
+        let (err_reader, err_writer) = io::pipe()?;
+        let err_fd = err_reader.as_raw_fd();
+
+        let mut cmd = tokio::process::Command::new(program);
+        cmd.arg("log-shim")
+            .arg(spec)
+            .stdin(out_reader)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .process_group(0);
+        // SAFETY: the closure runs in the forked child before exec and only performs
+        // async-signal-safe system calls on a descriptor opened before the fork.
+        unsafe {
+            cmd.pre_exec(move || {
+                // dup2 clears close-on-exec, except when the descriptor already is 3
+                let moved = if err_fd == SHIM_STDERR_FD {
+                    libc::fcntl(err_fd, libc::F_SETFD, 0)
+                } else {
+                    libc::dup2(err_fd, SHIM_STDERR_FD)
+                };
+                if moved < 0 {
+                    return Err(io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+        // The shim is reaped in the background once dropped; its read ends are closed
+        // here along with the command, so the process only keeps the shim as reader
+        cmd.spawn()?;
+        Ok((out_writer.into(), err_writer.into()))
+    }
+
+    /// Log shims are not supported on this platform
+    #[cfg(not(unix))]
+    pub(crate) fn spawn(
+        &self,
+        _program: &Path,
+    ) -> Result<(std::process::Stdio, std::process::Stdio)> {
+        Err(Error::internal("log shims are only supported on Unix"))
+    }
+
+    /// Pump the process' stdout from stdin and its stderr from file descriptor 3 until
+    /// both are closed. This is the body of the `pmdaemon log-shim` command.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a log file cannot be opened.
+    #[cfg(unix)]
+    pub async fn run(self) -> Result<()> {
+        use std::os::fd::{FromRawFd, OwnedFd};
+        use std::os::unix::fs::MetadataExt;
+
+        async fn open(path: Option<&Path>) -> Result<Option<tokio::fs::File>> {
+            match path {
+                Some(path) => Ok(Some(open_append(path).await?)),
+                None => Ok(None),
+            }
+        }
+        // Logging goes on without subscribers if the socket cannot be served
+        let hub = Arc::new(ShimHub::new(self.out.clone(), self.err.clone()));
+        let server = self.socket.as_deref().and_then(|socket| {
+            let (done, serving) = tokio::sync::oneshot::channel();
+            let server = Arc::clone(&hub).serve(socket, serving).ok()?;
+            let inode = fs::metadata(socket).ok()?.ino();
+            Some((server, done, socket, inode))
+        });
+
+        let pump = |stream, log, combined| LogPump {
+            process_id: self.process_id,
+            process_name: self.process_name.clone(),
+            stream,
+            date_format: self.date_format.clone(),
+            format: self.format,
+            instance: self.instance,
+            log,
+            combined,
+            hub: server.as_ref().map(|_| Arc::clone(&hub)),
+        };
+        let out = pump(
+            LogStream::Out,
+            open(self.out.as_deref()).await?,
+            open(self.combined.as_deref()).await?,
+        );
+        let err = pump(
+            LogStream::Err,
+            open(self.err.as_deref()).await?,
+            open(self.combined.as_deref()).await?,
+        );
+
+        // SAFETY: `spawn` hands the shim the read ends of the process' pipes as
+        // stdin and descriptor 3, which nothing else in the shim uses
+        let (stdout, stderr) = unsafe {
+            (
+                std::fs::File::from(OwnedFd::from_raw_fd(0)),
+                std::fs::File::from(OwnedFd::from_raw_fd(SHIM_STDERR_FD)),
+            )
+        };
+        tokio::join!(
+            out.run(tokio::fs::File::from_std(stdout)),
+            err.run(tokio::fs::File::from_std(stderr)),
+        );
+
+        drop(hub);
+        if let Some((server, done, socket, inode)) = server {
+            // A shim started for a restart of the process may have taken the socket over
+            if fs::metadata(socket).is_ok_and(|metadata| metadata.ino() == inode) {
+                let _ = fs::remove_file(socket);
+            }
+            let _ = done.send(());
+            // Subscribers that stopped reading catch up from the logs instead
+            let _ = tokio::time::timeout(SHIM_DRAIN_TIMEOUT, server).await;
+        }
+        Ok(())
+    }
+
+    /// Log shims are not supported on this platform
+    #[cfg(not(unix))]
+    pub async fn run(self) -> Result<()> {
+        Err(Error::internal("log shims are only supported on Unix"))
+    }
+}
+
+/// New output lines of one process, see
+/// [`ProcessManager::subscribe_process_logs`](crate::ProcessManager::subscribe_process_logs)
+///
+/// Lines come straight from the process' [`LogShim`] over its
+/// [socket](subscriber_socket) as they are pumped. While no shim is serving, such as
+/// between a restart of the process and the start of its new shim, or for processes
+/// that write to their logs directly, the log files are read every 500ms instead;
+/// on connecting to a shim, the lines written in the meantime are read from the logs
+/// first, so none are lost or repeated. Lines are delivered as written to the
+/// stream's own log, with any timestamp the shim wrote and in the process'
+/// [`format`](Self::format) (see [`humanize_json_line`] to render JSON lines as text).
+pub struct LogSubscription {
+    /// Process whose lines are delivered
+    process_id: ProcessId,
+    /// Name of the process
+    process_name: String,
+    /// Followers of the stdout and stderr logs
+    followers: Vec<LogFileFollower>,
+    /// Format of the lines in the files
+    format: LogFormat,
+    /// Socket of the process' log shim
+    socket: Option<PathBuf>,
+    /// Connection to the log shim, while one is serving
+    #[cfg(unix)]
+    connection: Option<tokio::io::Lines<BufReader<tokio::net::UnixStream>>>,
+    /// Interval between attempts to connect to the shim and reads of the log files
+    interval: tokio::time::Interval,
+    /// Lines read but not yet delivered
+    pending: VecDeque<LogLine>,
+}
+
+impl LogSubscription {
+    /// Subscription to the lines a process writes to its stdout and stderr logs from
+    /// now on, served by its log shim on `socket` when there is one
+    pub(crate) fn new(
+        process_id: ProcessId,
+        process_name: String,
+        out_log: PathBuf,
+        err_log: PathBuf,
+        socket: Option<PathBuf>,
+        format: LogFormat,
+    ) -> Self {
+        let mut interval = tokio::time::interval(std::time::Duration::from_millis(500));
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        Self {
+            process_id,
+            process_name,
+            followers: vec![
+                LogFileFollower::new(out_log, LogStream::Out),
+                LogFileFollower::new(err_log, LogStream::Err),
+            ],
+            format,
+            socket,
+            #[cfg(unix)]
+            connection: None,
+            interval,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Process whose lines are delivered
+    pub fn process_id(&self) -> ProcessId {
+        self.process_id
+    }
+
+    /// Name of the process
+    pub fn process_name(&self) -> &str {
+        &self.process_name
+    }
+
+    /// Format of the delivered lines
+    pub fn format(&self) -> LogFormat {
+        self.format
+    }
+
+    /// Wait for the next line of the process.
+    ///
+    /// The process being restarted or deleted does not end the subscription. Cancel
+    /// safe, so it can be used in `tokio::select!`.
+    pub async fn next(&mut self) -> Option<LogLine> {
+        loop {
+            if let Some(line) = self.pending.pop_front() {
+                return Some(line);
+            }
+
+            #[cfg(unix)]
+            if let Some(connection) = self.connection.as_mut() {
+                match connection.next_line().await {
+                    Ok(Some(message)) => self.receive(&message),
+                    // The shim exited along with the process
+                    Ok(None) | Err(_) => self.connection = None,
+                }
+                continue;
+            }
+
+            self.interval.tick().await;
+            #[cfg(unix)]
+            if self.connect().await {
+                continue;
+            }
+            for stream in [LogStream::Out, LogStream::Err] {
+                self.read_logs(stream, None);
+            }
+        }
+    }
+
+    /// Connect to the process' log shim and read the lines written to the logs
+    /// before it started serving this subscription. Returns whether it is connected.
+    #[cfg(unix)]
+    async fn connect(&mut self) -> bool {
+        let Some(socket) = &self.socket else {
+            return false;
+        };
+        let Ok(stream) = tokio::net::UnixStream::connect(socket).await else {
+            return false;
+        };
+        let mut connection = BufReader::new(stream).lines();
+        let subscribed = match connection.next_line().await {
+            Ok(Some(message)) => serde_json::from_str::<ShimMessage>(&message).ok(),
+            _ => None,
+        };
+        let Some(ShimMessage::Subscribed { out, err }) = subscribed else {
+            return false;
+        };
+
+        self.read_logs(LogStream::Out, out);
+        self.read_logs(LogStream::Err, err);
+        self.connection = Some(connection);
+        true
+    }
+
+    /// Handle a message from the log shim
+    #[cfg(unix)]
+    fn receive(&mut self, message: &str) {
+        match serde_json::from_str::<ShimMessage>(message) {
+            Ok(ShimMessage::Line { line, end }) => {
+                if let Some(end) = end {
+                    self.follower(line.stream).position = end;
+                }
+                self.pending.push_back(line);
+            }
+            Ok(ShimMessage::Subscribed { .. }) => {}
+            Err(e) => warn!("Ignoring unreadable message from log shim: {}", e),
+        }
+    }
+
+    /// Queue the lines appended to the log of `stream` since the last read, up to the
+    /// log size `end` when given
+    fn read_logs(&mut self, stream: LogStream, end: Option<u64>) {
+        let (process_id, process_name) = (self.process_id, self.process_name.clone());
+        let lines = self.follower(stream).read_new_lines(end);
+        self.pending.extend(lines.into_iter().map(|line| LogLine {
+            at: Utc::now(),
+            process_id,
+            process_name: process_name.clone(),
+            stream,
+            line,
+        }));
+    }
+
+    /// Follower of the log of `stream`
+    fn follower(&mut self, stream: LogStream) -> &mut LogFileFollower {
+        self.followers
+            .iter_mut()
+            .find(|follower| follower.stream == stream)
+            .expect("both streams are followed")
+    }
+}
+
+/// Reads the lines appended to a log file since the last read
+struct LogFileFollower {
+    /// The log file
+    path: PathBuf,
+    /// Stream the log holds
+    stream: LogStream,
+    /// Offset up to which the file has been read
+    position: u64,
+}
+
+impl LogFileFollower {
+    /// Follower that starts at the current end of `path`
+    fn new(path: PathBuf, stream: LogStream) -> Self {
+        let position = fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        Self {
+            path,
+            stream,
+            position,
+        }
+    }
+
+    /// Complete lines appended since the last read and before offset `end` (the end
+    /// of the file if not given); a partly written last line is left for the next read
+    fn read_new_lines(&mut self, end: Option<u64>) -> Vec<String> {
+        use std::io::{Seek, SeekFrom};
+
+        let Ok(metadata) = fs::metadata(&self.path) else {
+            return Vec::new();
+        };
+        let end = end.unwrap_or(u64::MAX).min(metadata.len());
+        if end < self.position {
+            // The file was truncated by log rotation or clearing
+            self.position = 0;
+        }
+        if end == self.position {
+            return Vec::new();
+        }
+
+        let mut buffer = Vec::new();
+        let read = File::open(&self.path).and_then(|mut file| {
+            file.seek(SeekFrom::Start(self.position))?;
+            file.take(end - self.position).read_to_end(&mut buffer)
+        });
+        if let Err(e) = read {
+            warn!("Failed to read log file {:?}: {}", self.path, e);
+            return Vec::new();
+        }
+        let complete = buffer
+            .iter()
+            .rposition(|byte| *byte == b'\n')
+            .map_or(0, |newline| newline + 1);
+        self.position += complete as u64;
+        String::from_utf8_lossy(&buffer[..complete])
+            .lines()
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+/// Time-based log rotation schedule.
+///
+/// Periods start on UTC boundaries: the top of the hour, midnight, or Monday midnight.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RotationInterval {
+    /// Rotate at the start of every hour
+    Hourly,
+
+    /// Rotate at midnight
+    Daily,
+
+    /// Rotate at midnight between Sunday and Monday
+    Weekly,
+}
+
+impl RotationInterval {
+    /// Start of the rotation period that contains `time`
+    pub fn period_start(self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let day = time.date_naive();
+        let start = match self {
+            RotationInterval::Hourly => day.and_hms_opt(time.hour(), 0, 0),
+            RotationInterval::Daily => day.and_hms_opt(0, 0, 0),
+            RotationInterval::Weekly => {
+                let monday = day - Duration::days(day.weekday().num_days_from_monday() as i64);
+                monday.and_hms_opt(0, 0, 0)
+            }
+        };
+        Utc.from_utc_datetime(&start.expect("midnight and whole hours are valid times"))
+    }
+}
+
+impl fmt::Display for RotationInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RotationInterval::Hourly => write!(f, "hourly"),
+            RotationInterval::Daily => write!(f, "daily"),
+            RotationInterval::Weekly => write!(f, "weekly"),
+        }
+    }
+}
+
+impl FromStr for RotationInterval {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "hourly" => Ok(RotationInterval::Hourly),
+            "daily" => Ok(RotationInterval::Daily),
+            "weekly" => Ok(RotationInterval::Weekly),
+            _ => Err(Error::config(format!(
+                "Invalid rotation interval '{}' (expected hourly, daily or weekly)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Log rotation settings for a process
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct LogRotationConfig {
+    /// Rotate a log file once it reaches this many bytes
+    pub max_size: Option<u64>,
+
+    /// Number of rotated segments to keep per log file (default: 10)
+    pub retain: usize,
+
+    /// Compress rotated segments with gzip
+    pub compress: bool,
+
+    /// Also rotate at the start of every period
+    pub interval: Option<RotationInterval>,
+}
+
+impl Default for LogRotationConfig {
+    fn default() -> Self {
+        Self {
+            max_size: None,
+            retain: crate::DEFAULT_LOG_RETAIN,
+            compress: false,
+            interval: None,
+        }
+    }
+}
+
+impl LogRotationConfig {
+    /// Rotate once a log file reaches `bytes`
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.max_size = Some(bytes);
+        self
+    }
+
+    /// Keep this many rotated segments per log file
+    pub fn retain(mut self, segments: usize) -> Self {
+        self.retain = segments;
+        self
+    }
+
+    /// Compress rotated segments with gzip
+    pub fn compress(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Rotate at the start of every `interval` period
+    pub fn interval(mut self, interval: RotationInterval) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Validate the rotation settings
+    pub fn validate(&self) -> Result<()> {
+        match (self.max_size, self.interval) {
+            (Some(0), _) => Err(Error::config("Log max_size must be greater than 0")),
+            (None, None) => Err(Error::config(
+                "Log rotation requires max_size and/or interval",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether a log file of `size` bytes, last rotated at `last_rotation`, is due
+    /// for rotation at `now`. Empty files are never rotated.
+    pub fn is_due(&self, size: u64, last_rotation: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        if size == 0 {
+            return false;
+        }
+        self.max_size.is_some_and(|max_size| size >= max_size)
+            || self
+                .interval
+                .is_some_and(|interval| interval.period_start(now) > last_rotation)
+    }
+}
+
+/// Path of the `index`-th rotated segment of the log at `path` (1 is the newest)
+pub fn segment_path(path: &Path, index: usize, compressed: bool) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(format!(".{}", index));
+    if compressed {
+        name.push(".gz");
+    }
+    PathBuf::from(name)
+}
+
+/// Rotated segments of the log at `path` that exist on disk, newest first
+pub fn segments(path: &Path) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    for index in 1.. {
+        let segment = [false, true]
+            .into_iter()
+            .map(|compressed| segment_path(path, index, compressed))
+            .find(|segment| segment.exists());
+        match segment {
+            Some(segment) => found.push(segment),
+            None => break,
+        }
+    }
+    found
+}
+
+/// Rotate the log at `path` if it is not empty.
+///
+/// Returns whether the file was rotated. Segments beyond `retain` are deleted.
+pub async fn rotate(path: &Path, config: &LogRotationConfig) -> Result<bool> {
+    let path = path.to_path_buf();
+    let config = config.clone();
+    let rotated = tokio::task::spawn_blocking(move || rotate_blocking(&path, &config))
+        .await
+        .map_err(|e| Error::internal(format!("Log rotation task failed: {}", e)))??;
+    Ok(rotated)
+}
+
+fn rotate_blocking(path: &Path, config: &LogRotationConfig) -> io::Result<bool> {
+    let mut source = match OpenOptions::new().read(true).write(true).open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    if source.metadata()?.len() == 0 {
+        return Ok(false);
+    }
+
+    // Make room for the new segment: drop what falls out of retention, shift the rest
+    for (position, segment) in segments(path).iter().enumerate().rev() {
+        let index = position + 1;
+        if index >= config.retain {
+            fs::remove_file(segment)?;
+        } else {
+            let compressed = segment.extension().is_some_and(|ext| ext == "gz");
+            fs::rename(segment, segment_path(path, index + 1, compressed))?;
+        }
+    }
+
+    if config.retain > 0 {
+        let target = File::create(segment_path(path, 1, config.compress))?;
+        if config.compress {
+            let mut encoder = GzEncoder::new(target, Compression::default());
+            io::copy(&mut source, &mut encoder)?;
+            encoder.finish()?;
+        } else {
+            let mut target = target;
+            io::copy(&mut source, &mut target)?;
+        }
+    }
+
+    // Truncate in place so that the process keeps writing to the same file
+    source.set_len(0)?;
+    Ok(true)
+}
+
+/// Read the last `lines` lines of the log at `path`, continuing into its rotated
+/// segments when the current file holds fewer lines. Lines are returned oldest first.
+pub async fn tail_lines(path: &Path, lines: usize) -> Result<Vec<String>> {
+    let path = path.to_path_buf();
+    let lines = tokio::task::spawn_blocking(move || tail_lines_blocking(&path, lines))
+        .await
+        .map_err(|e| Error::internal(format!("Log reading task failed: {}", e)))??;
+    Ok(lines)
+}
+
+fn tail_lines_blocking(path: &Path, lines: usize) -> io::Result<Vec<String>> {
+    let mut files = vec![path.to_path_buf()];
+    files.extend(segments(path));
+
+    // Newest chunks first; each chunk is in file order
+    let mut chunks = Vec::new();
+    let mut remaining = lines;
+    for file in files {
+        if remaining == 0 {
+            break;
+        }
+        let chunk = tail_file(&file, remaining)?;
+        remaining -= chunk.len();
+        chunks.push(chunk);
+    }
+
+    Ok(chunks.into_iter().rev().flatten().collect())
+}
+
+/// Read the last `lines` lines of a single log file or segment. Plain files are read
+/// backwards from the end in blocks; compressed segments are decompressed as a stream.
+fn tail_file(path: &Path, lines: usize) -> io::Result<Vec<String>> {
+    use std::io::{Seek, SeekFrom};
+    const BLOCK_SIZE: u64 = 64 * 1024;
+
+    let mut file = match File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+    if !is_compressed(path) {
+        // Find the start of the last lines; the line ending at the very end of the
+        // file does not start another line
+        let mut start = file.metadata()?.len();
+        let mut newlines = 0;
+        let mut block = vec![0; BLOCK_SIZE as usize];
+        while start > 0 && newlines <= lines {
+            let size = BLOCK_SIZE.min(start);
+            start -= size;
+            file.seek(SeekFrom::Start(start))?;
+            let chunk = &mut block[..size as usize];
+            file.read_exact(chunk)?;
+            newlines += chunk.iter().filter(|byte| **byte == b'\n').count();
+        }
+        file.seek(SeekFrom::Start(start))?;
+    }
+
+    let mut tail = VecDeque::new();
+    for_each_line(log_reader(path, file), |line| {
+        tail.push_back(line);
+        if tail.len() > lines {
+            tail.pop_front();
+        }
+        true
+    })?;
+    Ok(tail.into())
+}
+
+/// Whether `path` is a gzip-compressed rotated segment
+fn is_compressed(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "gz")
+}
+
+/// Buffered reader over the lines of an open log file or segment, decompressing
+/// `.gz` segments
+fn log_reader(path: &Path, file: File) -> Box<dyn io::BufRead> {
+    if is_compressed(path) {
+        Box::new(io::BufReader::new(GzDecoder::new(file)))
+    } else {
+        Box::new(io::BufReader::new(file))
+    }
+}
+
+/// Call `f` with each line of `reader`, without line endings and with invalid UTF-8
+/// replaced, until it returns `false`
+fn for_each_line(
+    mut reader: impl io::BufRead,
+    mut f: impl FnMut(String) -> bool,
+) -> io::Result<()> {
+    let mut buffer = Vec::new();
+    loop {
+        buffer.clear();
+        if reader.read_until(b'\n', &mut buffer)? == 0 {
+            return Ok(());
+        }
+        if buffer.ends_with(b"\n") {
+            buffer.pop();
+            if buffer.ends_with(b"\r") {
+                buffer.pop();
+            }
+        }
+        if !f(String::from_utf8_lossy(&buffer).into_owned()) {
+            return Ok(());
+        }
+    }
+}
+
+/// Severity of a log line, see [`LogLevel::of_line`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    /// Tracing output
+    Trace,
+    /// Debugging output
+    Debug,
+    /// Informational messages
+    Info,
+    /// Warnings
+    Warn,
+    /// Errors
+    Error,
+    /// Fatal errors
+    Fatal,
+}
+
+impl LogLevel {
+    /// The level of a line as written to a log in `format`.
+    ///
+    /// JSON lines use their `level` field, either a name or a pino/bunyan style
+    /// number (`30` is info, `50` is error). Text lines use the first level name in
+    /// the line, such as `ERROR`, `[warn]` or `level=info`. Lines without a level
+    /// yield `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmdaemon::logs::{LogFormat, LogLevel};
+    ///
+    /// let text = "2025-06-01 12:00:00 [WARN] disk almost full";
+    /// assert_eq!(LogLevel::of_line(text, LogFormat::Text), Some(LogLevel::Warn));
+    ///
+    /// let json = r#"{"level":50,"msg":"query failed"}"#;
+    /// assert_eq!(LogLevel::of_line(json, LogFormat::Json), Some(LogLevel::Error));
+    /// ```
+    pub fn of_line(line: &str, format: LogFormat) -> Option<Self> {
+        if format == LogFormat::Json {
+            if let Ok(serde_json::Value::Object(entry)) = serde_json::from_str(line) {
+                return match entry.get("level")? {
+                    serde_json::Value::String(level) => level.parse().ok(),
+                    serde_json::Value::Number(level) => Some(match level.as_u64()? {
+                        0..=10 => LogLevel::Trace,
+                        11..=20 => LogLevel::Debug,
+                        21..=30 => LogLevel::Info,
+                        31..=40 => LogLevel::Warn,
+                        41..=50 => LogLevel::Error,
+                        _ => LogLevel::Fatal,
+                    }),
+                    _ => None,
+                };
+            }
+        }
+        line.split(|c: char| !c.is_ascii_alphabetic())
+            .filter(|word| word.len() >= 4)
+            .find_map(|word| match word.to_ascii_lowercase().as_str() {
+                // Shorthands like "err" are too common in messages to count here
+                "trace" | "debug" | "info" | "warn" | "warning" | "error" | "fatal"
+                | "critical" => word.parse().ok(),
+                _ => None,
+            })
+    }
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogLevel::Trace => write!(f, "trace"),
+            LogLevel::Debug => write!(f, "debug"),
+            LogLevel::Info => write!(f, "info"),
+            LogLevel::Warn => write!(f, "warn"),
+            LogLevel::Error => write!(f, "error"),
+            LogLevel::Fatal => write!(f, "fatal"),
+        }
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "trace" => Ok(LogLevel::Trace),
+            "debug" => Ok(LogLevel::Debug),
+            "info" => Ok(LogLevel::Info),
+            "warn" | "warning" => Ok(LogLevel::Warn),
+            "error" | "err" => Ok(LogLevel::Error),
+            "fatal" | "critical" | "crit" | "panic" => Ok(LogLevel::Fatal),
+            _ => Err(Error::invalid_argument(format!(
+                "Invalid log level '{}' (expected trace, debug, info, warn, error or fatal)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Serializes a search pattern as its source text
+mod pattern_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S>(
+        pattern: &Option<regex::Regex>,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        pattern
+            .as_ref()
+            .map(regex::Regex::as_str)
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(
+        deserializer: D,
+    ) -> std::result::Result<Option<regex::Regex>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|pattern| regex::Regex::new(&pattern).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+/// Which lines to find in a process' logs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogSearch {
+    /// Only lines matching this regular expression; JSON lines are matched as text
+    /// (see [`humanize_json_line`])
+    #[serde(with = "pattern_serde")]
+    pub pattern: Option<regex::Regex>,
+    /// Only lines written at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only lines written at or before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Only lines of at least this level, see [`LogLevel::of_line`]
+    pub level: Option<LogLevel>,
+    /// Only lines of these streams; both when empty
+    pub streams: Vec<LogStream>,
+    /// Only the most recent matching lines, at most this many
+    pub limit: Option<usize>,
+}
+
+impl LogSearch {
+    /// Whether lines of `stream` are searched
+    pub fn includes(&self, stream: LogStream) -> bool {
+        self.streams.is_empty() || self.streams.contains(&stream)
+    }
+
+    /// Whether `line`, as written to a log in `format`, is found by the search: it
+    /// belongs to a searched stream, was written within the time window, has at least
+    /// the searched level and its text matches the pattern. `limit` does not apply.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use pmdaemon::logs::{LogFormat, LogLevel, LogLine, LogSearch, LogStream};
+    ///
+    /// let search = LogSearch {
+    ///     pattern: Some(regex::Regex::new("query").unwrap()),
+    ///     level: Some(LogLevel::Error),
+    ///     ..Default::default()
+    /// };
+    /// let line = LogLine {
+    ///     at: chrono::Utc::now(),
+    ///     process_id: uuid::Uuid::new_v4(),
+    ///     process_name: "api".to_string(),
+    ///     stream: LogStream::Out,
+    ///     line: r#"{"level":50,"msg":"query failed"}"#.to_string(),
+    /// };
+    /// assert!(search.matches(&line, LogFormat::Json));
+    /// assert!(!search.matches(&line, LogFormat::Text));
+    /// ```
+    pub fn matches(&self, line: &LogLine, format: LogFormat) -> bool {
+        self.includes(line.stream)
+            && self.since.is_none_or(|since| line.at >= since)
+            && self.until.is_none_or(|until| line.at <= until)
+            && self.level.is_none_or(|min| {
+                LogLevel::of_line(&line.line, format).is_some_and(|level| level >= min)
+            })
+            && self.pattern.as_ref().is_none_or(|pattern| match format {
+                LogFormat::Json => pattern.is_match(&humanize_json_line(&line.line)),
+                LogFormat::Text => pattern.is_match(&line.line),
+            })
+    }
+}
+
+/// Find the lines of the log at `path` and its rotated segments that match `search`,
+/// oldest first, with the time each line was written (see [`LogEntry::at`]).
+///
+/// Files are read line by line, so only the matching lines are held in memory.
+/// Segments last modified before `search.since` are skipped, and reading stops at the
+/// first line after `search.until`. JSON lines are returned as text.
+pub async fn search_lines(
+    path: &Path,
+    stream: LogStream,
+    format: LogFormat,
+    date_format: Option<&str>,
+    search: &LogSearch,
+) -> Result<Vec<(Option<DateTime<Utc>>, String)>> {
+    let path = path.to_path_buf();
+    let date_format = date_format.map(str::to_string);
+    let search = search.clone();
+    let lines = tokio::task::spawn_blocking(move || {
+        search_lines_blocking(&path, stream, format, date_format.as_deref(), &search)
+    })
+    .await
+    .map_err(|e| Error::internal(format!("Log search task failed: {}", e)))??;
+    Ok(lines)
+}
+
+fn search_lines_blocking(
+    path: &Path,
+    stream: LogStream,
+    format: LogFormat,
+    date_format: Option<&str>,
+    search: &LogSearch,
+) -> io::Result<Vec<(Option<DateTime<Utc>>, String)>> {
+    // Oldest segment first
+    let mut files = segments(path);
+    files.reverse();
+    files.push(path.to_path_buf());
+
+    let mut found = VecDeque::new();
+    let mut at = None;
+    let mut done = false;
+    for file in files {
+        if done {
+            break;
+        }
+        let handle = match File::open(&file) {
+            Ok(handle) => handle,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e),
+        };
+        if let Some(since) = search.since {
+            // Every line of a file was written by the time it was last modified
+            if let Ok(modified) = handle.metadata().and_then(|m| m.modified()) {
+                let modified = DateTime::<Utc>::from(modified);
+                if modified < since {
+                    at = Some(modified);
+                    continue;
+                }
+            }
+        }
+
+        for_each_line(log_reader(&file, handle), |line| {
+            if let Some(timestamp) = line_timestamp(&line, format, date_format) {
+                at = Some(timestamp);
+            }
+            // Logs are written in order, so no later line is in the time window
+            if search
+                .until
+                .is_some_and(|until| at.is_some_and(|at| at > until))
+            {
+                done = true;
+                return false;
+            }
+            // Only the stream, time and text of a line are searched. Lines before the
+            // first timestamp count as the oldest, so that `since` skips them.
+            let line = LogLine {
+                at: at.unwrap_or(DateTime::<Utc>::MIN_UTC),
+                process_id: ProcessId::nil(),
+                process_name: String::new(),
+                stream,
+                line,
+            };
+            if !search.matches(&line, format) {
+                return true;
+            }
+            let line = match format {
+                LogFormat::Json => humanize_json_line(&line.line),
+                LogFormat::Text => line.line,
+            };
+            found.push_back((at, line));
+            if search.limit.is_some_and(|limit| found.len() > limit) {
+                found.pop_front();
+            }
+            true
+        })?;
+    }
+    Ok(found.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_validate_date_format() {
+        assert!(validate_date_format("%Y-%m-%d %H:%M:%S%.3f").is_ok());
+        assert!(validate_date_format("%Q").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pump_tags_lines() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("out.log");
+        let combined = dir.path().join("all.log");
+
+        let pump = LogPump {
+            process_id: uuid::Uuid::new_v4(),
+            process_name: "web".to_string(),
+            stream: LogStream::Err,
+            date_format: None,
+            format: LogFormat::Text,
+            instance: None,
+            log: Some(open_append(&out).await.unwrap()),
+            combined: Some(open_append(&combined).await.unwrap()),
+            hub: None,
+        };
+        pump.run(&b"first\r\nsecond\nunterminated"[..]).await;
+
+        assert_eq!(
+            fs::read_to_string(&out).unwrap(),
+            "first\nsecond\nunterminated\n"
+        );
+        assert_eq!(
+            fs::read_to_string(&combined).unwrap(),
+            "[err] first\n[err] second\n[err] unterminated\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_log_shim_pumps_both_streams() {
+        let dir = TempDir::new().unwrap();
+        let log = |name: &str| dir.path().join(name);
+        let shim = LogShim {
+            process_id: uuid::Uuid::new_v4(),
+            process_name: "web".to_string(),
+            instance: None,
+            date_format: None,
+            format: LogFormat::Text,
+            out: Some(log("out.log")),
+            err: Some(log("err.log")),
+            combined: Some(log("all.log")),
+            socket: None,
+        };
+        let program = LogShim::program().expect("pmdaemon is built along with the tests");
+        let (stdout, stderr) = shim.spawn(&program).unwrap();
+        let status = std::process::Command::new("sh")
+            .args(["-c", "echo first; echo second >&2"])
+            .stdout(stdout)
+            .stderr(stderr)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let combined = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let combined = fs::read_to_string(log("all.log")).unwrap_or_default();
+                if combined.lines().count() == 2 {
+                    return combined;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(combined.contains("[out] first\n") && combined.contains("[err] second\n"));
+        assert_eq!(fs::read_to_string(log("out.log")).unwrap(), "first\n");
+        assert_eq!(fs::read_to_string(log("err.log")).unwrap(), "second\n");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_log_shim_serves_subscribers() {
+        let dir = TempDir::new().unwrap();
+        let log = |name: &str| dir.path().join(name);
+        let socket = subscriber_socket(&log("all.log"));
+        let process_id = uuid::Uuid::new_v4();
+        let shim = LogShim {
+            process_id,
+            process_name: "web".to_string(),
+            instance: None,
+            date_format: None,
+            format: LogFormat::Text,
+            out: Some(log("out.log")),
+            err: Some(log("err.log")),
+            combined: Some(log("all.log")),
+            socket: Some(socket.clone()),
+        };
+        let mut subscription = LogSubscription::new(
+            process_id,
+            "web".to_string(),
+            log("out.log"),
+            log("err.log"),
+            Some(socket.clone()),
+            LogFormat::Text,
+        );
+
+        let program = LogShim::program().expect("pmdaemon is built along with the tests");
+        let (stdout, stderr) = shim.spawn(&program).unwrap();
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "echo early; sleep 1; echo late >&2"])
+            .stdout(stdout)
+            .stderr(stderr)
+            .spawn()
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while !socket.exists()
+                || fs::read_to_string(log("out.log"))
+                    .unwrap_or_default()
+                    .is_empty()
+            {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // A line written before the subscription connected is read from the log,
+        // later ones come from the shim
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            let line = tokio::time::timeout(std::time::Duration::from_secs(5), subscription.next())
+                .await
+                .unwrap()
+                .unwrap();
+            received.push((line.stream, line.line));
+        }
+        assert_eq!(
+            received,
+            [
+                (LogStream::Out, "early".to_string()),
+                (LogStream::Err, "late".to_string())
+            ]
+        );
+        assert!(subscription.connection.is_some());
+
+        // The shim removes its socket when the process is done
+        assert!(child.wait().unwrap().success());
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while socket.exists() {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[test]
+    fn test_json_lines_merge_application_fields() {
+        let mut line = LogLine {
+            at: Utc::now(),
+            process_id: uuid::Uuid::new_v4(),
+            process_name: "api-2".to_string(),
+            stream: LogStream::Err,
+            line: "plain text".to_string(),
+        };
+        let entry = line.to_json(Some(2));
+        assert_eq!(entry["message"], "plain text");
+        assert_eq!(entry["app"], "api");
+        assert_eq!(entry["instance"], 2);
+        assert_eq!(entry["stream"], "err");
+        assert_eq!(entry["process_id"], line.process_id.to_string());
+
+        line.line = r#"{"msg":"ready","level":"info","app":"custom"}"#.to_string();
+        let entry = line.to_json(None);
+        assert_eq!(entry["msg"], "ready");
+        assert_eq!(entry["level"], "info");
+        assert_eq!(entry["app"], "custom");
+        assert!(entry["instance"].is_null());
+        assert!(entry.get("message").is_none());
+
+        // JSON that is not an object is kept as the message
+        line.line = "[1, 2]".to_string();
+        assert_eq!(line.to_json(None)["message"], "[1, 2]");
+    }
+
+    #[test]
+    fn test_humanize_json_line() {
+        assert_eq!(
+            humanize_json_line(r#"{"msg":"ready","level":"info","app":"api","stream":"out"}"#),
+            "ready level=info"
+        );
+        assert!(
+            humanize_json_line(r#"{"timestamp":"2026-01-02T03:04:05.678Z","message":"hi"}"#)
+                .ends_with(".678 hi")
+        );
+        assert_eq!(humanize_json_line("not json {"), "not json {");
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert!("yaml".parse::<LogFormat>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_pump_writes_json_lines() {
+        let dir = TempDir::new().unwrap();
+        let out = dir.path().join("out.log");
+        let combined = dir.path().join("all.log");
+
+        let pump = LogPump {
+            process_id: uuid::Uuid::new_v4(),
+            process_name: "web-0".to_string(),
+            stream: LogStream::Out,
+            date_format: Some("%H:%M:%S".to_string()),
+            format: LogFormat::Json,
+            instance: Some(0),
+            log: Some(open_append(&out).await.unwrap()),
+            combined: Some(open_append(&combined).await.unwrap()),
+            hub: None,
+        };
+        pump.run(&b"hello\n{\"level\":\"warn\",\"message\":\"slow\"}\n"[..])
+            .await;
+
+        let logged = fs::read_to_string(&out).unwrap();
+        assert_eq!(logged, fs::read_to_string(&combined).unwrap());
+        let entries: Vec<serde_json::Value> = logged
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["message"], "hello");
+        assert_eq!(entries[0]["app"], "web");
+        assert_eq!(entries[0]["instance"], 0);
+        assert_eq!(entries[0]["stream"], "out");
+        assert_eq!(entries[1]["message"], "slow");
+        assert_eq!(entries[1]["level"], "warn");
+    }
+
+    #[test]
+    fn test_file_follower_reads_complete_new_lines() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("app-out.log");
+        write_lines(&path, 0..2);
+
+        let mut follower = LogFileFollower::new(path.clone(), LogStream::Out);
+        assert!(follower.read_new_lines(None).is_empty());
+
+        write_lines(&path, 2..3);
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, b"partial").unwrap();
+        assert_eq!(follower.read_new_lines(None), numbered(2..3));
+
+        std::io::Write::write_all(&mut file, b" line\n").unwrap();
+        assert_eq!(
+            follower.read_new_lines(None),
+            vec!["partial line".to_string()]
+        );
+
+        // Reading can stop at an offset, such as where a log shim took over
+        let end = fs::metadata(&path).unwrap().len();
+        write_lines(&path, 3..5);
+        let bounded = fs::metadata(&path).unwrap().len() - "line 4\n".len() as u64;
+        assert_eq!(follower.read_new_lines(Some(bounded)), numbered(3..4));
+        follower.position = end;
+        assert_eq!(follower.read_new_lines(None), numbered(3..5));
+
+        // Truncation by rotation starts over at the beginning of the file
+        fs::write(&path, "after rotation\n").unwrap();
+        assert_eq!(
+            follower.read_new_lines(None),
+            vec!["after rotation".to_string()]
+        );
+    }
+
+    fn write_lines(path: &Path, range: std::ops::Range<usize>) {
+        use std::io::Write;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap();
+        for i in range {
+            writeln!(file, "line {}", i).unwrap();
+        }
+    }
+
+    fn numbered(range: std::ops::Range<usize>) -> Vec<String> {
+        range.map(|i| format!("line {}", i)).collect()
+    }
+
+    #[test]
+    fn test_rotation_config_validate() {
+        assert!(LogRotationConfig::default().validate().is_err());
+        assert!(LogRotationConfig::default().max_size(0).validate().is_err());
+        assert!(LogRotationConfig::default()
+            .max_size(1024)
+            .validate()
+            .is_ok());
+        assert!(LogRotationConfig::default()
+            .interval(RotationInterval::Daily)
+            .validate()
+            .is_ok());
+
+        let config: LogRotationConfig =
+            serde_json::from_str(r#"{"max_size": 1024, "interval": "hourly"}"#).unwrap();
+        assert_eq!(config.retain, crate::DEFAULT_LOG_RETAIN);
+        assert_eq!(config.interval, Some(RotationInterval::Hourly));
+        assert!("monthly".parse::<RotationInterval>().is_err());
+    }
+
+    #[test]
+    fn test_rotation_due() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 11, 15, 30, 0).unwrap(); // a Wednesday
+        let config = LogRotationConfig::default().max_size(100);
+        assert!(!config.is_due(0, now, now));
+        assert!(!config.is_due(99, now, now));
+        assert!(config.is_due(100, now, now));
+
+        let config = LogRotationConfig::default().interval(RotationInterval::Daily);
+        let earlier_today = Utc.with_ymd_and_hms(2026, 3, 11, 0, 5, 0).unwrap();
+        let yesterday = Utc.with_ymd_and_hms(2026, 3, 10, 23, 59, 0).unwrap();
+        assert!(!config.is_due(10, earlier_today, now));
+        assert!(config.is_due(10, yesterday, now));
+        assert!(!config.is_due(0, yesterday, now));
+
+        assert_eq!(
+            RotationInterval::Hourly.period_start(now),
+            Utc.with_ymd_and_hms(2026, 3, 11, 15, 0, 0).unwrap()
+        );
+        assert_eq!(
+            RotationInterval::Weekly.period_start(now),
+            Utc.with_ymd_and_hms(2026, 3, 9, 0, 0, 0).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rotate_keeps_retained_segments() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("app-out.log");
+        let config = LogRotationConfig::default().max_size(1).retain(2);
+
+        assert!(!rotate(&path, &config).await.unwrap());
+        for round in 0..3 {
+            write_lines(&path, round * 10..round * 10 + 10);
+            assert!(rotate(&path, &config).await.unwrap());
+        }
+        write_lines(&path, 30..35);
+
+        // The file is truncated in place and only two segments are kept
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            numbered(30..35).join("\n") + "\n"
+        );
+        assert_eq!(
+            segments(&path),
+            vec![segment_path(&path, 1, false), segment_path(&path, 2, false)]
+        );
+        assert_eq!(tail_lines(&path, 100).await.unwrap(), numbered(10..35));
+        assert_eq!(tail_lines(&path, 8).await.unwrap(), numbered(27..35));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_compressed() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("app-error.log");
+        let config = LogRotationConfig::default().max_size(1).compress(true);
+
+        write_lines(&path, 0..10);
+        rotate(&path, &config).await.unwrap();
+        write_lines(&path, 10..12);
+
+        let segment = segment_path(&path, 1, true);
+        assert!(segment.exists());
+        assert_eq!(segments(&path), vec![segment]);
+        assert_eq!(tail_lines(&path, 5).await.unwrap(), numbered(7..12));
+    }
+
+    #[test]
+    fn test_tail_lines_reads_from_the_end_in_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("big-out.log");
+        // Spans several read blocks
+        write_lines(&path, 0..20_000);
+
+        assert_eq!(tail_file(&path, 3).unwrap(), numbered(19_997..20_000));
+        assert_eq!(tail_file(&path, 20_000).unwrap(), numbered(0..20_000));
+        assert_eq!(tail_file(&path, 0).unwrap(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_log_level_of_line() {
+        let level = |line| LogLevel::of_line(line, LogFormat::Text);
+        assert_eq!(level("ERROR connection refused"), Some(LogLevel::Error));
+        assert_eq!(level("[info] no error here"), Some(LogLevel::Info));
+        assert_eq!(level("ts=1 level=warning msg=slow"), Some(LogLevel::Warn));
+        assert_eq!(level("listening on :3000"), None);
+        assert_eq!(level("[err] short tags do not count"), None);
+
+        let json = |line| LogLevel::of_line(line, LogFormat::Json);
+        assert_eq!(json(r#"{"level":"debug"}"#), Some(LogLevel::Debug));
+        assert_eq!(json(r#"{"level":60}"#), Some(LogLevel::Fatal));
+        assert_eq!(json(r#"{"msg":"no level"}"#), None);
+        assert_eq!(json("WARN not json"), Some(LogLevel::Warn));
+
+        assert!(LogLevel::Error > LogLevel::Warn);
+        assert_eq!("CRITICAL".parse::<LogLevel>().unwrap(), LogLevel::Fatal);
+        assert!("loud".parse::<LogLevel>().is_err());
+    }
+
+    #[test]
+    fn test_log_search_matches_followed_lines() {
+        let at = Utc.with_ymd_and_hms(2026, 3, 11, 12, 0, 0).unwrap();
+        let line = |stream, line: &str| LogLine {
+            at,
+            process_id: ProcessId::new_v4(),
+            process_name: "api".to_string(),
+            stream,
+            line: line.to_string(),
+        };
+        let json = line(LogStream::Out, r#"{"level":50,"msg":"query failed"}"#);
+        let errors = LogSearch {
+            level: Some(LogLevel::Error),
+            ..Default::default()
+        };
+        assert!(errors.matches(&json, LogFormat::Json));
+        assert!(!errors.matches(&json, LogFormat::Text));
+
+        // Patterns match JSON lines as rendered for display
+        let pattern = LogSearch {
+            pattern: Some(regex::Regex::new("^query failed level=50$").unwrap()),
+            ..Default::default()
+        };
+        assert!(pattern.matches(&json, LogFormat::Json));
+        assert!(!pattern.matches(&json, LogFormat::Text));
+
+        let err = line(LogStream::Err, "ERROR refused");
+        let window = LogSearch {
+            streams: vec![LogStream::Err],
+            since: Some(at - Duration::minutes(1)),
+            until: Some(at),
+            ..Default::default()
+        };
+        assert!(window.matches(&err, LogFormat::Text));
+        assert!(!window.matches(&line(LogStream::Out, "ERROR refused"), LogFormat::Text));
+        let later = LogSearch {
+            since: Some(at + Duration::seconds(1)),
+            ..window
+        };
+        assert!(!later.matches(&err, LogFormat::Text));
+    }
+
+    #[tokio::test]
+    async fn test_search_lines_across_segments() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("api-out.log");
+        let date_format = "%Y-%m-%dT%H:%M:%S%z";
+        let base = Utc.with_ymd_and_hms(2026, 3, 11, 12, 0, 0).unwrap();
+        let write = |range: std::ops::Range<i64>| {
+            use std::io::Write;
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .unwrap();
+            for minute in range {
+                let at = base + Duration::minutes(minute);
+                let level = if minute % 2 == 0 { "INFO" } else { "ERROR" };
+                writeln!(
+                    file,
+                    "{} {} request {}",
+                    at.format(date_format),
+                    level,
+                    minute
+                )
+                .unwrap();
+                writeln!(file, "  continued {}", minute).unwrap();
+            }
+        };
+        let config = LogRotationConfig::default().max_size(1).compress(true);
+        write(0..10);
+        rotate(&path, &config).await.unwrap();
+        write(10..20);
+
+        let search = |search: LogSearch| {
+            let path = path.clone();
+            async move {
+                search_lines(
+                    &path,
+                    LogStream::Out,
+                    LogFormat::Text,
+                    Some(date_format),
+                    &search,
+                )
+                .await
+                .unwrap()
+            }
+        };
+        let requests = |found: Vec<(Option<DateTime<Utc>>, String)>| -> Vec<String> {
+            found
+                .into_iter()
+                .map(|(_, line)| line.rsplit(' ').next().unwrap().to_string())
+                .collect()
+        };
+
+        // Time window across the compressed segment and the current file
+        let window = search(LogSearch {
+            pattern: Some(regex::Regex::new("request").unwrap()),
+            since: Some(base + Duration::minutes(8)),
+            until: Some(base + Duration::minutes(11)),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(requests(window), vec!["8", "9", "10", "11"]);
+
+        // Continuation lines take the time of the line before them
+        let continued = search(LogSearch {
+            pattern: Some(regex::Regex::new("continued 1[89]").unwrap()),
+            since: Some(base + Duration::minutes(18)),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(continued[0].0, Some(base + Duration::minutes(18)));
+        assert_eq!(continued.len(), 2);
+
+        let errors = search(LogSearch {
+            level: Some(LogLevel::Error),
+            limit: Some(3),
+            ..Default::default()
+        })
+        .await;
+        assert_eq!(requests(errors), vec!["15", "17", "19"]);
+
+        assert!(search(LogSearch {
+            since: Some(base + Duration::hours(1)),
+            ..Default::default()
+        })
+        .await
+        .is_empty());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_rotate_while_process_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("writer-out.log");
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .unwrap();
+
+        // The writer keeps its file descriptor across the rotation
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "echo before; sleep 0.5; echo after"])
+            .stdout(file)
+            .spawn()
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        rotate(&path, &LogRotationConfig::default().max_size(1))
+            .await
+            .unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "after\n");
+        assert_eq!(
+            tail_lines(&path, 10).await.unwrap(),
+            vec!["before", "after"]
+        );
+    }
+}
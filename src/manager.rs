@@ -67,20 +67,66 @@
 //! # }
 //! ```
 
-use crate::config::{PortConfig, ProcessConfig};
+use crate::actor::ProcessHandle;
+use crate::config::{ExecMode, PortConfig, ProcessConfig};
+use crate::crashes::{CrashRecord, CrashStore};
 use crate::error::{Error, Result};
+use crate::events::{Event, EventFilter, EventJournal, EventKind};
+use crate::health::{
+    HealthCheckConfig, HealthEvent, HealthMonitor, HealthState, HealthStatus, UnhealthyAction,
+};
+use crate::logs::{LogEntry, LogFiles, LogSearch, LogSubscription};
 use crate::monitoring::Monitor;
-use crate::process::{Process, ProcessId, ProcessStatus};
+use crate::process::{
+    ExitEvent, Process, ProcessExit, ProcessId, ProcessState, ProcessStatus, StopReport,
+};
+use crate::restart::{RestartDecision, RestartReason, RestartRecord};
+use crate::signals::ProcessSignal;
+use crate::watcher::{ProcessWatcher, WatchEvent};
 use comfy_table::{presets::UTF8_FULL, Attribute, Cell, Color, ContentArrangement, Table};
+use futures_util::future::join_all;
+use serde::Serialize;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tokio::fs;
-use tokio::sync::RwLock;
+use tokio::sync::{mpsc, RwLock};
 use tokio::time::interval;
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
+/// Runtime state of a process saved next to its configuration
+#[derive(Debug, Clone, Serialize)]
+struct ProcessMetadata {
+    /// Name of the process, which names the metadata file
+    #[serde(skip)]
+    name: String,
+    id: ProcessId,
+    assigned_port: Option<u16>,
+    instance: Option<u32>,
+    stored_pid: Option<u32>,
+    stored_pid_start_time: Option<u64>,
+    last_exit_code: Option<i32>,
+    last_exit_signal: Option<ProcessSignal>,
+    restart_history: Vec<RestartRecord>,
+}
+
+impl ProcessMetadata {
+    fn of(process: &Process) -> Self {
+        Self {
+            name: process.config.name.clone(),
+            id: process.id,
+            assigned_port: process.assigned_port,
+            instance: process.instance,
+            stored_pid: process.stored_pid,
+            stored_pid_start_time: process.stored_pid_start_time,
+            last_exit_code: process.last_exit.and_then(|exit| exit.code),
+            last_exit_signal: process.last_exit.and_then(|exit| exit.signal),
+            restart_history: process.restart_history.iter().cloned().collect(),
+        }
+    }
+}
+
 /// Main process manager for orchestrating process lifecycle and resources.
 ///
 /// The `ProcessManager` is the central component that handles all process operations
@@ -98,9 +144,9 @@ use uuid::Uuid;
 ///
 /// ## Thread Safety
 ///
-/// All operations are thread-safe using `RwLock` for concurrent access.
-/// Multiple threads can safely read process information while write operations
-/// are properly synchronized.
+/// Each process is owned by its own actor task (see [`crate::actor`]), and all
+/// operations take `&self`. Operations on one process are queued for its actor, so a
+/// slow stop or restart never blocks status queries or operations on other processes.
 ///
 /// # Examples
 ///
@@ -121,8 +167,8 @@ use uuid::Uuid;
 /// # }
 /// ```
 pub struct ProcessManager {
-    /// Map of process ID to process
-    processes: RwLock<HashMap<ProcessId, Process>>,
+    /// Actor handle of each process by process ID
+    processes: RwLock<HashMap<ProcessId, ProcessHandle>>,
     /// Map of process name to process ID for quick lookup
     name_to_id: RwLock<HashMap<String, ProcessId>>,
     /// System monitor for collecting metrics
@@ -131,13 +177,41 @@ pub struct ProcessManager {
     config_dir: PathBuf,
     /// Set of allocated ports
     allocated_ports: RwLock<HashSet<u16>>,
+    /// Active file watchers for processes with `watch` enabled
+    watchers: RwLock<HashMap<ProcessId, ProcessWatcher>>,
+    /// Sender handed to file watchers for debounced change events
+    watch_tx: mpsc::UnboundedSender<WatchEvent>,
+    /// Receiving end of the watch event channel, until taken by the host
+    watch_rx: std::sync::Mutex<Option<mpsc::UnboundedReceiver<WatchEvent>>>,
+    /// Active health monitors for running processes with health checks enabled
+    health_monitors: RwLock<HashMap<ProcessId, HealthMonitor>>,
+    /// Sender handed to health monitors for check results
+    health_tx: mpsc::UnboundedSender<HealthEvent>,
+    /// Receiving end of the health event channel, until taken by the host
+    health_rx: std::sync::Mutex<Option<mpsc::UnboundedReceiver<HealthEvent>>>,
+    /// Sender handed to processes so that their children report exits
+    exit_tx: mpsc::UnboundedSender<ExitEvent>,
+    /// Receiving end of the exit event channel, until taken by the host
+    exit_rx: std::sync::Mutex<Option<mpsc::UnboundedReceiver<ExitEvent>>>,
+    /// Last rotation time of each log file with rotation configured
+    log_rotations: RwLock<HashMap<PathBuf, chrono::DateTime<chrono::Utc>>>,
+    /// Journal of process lifecycle events
+    events: EventJournal,
+    /// Crash records of processes that exited abnormally
+    crashes: CrashStore,
 }
 
 impl ProcessManager {
     /// Create a new process manager
     pub async fn new() -> Result<Self> {
-        let config_dir = Self::get_config_dir()?;
+        Self::with_config_dir(Self::get_config_dir()?).await
+    }
 
+    /// Create a new process manager rooted at an explicit configuration directory
+    ///
+    /// This bypasses the `PMDAEMON_HOME`/home directory lookup used by [`new`](Self::new),
+    /// which is useful for embedding and for tests that need an isolated state directory.
+    pub async fn with_config_dir(config_dir: PathBuf) -> Result<Self> {
         // Ensure config directory exists
         if !config_dir.exists() {
             fs::create_dir_all(&config_dir)
@@ -145,12 +219,29 @@ impl ProcessManager {
                 .map_err(|e| Error::config(format!("Failed to create config directory: {}", e)))?;
         }
 
-        let mut manager = Self {
+        let (watch_tx, watch_rx) = mpsc::unbounded_channel();
+        let (health_tx, health_rx) = mpsc::unbounded_channel();
+        let (exit_tx, exit_rx) = mpsc::unbounded_channel();
+
+        let events = EventJournal::new(config_dir.join(crate::EVENTS_FILE));
+        let crashes = CrashStore::new(config_dir.join(crate::CRASHES_DIR));
+        let manager = Self {
             processes: RwLock::new(HashMap::new()),
             name_to_id: RwLock::new(HashMap::new()),
             monitor: RwLock::new(Monitor::new()),
             config_dir,
             allocated_ports: RwLock::new(HashSet::new()),
+            watchers: RwLock::new(HashMap::new()),
+            watch_tx,
+            watch_rx: std::sync::Mutex::new(Some(watch_rx)),
+            health_monitors: RwLock::new(HashMap::new()),
+            health_tx,
+            health_rx: std::sync::Mutex::new(Some(health_rx)),
+            exit_tx,
+            exit_rx: std::sync::Mutex::new(Some(exit_rx)),
+            log_rotations: RwLock::new(HashMap::new()),
+            events,
+            crashes,
         };
 
         // Load existing processes from configuration
@@ -180,6 +271,11 @@ impl ProcessManager {
         Ok(Self::get_config_dir()?.join("api-key"))
     }
 
+    /// Get the daemon control socket path
+    pub fn get_socket_path() -> Result<PathBuf> {
+        Ok(Self::get_config_dir()?.join(crate::DAEMON_SOCKET))
+    }
+
     /// Get the PID directory path
     fn get_pid_dir(&self) -> PathBuf {
         self.config_dir.join(crate::PID_DIR)
@@ -190,7 +286,7 @@ impl ProcessManager {
         self.config_dir.join(crate::LOG_DIR)
     }
 
-    /// Get log file paths for a process
+    /// Get the default log file paths for a process
     fn get_log_paths(&self, process_name: &str) -> (PathBuf, PathBuf, PathBuf) {
         let logs_dir = self.get_logs_dir();
         let out_file = logs_dir.join(format!("{}-out.log", process_name));
@@ -199,50 +295,88 @@ impl ProcessManager {
         (out_file, err_file, combined_file)
     }
 
-    /// Ensure logs directory exists
-    async fn ensure_logs_dir(&self) -> Result<()> {
-        let logs_dir = self.get_logs_dir();
-        if !logs_dir.exists() {
-            fs::create_dir_all(&logs_dir)
-                .await
-                .map_err(|e| Error::config(format!("Failed to create logs directory: {}", e)))?;
+    /// Get the stdout, stderr and combined log paths of a process, honoring its
+    /// `out_file`, `error_file` and `log_file` settings
+    fn log_paths(&self, config: &ProcessConfig) -> (PathBuf, PathBuf, PathBuf) {
+        let (out_file, err_file, combined_file) = self.get_log_paths(&config.name);
+        (
+            config
+                .resolve_path(config.out_file.as_ref())
+                .unwrap_or(out_file),
+            config
+                .resolve_path(config.error_file.as_ref())
+                .unwrap_or(err_file),
+            config
+                .resolve_path(config.log_file.as_ref())
+                .unwrap_or(combined_file),
+        )
+    }
+
+    /// Get the log files a process writes to when started
+    ///
+    /// Output is pumped through a [`LogShim`](crate::logs::LogShim), which writes the
+    /// combined log next to the stdout and stderr logs.
+    fn log_files(&self, config: &ProcessConfig) -> LogFiles {
+        let (out, err, combined) = self.log_paths(config);
+        LogFiles {
+            out,
+            err,
+            combined: Some(combined),
+        }
+    }
+
+    /// Get the PID file path of a process, honoring its `pid_file` setting
+    fn pid_path(&self, config: &ProcessConfig) -> PathBuf {
+        config
+            .resolve_path(config.pid_file.as_ref())
+            .unwrap_or_else(|| self.get_pid_dir().join(format!("{}.pid", config.name)))
+    }
+
+    /// Ensure the directories holding a process' log files exist
+    async fn ensure_log_dirs(&self, config: &ProcessConfig) -> Result<()> {
+        let (out_log, err_log, combined_log) = self.log_paths(config);
+        for log_file in [out_log, err_log, combined_log] {
+            if let Some(dir) = log_file.parent().filter(|dir| !dir.exists()) {
+                fs::create_dir_all(dir).await.map_err(|e| {
+                    Error::config(format!("Failed to create log directory {:?}: {}", dir, e))
+                })?;
+            }
         }
         Ok(())
     }
 
     /// Save PID file for a process
-    async fn save_pid_file(&self, process_name: &str, pid: u32) -> Result<()> {
-        let pid_dir = self.get_pid_dir();
-        if !pid_dir.exists() {
-            fs::create_dir_all(&pid_dir)
+    async fn save_pid_file(&self, config: &ProcessConfig, pid: u32) -> Result<()> {
+        let pid_file = self.pid_path(config);
+        if let Some(pid_dir) = pid_file.parent().filter(|dir| !dir.exists()) {
+            fs::create_dir_all(pid_dir)
                 .await
                 .map_err(|e| Error::config(format!("Failed to create PID directory: {}", e)))?;
         }
 
-        let pid_file = pid_dir.join(format!("{}.pid", process_name));
         fs::write(&pid_file, pid.to_string())
             .await
             .map_err(|e| Error::config(format!("Failed to write PID file: {}", e)))?;
 
-        debug!("Saved PID file for process {}: {}", process_name, pid);
+        debug!("Saved PID file for process {}: {}", config.name, pid);
         Ok(())
     }
 
     /// Remove PID file for a process
-    async fn remove_pid_file(&self, process_name: &str) -> Result<()> {
-        let pid_file = self.get_pid_dir().join(format!("{}.pid", process_name));
+    async fn remove_pid_file(&self, config: &ProcessConfig) -> Result<()> {
+        let pid_file = self.pid_path(config);
         if pid_file.exists() {
             fs::remove_file(&pid_file)
                 .await
                 .map_err(|e| Error::config(format!("Failed to remove PID file: {}", e)))?;
-            debug!("Removed PID file for process: {}", process_name);
+            debug!("Removed PID file for process: {}", config.name);
         }
         Ok(())
     }
 
     /// Read PID from PID file
-    async fn read_pid_file(&self, process_name: &str) -> Result<Option<u32>> {
-        let pid_file = self.get_pid_dir().join(format!("{}.pid", process_name));
+    async fn read_pid_file(&self, config: &ProcessConfig) -> Result<Option<u32>> {
+        let pid_file = self.pid_path(config);
         if !pid_file.exists() {
             return Ok(None);
         }
@@ -260,13 +394,32 @@ impl ProcessManager {
     }
 
     /// Start a new process (or multiple instances for clustering)
-    pub async fn start(&mut self, config: ProcessConfig) -> Result<ProcessId> {
+    pub async fn start(&self, config: ProcessConfig) -> Result<ProcessId> {
+        let wait_ready = config.wait_ready;
+        let process_ids = self.launch(config).await?;
+
+        // Hold `start` until every instance passes its health check
+        if wait_ready {
+            for process_id in &process_ids {
+                self.wait_ready(*process_id).await?;
+            }
+        }
+
+        Ok(process_ids[0])
+    }
+
+    /// Spawn a process (or every instance of a cluster) without waiting for readiness.
+    ///
+    /// Returns the IDs of all started instances. Processes configured with
+    /// `wait_ready` stay in the `starting` state until [`wait_ready`](Self::wait_ready)
+    /// completes for them; [`start`](Self::start) does both steps.
+    pub async fn launch(&self, config: ProcessConfig) -> Result<Vec<ProcessId>> {
         // Validate configuration
         config.validate()?;
 
         if config.instances == 1 {
             // Single instance
-            self.start_single_instance(config).await
+            Ok(vec![self.start_single_instance(config).await?])
         } else {
             // Multiple instances (clustering)
             self.start_cluster(config).await
@@ -274,17 +427,61 @@ impl ProcessManager {
     }
 
     /// Start a single process instance
-    async fn start_single_instance(&mut self, config: ProcessConfig) -> Result<ProcessId> {
-        // Check if process with same name already exists
-        let name_map = self.name_to_id.read().await;
-        if name_map.contains_key(&config.name) {
-            return Err(Error::process_already_exists(&config.name));
-        }
-        drop(name_map);
-
-        // Create new process
+    async fn start_single_instance(&self, config: ProcessConfig) -> Result<ProcessId> {
         let mut process = Process::new(config.clone());
+        process.notify_exits(self.exit_tx.clone());
         let process_id = process.id;
+        let wait_ready = config.ready_check().is_some();
+
+        // Reserve the name first, so that concurrent starts cannot both take it
+        {
+            let mut name_map = self.name_to_id.write().await;
+            if name_map.contains_key(&config.name) {
+                return Err(Error::process_already_exists(&config.name));
+            }
+            name_map.insert(config.name.clone(), process_id);
+        }
+
+        if let Err(e) = self.spawn_process(&mut process).await {
+            self.name_to_id.write().await.remove(&config.name);
+            return Err(e);
+        }
+
+        // Hand the process to its actor
+        self.processes
+            .write()
+            .await
+            .insert(process_id, ProcessHandle::spawn(process));
+
+        if let Err(e) = self.start_watching(process_id).await {
+            warn!("Failed to watch files for process {}: {}", process_id, e);
+        }
+        // Processes waiting for readiness get their monitor once they are online
+        if !wait_ready {
+            self.start_health_check(process_id).await;
+        }
+
+        Ok(process_id)
+    }
+
+    /// Allocate the port of a new process, start it and save its state to disk
+    async fn spawn_process(&self, process: &mut Process) -> Result<()> {
+        // Fail early on watch paths that cannot be watched
+        if process.config.watch {
+            for root in process.config.watch_roots() {
+                if !root.exists() {
+                    return Err(Error::config(format!(
+                        "Watch path does not exist: {:?}",
+                        root
+                    )));
+                }
+            }
+        }
+
+        if let Some(kind) = self.config_event(&process.config).await {
+            self.record_event(process.id, &process.config.name, kind)
+                .await;
+        }
 
         // Allocate port if specified
         if let Some(port_config) = &process.config.port {
@@ -292,6 +489,14 @@ impl ProcessManager {
                 .allocate_port(port_config, &process.config.name)
                 .await?;
             process.assigned_port = Some(assigned_port);
+            self.record_event(
+                process.id,
+                &process.config.name,
+                EventKind::PortAllocated {
+                    port: assigned_port,
+                },
+            )
+            .await;
 
             // Add PORT environment variable
             process
@@ -300,42 +505,33 @@ impl ProcessManager {
                 .insert("PORT".to_string(), assigned_port.to_string());
         }
 
-        // Ensure logs directory exists
-        self.ensure_logs_dir().await?;
-
-        // Get log file paths
-        let (out_log, err_log, _combined_log) = self.get_log_paths(&process.config.name);
+        // Ensure log directories exist
+        self.ensure_log_dirs(&process.config).await?;
 
         // Start the process with log redirection
         process
-            .start_with_logs(Some(out_log), Some(err_log))
+            .start_with_log_files(&self.log_files(&process.config))
             .await?;
 
         // Save PID file if process started successfully
         if let Some(pid) = process.pid() {
-            self.save_pid_file(&process.config.name, pid).await?;
+            self.record_event(process.id, &process.config.name, EventKind::Started { pid })
+                .await;
+            self.save_pid_file(&process.config, pid).await?;
             // Also store the PID in the process for later retrieval
             process.set_stored_pid(Some(pid));
         }
 
         // Save configuration to disk
-        self.save_process_config(&process).await?;
+        self.save_process_config(&process.config).await?;
 
         // Save runtime metadata (assigned port, etc.)
-        self.save_process_metadata(&process).await?;
-
-        // Store process
-        let mut processes = self.processes.write().await;
-        let mut name_map = self.name_to_id.write().await;
-
-        processes.insert(process_id, process);
-        name_map.insert(config.name, process_id);
-
-        Ok(process_id)
+        self.save_process_metadata(&ProcessMetadata::of(process))
+            .await
     }
 
     /// Start multiple process instances (clustering)
-    async fn start_cluster(&mut self, config: ProcessConfig) -> Result<ProcessId> {
+    async fn start_cluster(&self, config: ProcessConfig) -> Result<Vec<ProcessId>> {
         // Check if any instance with the base name already exists
         let name_map = self.name_to_id.read().await;
         for i in 0..config.instances {
@@ -346,7 +542,6 @@ impl ProcessManager {
         }
         drop(name_map);
 
-        let mut first_process_id = None;
         let mut started_instances = Vec::new();
 
         // Start each instance
@@ -355,6 +550,20 @@ impl ProcessManager {
             let mut instance_config = config.clone();
             instance_config.name = instance_name.clone();
             instance_config.instances = 1; // Each instance is a single process
+            instance_config.exec_mode = ExecMode::Cluster; // Reloads roll through the instances
+
+            // Configured log and PID files get an instance suffix, like the default ones
+            for path in [
+                &mut instance_config.out_file,
+                &mut instance_config.error_file,
+                &mut instance_config.log_file,
+                &mut instance_config.pid_file,
+            ]
+            .into_iter()
+            .flatten()
+            {
+                *path = crate::config::instance_path(path, i);
+            }
 
             // Add instance-specific environment variable
             instance_config
@@ -396,9 +605,18 @@ impl ProcessManager {
 
             match self.start_single_instance(instance_config).await {
                 Ok(process_id) => {
-                    if first_process_id.is_none() {
-                        first_process_id = Some(process_id);
+                    if let Ok(handle) = self.handle(process_id).await {
+                        let metadata = handle
+                            .with(move |process| {
+                                process.set_instance(Some(i));
+                                ProcessMetadata::of(process)
+                            })
+                            .await?;
+                        if let Err(e) = self.save_process_metadata(&metadata).await {
+                            warn!("Failed to save metadata for {}: {}", instance_name, e);
+                        }
                     }
+
                     started_instances.push((i, process_id));
                     info!("Started cluster instance {}: {}", i, instance_name);
                 }
@@ -421,147 +639,666 @@ impl ProcessManager {
             "Started cluster '{}' with {} instances",
             config.name, config.instances
         );
-        Ok(first_process_id.unwrap()) // We know this is Some because instances > 1
+        Ok(started_instances
+            .into_iter()
+            .map(|(_, process_id)| process_id)
+            .collect())
     }
 
     /// Stop a process by ProcessId
-    async fn stop_by_id(&mut self, process_id: ProcessId) -> Result<()> {
-        let mut processes = self.processes.write().await;
-        if let Some(process) = processes.get_mut(&process_id) {
-            let process_name = process.config.name.clone();
-            process.stop().await?;
+    async fn stop_by_id(&self, process_id: ProcessId) -> Result<StopReport> {
+        self.stop_watching(process_id).await;
+        self.stop_health_check(process_id).await;
 
-            // Remove PID file
-            drop(processes); // Release lock before async operation
-            self.remove_pid_file(&process_name).await?;
+        let Ok(handle) = self.handle(process_id).await else {
+            return Ok(StopReport::default());
+        };
+        let (config, report, metadata) = handle
+            .call(|process| {
+                Box::pin(async move {
+                    process.restart_state.reset();
+                    let report = process.stop().await;
+                    (process.config.clone(), report, ProcessMetadata::of(process))
+                })
+            })
+            .await?;
+        let report = report?;
+        if let Err(e) = self.save_process_metadata(&metadata).await {
+            warn!("Failed to update metadata after stop: {}", e);
         }
-        Ok(())
+        self.record_event(
+            process_id,
+            &config.name,
+            EventKind::Stopped {
+                forced: report.forced,
+            },
+        )
+        .await;
+
+        // Remove PID file
+        self.remove_pid_file(&config).await?;
+        Ok(report)
     }
 
     /// Stop a process
-    pub async fn stop(&mut self, identifier: &str) -> Result<()> {
+    ///
+    /// The returned report lists processes it left behind that had to be killed.
+    pub async fn stop(&self, identifier: &str) -> Result<StopReport> {
         let process_id = self.resolve_identifier(identifier).await?;
-
-        let mut processes = self.processes.write().await;
-        if let Some(process) = processes.get_mut(&process_id) {
-            let process_name = process.config.name.clone();
-            process.stop().await?;
-
-            // Remove PID file
-            drop(processes); // Release lock before async operation
-            self.remove_pid_file(&process_name).await?;
-        }
-
-        Ok(())
+        self.stop_by_id(process_id).await
     }
 
     /// Restart a process
-    pub async fn restart(&mut self, identifier: &str) -> Result<()> {
+    pub async fn restart(&self, identifier: &str) -> Result<()> {
         self.restart_with_port(identifier, None).await
     }
 
     /// Restart a process with optional port override
     pub async fn restart_with_port(
-        &mut self,
+        &self,
         identifier: &str,
         port_override: Option<PortConfig>,
     ) -> Result<()> {
         let process_id = self.resolve_identifier(identifier).await?;
+        self.restart_by_id(process_id, port_override, RestartReason::Manual)
+            .await
+    }
 
-        let mut processes = self.processes.write().await;
-        if let Some(process) = processes.get_mut(&process_id) {
-            // Handle port deallocation and reallocation if there's an override
-            if let Some(new_port_config) = port_override {
-                // Deallocate current port if any
-                if let Some(current_port_config) = &process.config.port {
-                    self.deallocate_ports(current_port_config, process.assigned_port)
-                        .await;
-                }
+    /// Restart a process by ProcessId, journaling `reason` as the cause
+    async fn restart_by_id(
+        &self,
+        process_id: ProcessId,
+        port_override: Option<PortConfig>,
+        reason: RestartReason,
+    ) -> Result<()> {
+        let handle = self.handle(process_id).await?;
+        let config = handle.config();
+
+        // Handle port deallocation and reallocation if there's an override
+        let mut new_port = None;
+        if let Some(new_port_config) = port_override {
+            // Deallocate current port if any
+            self.release_ports(process_id, &config, handle.status().assigned_port)
+                .await;
+
+            // Allocate new port
+            let assigned_port = self.allocate_port(&new_port_config, &config.name).await?;
+            info!(
+                "Restarting {} with new port: {}",
+                config.name, assigned_port
+            );
+            self.record_event(
+                process_id,
+                &config.name,
+                EventKind::PortAllocated {
+                    port: assigned_port,
+                },
+            )
+            .await;
+            new_port = Some(assigned_port);
+        }
 
-                // Allocate new port
-                let assigned_port = self
-                    .allocate_port(&new_port_config, &process.config.name)
-                    .await?;
-                process.assigned_port = Some(assigned_port);
+        let log_files = self.log_files(&config);
+        let (config, new_pid, metadata) = handle
+            .call(move |process| {
+                Box::pin(async move {
+                    if let Some(assigned_port) = new_port {
+                        process.assigned_port = Some(assigned_port);
+                        process
+                            .config
+                            .env
+                            .insert("PORT".to_string(), assigned_port.to_string());
+                    }
 
-                // Update environment variable
-                process
-                    .config
-                    .env
-                    .insert("PORT".to_string(), assigned_port.to_string());
+                    process.restart_state.reset();
+                    process.restart_with_log_files(&log_files).await?;
+                    process.record_restart(reason);
 
-                info!(
-                    "Restarting {} with new port: {}",
-                    process.config.name, assigned_port
-                );
-            }
+                    // Later invocations find the new process through the PID file and
+                    // metadata
+                    let new_pid = process.pid();
+                    if new_pid.is_some() {
+                        process.set_stored_pid(new_pid);
+                    }
+                    Ok::<_, Error>((
+                        process.config.clone(),
+                        new_pid,
+                        ProcessMetadata::of(process),
+                    ))
+                })
+            })
+            .await??;
+        self.record_event(
+            process_id,
+            &config.name,
+            EventKind::Restarted {
+                reason,
+                pid: new_pid,
+            },
+        )
+        .await;
+        if let Some(new_pid) = new_pid {
+            self.save_pid_file(&config, new_pid).await?;
+        }
+        self.save_process_metadata(&metadata).await?;
 
-            process.restart().await?;
+        // Resume watching if the process was stopped (or restored from disk) before
+        let watching = self.watchers.read().await.contains_key(&process_id);
+        if !watching {
+            if let Err(e) = self.start_watching(process_id).await {
+                warn!("Failed to watch files for process {}: {}", process_id, e);
+            }
         }
 
+        // Health checks start over for the new process
+        self.start_health_check(process_id).await;
+
         Ok(())
     }
 
     /// Reload a process (graceful restart)
-    pub async fn reload(&mut self, identifier: &str) -> Result<()> {
+    pub async fn reload(&self, identifier: &str) -> Result<()> {
         self.reload_with_port(identifier, None).await
     }
 
-    /// Reload a process with optional port override
+    /// Reload a process with optional port override.
+    ///
+    /// Cluster instances are replaced one at a time: each new instance must pass its
+    /// health check (or stay up for `min_uptime` when it has none) before the old one
+    /// is stopped gracefully. The identifier may name a single instance or the
+    /// cluster's base name. If a new instance fails, the reload stops there and the
+    /// remaining old instances keep running. Other processes are simply restarted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a replacement instance fails to become ready, or
+    /// [`Error::InvalidArgument`] when a port override is given for a cluster.
     pub async fn reload_with_port(
-        &mut self,
+        &self,
         identifier: &str,
         port_override: Option<PortConfig>,
     ) -> Result<()> {
-        // For now, reload is the same as restart with port override
-        self.restart_with_port(identifier, port_override).await
+        let instances = self.cluster_instances(identifier).await?;
+        if instances.is_empty() {
+            return self.restart_with_port(identifier, port_override).await;
+        }
+        if port_override.is_some() {
+            return Err(Error::invalid_argument(
+                "Cannot change the port of cluster instances on reload, use restart instead",
+            ));
+        }
+
+        for (replaced, process_id) in instances.iter().enumerate() {
+            if let Err(e) = self.replace_instance(*process_id).await {
+                error!(
+                    "Reload of {} aborted after replacing {} of {} instances: {}",
+                    identifier,
+                    replaced,
+                    instances.len(),
+                    e
+                );
+                return Err(e);
+            }
+        }
+
+        info!(
+            "Reloaded {} ({} instances replaced)",
+            identifier,
+            instances.len()
+        );
+        Ok(())
     }
 
-    /// Delete a process
-    pub async fn delete(&mut self, identifier: &str) -> Result<()> {
-        let process_id = self.resolve_identifier(identifier).await?;
+    /// Resolve the cluster instances a reload of `identifier` rolls through, in
+    /// instance order. Returns an empty list when the identifier names a process that
+    /// is not part of a cluster.
+    async fn cluster_instances(&self, identifier: &str) -> Result<Vec<ProcessId>> {
+        if let Ok(process_id) = self.resolve_identifier(identifier).await {
+            return Ok(match self.handle(process_id).await {
+                Ok(handle) if handle.config().is_cluster_mode() => vec![process_id],
+                _ => Vec::new(),
+            });
+        }
+
+        // Not a process name, so try the cluster's base name ("{name}-{instance}")
+        let prefix = format!("{}-", identifier);
+        let mut instances: Vec<(u32, ProcessId)> = self
+            .handles()
+            .await
+            .iter()
+            .filter_map(|handle| {
+                let config = handle.config();
+                if !config.is_cluster_mode() {
+                    return None;
+                }
+                let instance = config.name.strip_prefix(&prefix)?.parse().ok()?;
+                Some((instance, handle.id()))
+            })
+            .collect();
+
+        if instances.is_empty() {
+            return Err(Error::process_not_found(identifier));
+        }
+        instances.sort_by_key(|(instance, _)| *instance);
+        Ok(instances
+            .into_iter()
+            .map(|(_, process_id)| process_id)
+            .collect())
+    }
+
+    /// Send a signal to a process, or to every instance of a cluster by its base name
+    ///
+    /// Returns the names of the processes that were signalled. When several processes
+    /// match, stopped ones are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProcessNotFound`] if nothing matches the identifier, and an
+    /// error if none of the matching processes could be signalled.
+    pub async fn signal(&self, identifier: &str, signal: ProcessSignal) -> Result<Vec<String>> {
+        let process_ids = match self.resolve_identifier(identifier).await {
+            Ok(process_id) => vec![process_id],
+            Err(_) => self.cluster_instances(identifier).await?,
+        };
+        self.signal_processes(identifier, &process_ids, signal)
+            .await
+    }
+
+    /// Send a signal to every running process in a namespace
+    ///
+    /// Returns the names of the processes that were signalled.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::ProcessNotFound`] if the namespace has no processes, and an
+    /// error if none of them could be signalled.
+    pub async fn signal_namespace(
+        &self,
+        namespace: &str,
+        signal: ProcessSignal,
+    ) -> Result<Vec<String>> {
+        let mut processes: Vec<(String, ProcessId)> = self
+            .handles()
+            .await
+            .iter()
+            .map(|handle| (handle.config(), handle.id()))
+            .filter(|(config, _)| config.namespace == namespace)
+            .map(|(config, process_id)| (config.name, process_id))
+            .collect();
+        if processes.is_empty() {
+            return Err(Error::process_not_found(format!("namespace {}", namespace)));
+        }
+        processes.sort();
+        let process_ids: Vec<ProcessId> = processes.into_iter().map(|(_, id)| id).collect();
+        self.signal_processes(&format!("namespace {}", namespace), &process_ids, signal)
+            .await
+    }
+
+    /// Send a signal to each of `process_ids`, skipping stopped ones when there are several
+    async fn signal_processes(
+        &self,
+        target: &str,
+        process_ids: &[ProcessId],
+        signal: ProcessSignal,
+    ) -> Result<Vec<String>> {
+        let mut targets = Vec::new();
+        for process_id in process_ids {
+            if let Ok(handle) = self.handle(*process_id).await {
+                targets.push(handle);
+            }
+        }
+        if let [handle] = &targets[..] {
+            handle
+                .with(move |process| process.send_signal(signal))
+                .await??;
+            return Ok(vec![handle.config().name]);
+        }
+
+        let mut signalled = Vec::new();
+        for handle in targets {
+            let name = handle.config().name;
+            let result = handle
+                .with(move |process| {
+                    if !process.is_running() {
+                        return Ok(false);
+                    }
+                    process.send_signal(signal).map(|()| true)
+                })
+                .await
+                .and_then(|result| result);
+            match result {
+                Ok(true) => signalled.push(name),
+                Ok(false) => {}
+                Err(e) => warn!("Failed to send {} to process {}: {}", signal, name, e),
+            }
+        }
+        if signalled.is_empty() {
+            return Err(Error::process(format!(
+                "No running process in {} could be sent {}",
+                target, signal
+            )));
+        }
+        Ok(signalled)
+    }
+
+    /// Replace a running cluster instance with a fresh one without dropping traffic.
+    ///
+    /// The new instance is started next to the old one and only swapped in once it is
+    /// ready; the old instance is then stopped gracefully. If the new instance fails,
+    /// it is stopped and the old instance is left untouched.
+    ///
+    /// An instance holding a fixed port (from `Single` or `Range`) is stopped before
+    /// its replacement starts instead, since both cannot bind the port; the port is
+    /// not served in between, and if the new instance fails the old instance is
+    /// started again from its previous configuration.
+    async fn replace_instance(&self, process_id: ProcessId) -> Result<()> {
+        let handle = self.handle(process_id).await?;
+        let exit_tx = self.exit_tx.clone();
+        let (mut replacement, old_port) = handle
+            .with(move |old| {
+                let mut replacement = Process::new(old.config.clone());
+                replacement.notify_exits(exit_tx);
+                replacement.set_id(process_id);
+                replacement.set_instance(old.instance);
+                replacement.set_assigned_port(old.assigned_port);
+                replacement.restarts = old.restarts + 1;
+                replacement.last_exit = old.last_exit;
+                replacement.restart_history = old.restart_history.clone();
+                replacement.record_restart(RestartReason::Reload);
+                (replacement, old.assigned_port)
+            })
+            .await?;
+        let process_name = replacement.config.name.clone();
+        info!("Reloading cluster instance {}", process_name);
+
+        // Auto-assigned ports can move, so the new instance gets a fresh one; fixed
+        // ports have to be freed by the old instance first
+        let fixed_port =
+            old_port.filter(|_| !matches!(replacement.config.port, Some(PortConfig::Auto(_, _))));
+        let mut fresh_port = None;
+        if let Some(port_config @ PortConfig::Auto(_, _)) = &replacement.config.port {
+            let port = self.allocate_port(port_config, &process_name).await?;
+            replacement.assigned_port = Some(port);
+            replacement
+                .config
+                .env
+                .insert("PORT".to_string(), port.to_string());
+            self.record_event(process_id, &process_name, EventKind::PortAllocated { port })
+                .await;
+            fresh_port = Some(port);
+        }
+
+        if let Some(port) = fixed_port {
+            info!(
+                "Stopping old instance of {} to free port {} for the new one",
+                process_name, port
+            );
+            self.stop_health_check(process_id).await;
+            handle
+                .call(|process| Box::pin(async move { process.stop().await }))
+                .await??;
+        }
+
+        self.ensure_log_dirs(&replacement.config).await?;
+        let log_files = self.log_files(&replacement.config);
+        let started = replacement.start_with_log_files(&log_files).await;
+        let candidate = tokio::sync::Mutex::new(replacement);
+        let ready = match started {
+            Ok(()) => Self::wait_replacement_ready(&candidate).await,
+            Err(e) => Err(e),
+        };
+        let mut replacement = candidate.into_inner();
 
-        // First, stop the process if it's running
-        let (process_name, port_config, assigned_port, was_running) = {
-            let mut processes = self.processes.write().await;
-            if let Some(mut process) = processes.remove(&process_id) {
-                let process_name = process.config.name.clone();
-                let port_config = process.config.port.clone();
-                let assigned_port = process.assigned_port;
-                let was_running = process.is_running();
-
-                // Stop the process if it's running
-                if was_running {
-                    info!("Stopping process '{}' before deletion", process_name);
-                    if let Err(e) = process.stop().await {
+        let health = match ready {
+            Ok(health) => health,
+            Err(e) => {
+                if replacement.is_running() {
+                    if let Err(stop_err) = replacement.stop().await {
                         warn!(
-                            "Failed to stop process '{}' during deletion: {}",
-                            process_name, e
+                            "Failed to stop replacement for {}: {}",
+                            process_name, stop_err
                         );
-                        // Continue with deletion even if stop fails
                     }
                 }
+                if fresh_port.is_some() {
+                    self.release_ports(process_id, &replacement.config, fresh_port)
+                        .await;
+                }
+                let message = match e {
+                    Error::HealthCheck { message } | Error::Process { message } => message,
+                    other => other.to_string(),
+                };
+                if fixed_port.is_some() {
+                    self.restore_instance(process_id, &handle, &process_name, &message)
+                        .await?;
+                }
+                return Err(Error::process(format!(
+                    "New instance of {} failed to become ready: {}",
+                    process_name, message
+                )));
+            }
+        };
+
+        replacement.set_state(ProcessState::Online);
+        replacement.set_stored_pid(replacement.pid());
+        let watching = self.watchers.read().await.contains_key(&process_id);
+        replacement.watching = watching;
+
+        // Swap the new instance in; the old one keeps serving until this point
+        if !self.processes.read().await.contains_key(&process_id) {
+            let _ = replacement.stop().await;
+            return Err(Error::process_not_found(process_id.to_string()));
+        }
+        let mut old = handle
+            .with(move |slot| std::mem::replace(slot, replacement))
+            .await?;
+
+        self.start_health_check(process_id).await;
+        let (config, pid, metadata) = handle
+            .with(move |process| {
+                if health.is_some() {
+                    process.health = health;
+                }
+                (
+                    process.config.clone(),
+                    process.pid(),
+                    ProcessMetadata::of(process),
+                )
+            })
+            .await?;
+        self.record_event(
+            process_id,
+            &process_name,
+            EventKind::Restarted {
+                reason: RestartReason::Reload,
+                pid,
+            },
+        )
+        .await;
+        if let Some(pid) = pid {
+            self.save_pid_file(&config, pid).await?;
+        }
+        self.save_process_metadata(&metadata).await?;
 
-                // Remove from name map
-                let mut name_map = self.name_to_id.write().await;
-                name_map.remove(&process_name);
-                drop(name_map);
+        if fixed_port.is_none() {
+            info!(
+                "New instance of {} is ready, stopping the old one",
+                process_name
+            );
+            if let Err(e) = old.stop().await {
+                warn!("Failed to stop old instance of {}: {}", process_name, e);
+            }
+        }
+        if fresh_port.is_some() && old_port != fresh_port {
+            self.release_ports(process_id, &old.config, old_port).await;
+        }
 
-                (process_name, port_config, assigned_port, was_running)
-            } else {
-                return Ok(()); // Process not found, nothing to delete
+        Ok(())
+    }
+
+    /// Start the old instance of a fixed-port cluster instance again, from its
+    /// previous configuration, after its replacement failed with `message`.
+    ///
+    /// If the old instance cannot be started either, it is left errored.
+    async fn restore_instance(
+        &self,
+        process_id: ProcessId,
+        handle: &ProcessHandle,
+        process_name: &str,
+        message: &str,
+    ) -> Result<()> {
+        warn!(
+            "New instance of {} failed, starting the old one again",
+            process_name
+        );
+        let log_files = self.log_files(&handle.config());
+        let restarted = handle
+            .call(move |process| {
+                Box::pin(async move {
+                    process.start_with_log_files(&log_files).await?;
+                    let pid = process.pid();
+                    process.set_stored_pid(pid);
+                    Ok::<_, Error>((process.config.clone(), pid, ProcessMetadata::of(process)))
+                })
+            })
+            .await
+            .and_then(|result| result);
+
+        match restarted {
+            Ok((config, pid, metadata)) => {
+                if let Some(pid) = pid {
+                    self.record_event(process_id, process_name, EventKind::Started { pid })
+                        .await;
+                    self.save_pid_file(&config, pid).await?;
+                }
+                self.save_process_metadata(&metadata).await?;
+                self.start_health_check(process_id).await;
+            }
+            Err(e) => {
+                let error = format!(
+                    "Reload failed: {}; restarting the old instance failed: {}",
+                    message, e
+                );
+                self.record_event(
+                    process_id,
+                    process_name,
+                    EventKind::Errored {
+                        message: error.clone(),
+                    },
+                )
+                .await;
+                let config = handle
+                    .with(move |process| {
+                        process.set_state(ProcessState::Errored);
+                        process.set_stored_pid(None);
+                        process.error = Some(error);
+                        process.config.clone()
+                    })
+                    .await?;
+                if let Err(e) = self.remove_pid_file(&config).await {
+                    warn!("Failed to remove PID file for {}: {}", process_name, e);
+                }
             }
+        }
+        Ok(())
+    }
+
+    /// Wait until a replacement instance is ready to take over: it must pass its
+    /// health check within `wait_timeout`, or stay up for `min_uptime` if it has none
+    async fn wait_replacement_ready(
+        candidate: &tokio::sync::Mutex<Process>,
+    ) -> Result<Option<HealthStatus>> {
+        let config = candidate.lock().await.config.clone();
+        let still_running = || async {
+            let mut process = candidate.lock().await;
+            process.check_status().await.unwrap_or(false)
         };
 
-        // Deallocate ports
-        if let Some(port_config) = port_config {
-            self.deallocate_ports(&port_config, assigned_port).await;
+        match config.health_check {
+            Some(health_check) if health_check.enabled => {
+                candidate.lock().await.set_state(ProcessState::Starting);
+                let timeout = Duration::from_millis(config.wait_timeout);
+                crate::health::wait_until_healthy(health_check, timeout, still_running)
+                    .await
+                    .map(Some)
+            }
+            _ => {
+                tokio::time::sleep(Duration::from_millis(config.min_uptime)).await;
+                if still_running().await {
+                    Ok(None)
+                } else {
+                    Err(Error::process(format!(
+                        "process exited within min_uptime ({}ms)",
+                        config.min_uptime
+                    )))
+                }
+            }
+        }
+    }
+
+    /// Remove a process from the manager, stopping it first if it is running, and
+    /// release its ports.
+    ///
+    /// Returns its configuration, whether it was running and the outcome of stopping
+    /// it, or `None` if it was already removed.
+    async fn remove_process(
+        &self,
+        process_id: ProcessId,
+    ) -> Option<(ProcessConfig, bool, Result<StopReport>)> {
+        self.stop_watching(process_id).await;
+        self.stop_health_check(process_id).await;
+
+        let handle = self.processes.write().await.remove(&process_id)?;
+        let (config, assigned_port, was_running, stopped) = handle
+            .call(|process| {
+                Box::pin(async move {
+                    let was_running = process.is_running();
+                    let stopped = if was_running {
+                        process.stop().await
+                    } else {
+                        Ok(StopReport::default())
+                    };
+                    (
+                        process.config.clone(),
+                        process.assigned_port,
+                        was_running,
+                        stopped,
+                    )
+                })
+            })
+            .await
+            .ok()?;
+
+        // Remove from name map
+        self.name_to_id.write().await.remove(&config.name);
+
+        self.release_ports(process_id, &config, assigned_port).await;
+        self.record_event(process_id, &config.name, EventKind::Deleted)
+            .await;
+        Some((config, was_running, stopped))
+    }
+
+    /// Delete a process
+    pub async fn delete(&self, identifier: &str) -> Result<()> {
+        let process_id = self.resolve_identifier(identifier).await?;
+
+        // Stop the process if it's running
+        let Some((config, was_running, stopped)) = self.remove_process(process_id).await else {
+            return Ok(()); // Process not found, nothing to delete
+        };
+        let process_name = config.name.clone();
+        if let Err(e) = stopped {
+            warn!(
+                "Failed to stop process '{}' during deletion: {}",
+                process_name, e
+            );
+            // Continue with deletion even if stop fails
         }
 
         // Clean up files
         self.remove_process_config(&process_name).await?;
-        self.remove_pid_file(&process_name).await?;
-        self.remove_log_files(&process_name).await?;
+        self.remove_pid_file(&config).await?;
+        self.remove_log_files(&config).await?;
 
         if was_running {
             info!(
@@ -576,66 +1313,38 @@ impl ProcessManager {
     }
 
     /// Delete all processes
-    pub async fn delete_all(&mut self) -> Result<usize> {
-        let process_ids: Vec<ProcessId>;
-
+    pub async fn delete_all(&self) -> Result<usize> {
         // Get all process IDs
-        {
-            let processes = self.processes.read().await;
-            process_ids = processes.keys().cloned().collect();
-        }
+        let process_ids: Vec<ProcessId> = self.processes.read().await.keys().cloned().collect();
 
         let mut deleted_count = 0;
         let mut stopped_count = 0;
 
         for process_id in process_ids {
             // Stop and remove the process
-            let (process_name, port_config, assigned_port, _was_running) = {
-                let mut processes = self.processes.write().await;
-                if let Some(mut process) = processes.remove(&process_id) {
-                    let process_name = process.config.name.clone();
-                    let port_config = process.config.port.clone();
-                    let assigned_port = process.assigned_port;
-                    let was_running = process.is_running();
-
-                    // Stop the process if it's running
-                    if was_running {
-                        if let Err(e) = process.stop().await {
-                            warn!(
-                                "Failed to stop process '{}' during bulk deletion: {}",
-                                process_name, e
-                            );
-                            // Continue with deletion even if stop fails
-                        } else {
-                            stopped_count += 1;
-                        }
-                    }
-
-                    // Remove from name map
-                    let mut name_map = self.name_to_id.write().await;
-                    name_map.remove(&process_name);
-                    drop(name_map);
-
-                    deleted_count += 1;
-                    (process_name, port_config, assigned_port, was_running)
-                } else {
-                    continue; // Process already deleted
-                }
+            let Some((config, was_running, stopped)) = self.remove_process(process_id).await else {
+                continue; // Process already deleted
             };
-
-            // Deallocate ports
-            if let Some(port_config) = port_config {
-                self.deallocate_ports(&port_config, assigned_port).await;
+            let process_name = config.name.clone();
+            deleted_count += 1;
+            match stopped {
+                Ok(_) if was_running => stopped_count += 1,
+                Ok(_) => {}
+                // Continue with deletion even if stop fails
+                Err(e) => warn!(
+                    "Failed to stop process '{}' during bulk deletion: {}",
+                    process_name, e
+                ),
             }
 
             // Clean up files
             if let Err(e) = self.remove_process_config(&process_name).await {
                 warn!("Failed to remove config for {}: {}", process_name, e);
             }
-            if let Err(e) = self.remove_pid_file(&process_name).await {
+            if let Err(e) = self.remove_pid_file(&config).await {
                 warn!("Failed to remove PID file for {}: {}", process_name, e);
             }
-            if let Err(e) = self.remove_log_files(&process_name).await {
+            if let Err(e) = self.remove_log_files(&config).await {
                 warn!("Failed to remove log files for {}: {}", process_name, e);
             }
         }
@@ -653,7 +1362,7 @@ impl ProcessManager {
     }
 
     /// Delete processes by status
-    pub async fn delete_by_status(&mut self, status_str: &str) -> Result<usize> {
+    pub async fn delete_by_status(&self, status_str: &str) -> Result<usize> {
         use crate::process::ProcessState;
 
         // Parse the status string
@@ -670,65 +1379,38 @@ impl ProcessManager {
             ))),
         };
 
-        let process_ids_to_delete: Vec<ProcessId>;
-
         // Find processes with matching status
-        {
-            let processes = self.processes.read().await;
-            process_ids_to_delete = processes
-                .iter()
-                .filter(|(_, process)| process.state == target_state)
-                .map(|(id, _)| *id)
-                .collect();
-        }
+        let process_ids_to_delete: Vec<ProcessId> = self
+            .handles()
+            .await
+            .iter()
+            .filter(|handle| handle.status().state == target_state)
+            .map(|handle| handle.id())
+            .collect();
 
         let mut deleted_count = 0;
         let mut stopped_count = 0;
 
         for process_id in process_ids_to_delete {
             // Stop and remove the process
-            let (process_name, port_config, assigned_port, _was_running) = {
-                let mut processes = self.processes.write().await;
-                if let Some(mut process) = processes.remove(&process_id) {
-                    let process_name = process.config.name.clone();
-                    let port_config = process.config.port.clone();
-                    let assigned_port = process.assigned_port;
-                    let was_running = process.is_running();
-
-                    // Stop the process if it's running
-                    if was_running {
-                        if let Err(e) = process.stop().await {
-                            warn!(
-                                "Failed to stop process '{}' during status-based deletion: {}",
-                                process_name, e
-                            );
-                            // Continue with deletion even if stop fails
-                        } else {
-                            stopped_count += 1;
-                        }
-                    }
-
-                    // Remove from name map
-                    let mut name_map = self.name_to_id.write().await;
-                    name_map.remove(&process_name);
-                    drop(name_map);
-
-                    deleted_count += 1;
-                    (process_name, port_config, assigned_port, was_running)
-                } else {
-                    continue; // Process already deleted
-                }
+            let Some((config, was_running, stopped)) = self.remove_process(process_id).await else {
+                continue; // Process already deleted
             };
-
-            // Deallocate ports
-            if let Some(port_config) = port_config {
-                self.deallocate_ports(&port_config, assigned_port).await;
+            deleted_count += 1;
+            match stopped {
+                Ok(_) if was_running => stopped_count += 1,
+                Ok(_) => {}
+                // Continue with deletion even if stop fails
+                Err(e) => warn!(
+                    "Failed to stop process '{}' during status-based deletion: {}",
+                    config.name, e
+                ),
             }
 
             // Clean up files
-            let _ = self.remove_process_config(&process_name).await;
-            let _ = self.remove_pid_file(&process_name).await;
-            let _ = self.remove_log_files(&process_name).await;
+            let _ = self.remove_process_config(&config.name).await;
+            let _ = self.remove_pid_file(&config).await;
+            let _ = self.remove_log_files(&config).await;
         }
 
         if stopped_count > 0 {
@@ -748,8 +1430,12 @@ impl ProcessManager {
 
     /// List all processes
     pub async fn list(&self) -> Result<Vec<ProcessStatus>> {
-        let processes = self.processes.read().await;
-        Ok(processes.values().map(|p| p.status()).collect())
+        Ok(self
+            .handles()
+            .await
+            .iter()
+            .map(ProcessHandle::status)
+            .collect())
     }
 
     /// Monitor processes in real-time.
@@ -1001,11 +1687,9 @@ impl ProcessManager {
                             ]);
 
                         // Get monitoring data for all processes
-                        let processes_guard = self.processes.read().await;
                         let mut monitor = self.monitor.write().await;
 
-                        let pids: Vec<u32> =
-                            processes_guard.values().filter_map(|p| p.pid()).collect();
+                        let pids: Vec<u32> = processes.iter().filter_map(|p| p.pid).collect();
 
                         let monitoring_data = if !pids.is_empty() {
                             monitor.update_process_metrics(&pids).await
@@ -1014,7 +1698,6 @@ impl ProcessManager {
                         };
 
                         drop(monitor);
-                        drop(processes_guard);
 
                         // Add each process to the table
                         for process_status in processes {
@@ -1169,17 +1852,19 @@ impl ProcessManager {
     pub async fn get_logs(&self, identifier: &str, lines: usize) -> Result<String> {
         let process_id = self.resolve_identifier(identifier).await?;
 
-        // Get the process name for log file paths
-        let process_name = {
-            let processes = self.processes.read().await;
-            if let Some(process) = processes.get(&process_id) {
-                process.config.name.clone()
-            } else {
-                return Err(Error::process_not_found(identifier));
-            }
-        };
-
-        let (out_log, err_log, _combined_log) = self.get_log_paths(&process_name);
+        // Get the process name and log file paths
+        let (process_name, format, (out_log, err_log, _combined_log)) =
+            match self.handle(process_id).await {
+                Ok(handle) => {
+                    let config = handle.config();
+                    (
+                        config.name.clone(),
+                        config.log_format,
+                        self.log_paths(&config),
+                    )
+                }
+                Err(_) => return Err(Error::process_not_found(identifier)),
+            };
         let mut result = String::new();
 
         // Debug: Log the paths being used
@@ -1189,79 +1874,58 @@ impl ProcessManager {
         );
 
         // Read stdout log
+        result.push_str(&format!("==> {} stdout <==\n", process_name));
         if out_log.exists() {
-            result.push_str(&format!("==> {} stdout <==\n", process_name));
-            match fs::read_to_string(&out_log).await {
-                Ok(content) => {
-                    debug!("Read {} bytes from stdout log", content.len());
-                    if content.is_empty() {
-                        result.push_str("(stdout log file is empty)\n");
-                    } else {
-                        let log_lines: Vec<&str> = content.lines().collect();
-                        let start = if log_lines.len() > lines {
-                            log_lines.len() - lines
-                        } else {
-                            0
-                        };
-                        debug!(
-                            "Showing {} lines from stdout (total: {})",
-                            log_lines.len() - start,
-                            log_lines.len()
-                        );
-                        for line in &log_lines[start..] {
-                            result.push_str(line);
-                            result.push('\n');
-                        }
-                    }
-                }
-                Err(e) => {
-                    result.push_str(&format!("Error reading stdout log: {}\n", e));
-                }
-            }
+            Self::push_log_tail(&mut result, "stdout", &out_log, lines, format).await;
             result.push('\n');
         } else {
-            result.push_str(&format!("==> {} stdout <==\n", process_name));
             result.push_str(&format!("No stdout log file found at: {:?}\n\n", out_log));
         }
 
         // Read stderr log
+        result.push_str(&format!("==> {} stderr <==\n", process_name));
         if err_log.exists() {
-            result.push_str(&format!("==> {} stderr <==\n", process_name));
-            match fs::read_to_string(&err_log).await {
-                Ok(content) => {
-                    debug!("Read {} bytes from stderr log", content.len());
-                    if content.is_empty() {
-                        result.push_str("(stderr log file is empty)\n");
-                    } else {
-                        let log_lines: Vec<&str> = content.lines().collect();
-                        let start = if log_lines.len() > lines {
-                            log_lines.len() - lines
-                        } else {
-                            0
-                        };
-                        debug!(
-                            "Showing {} lines from stderr (total: {})",
-                            log_lines.len() - start,
-                            log_lines.len()
-                        );
-                        for line in &log_lines[start..] {
-                            result.push_str(line);
-                            result.push('\n');
-                        }
-                    }
-                }
-                Err(e) => {
-                    result.push_str(&format!("Error reading stderr log: {}\n", e));
-                }
-            }
+            Self::push_log_tail(&mut result, "stderr", &err_log, lines, format).await;
         } else {
-            result.push_str(&format!("==> {} stderr <==\n", process_name));
             result.push_str(&format!("No stderr log file found at: {:?}\n", err_log));
         }
 
         Ok(result)
     }
 
+    /// Append the last `lines` lines of a log, including rotated segments, to `result`
+    async fn push_log_tail(
+        result: &mut String,
+        stream: &str,
+        path: &Path,
+        lines: usize,
+        format: crate::logs::LogFormat,
+    ) {
+        match crate::logs::tail_lines(path, lines).await {
+            Ok(log_lines) if log_lines.is_empty() => {
+                result.push_str(&format!("({} log file is empty)\n", stream));
+            }
+            Ok(log_lines) => {
+                debug!("Showing {} lines from {}", log_lines.len(), stream);
+                for line in log_lines {
+                    result.push_str(&Self::humanize_log_line(&line, format));
+                    result.push('\n');
+                }
+            }
+            Err(e) => {
+                result.push_str(&format!("Error reading {} log: {}\n", stream, e));
+            }
+        }
+    }
+
+    /// A log line as shown to humans; JSON lines are rendered as text
+    fn humanize_log_line(line: &str, format: crate::logs::LogFormat) -> String {
+        match format {
+            crate::logs::LogFormat::Json => crate::logs::humanize_json_line(line),
+            crate::logs::LogFormat::Text => line.to_string(),
+        }
+    }
+
     /// Follow process logs in real-time.
     ///
     /// Continuously monitors and displays new log entries from both stdout and stderr
@@ -1293,129 +1957,274 @@ impl ProcessManager {
     pub async fn follow_logs(&self, identifier: &str) -> Result<()> {
         let process_id = self.resolve_identifier(identifier).await?;
 
-        // Get the process name for log file paths
-        let process_name = {
-            let processes = self.processes.read().await;
-            if let Some(process) = processes.get(&process_id) {
-                process.config.name.clone()
-            } else {
-                return Err(Error::process_not_found(identifier));
-            }
+        // Get the process name and log file paths
+        let process_name = match self.handle(process_id).await {
+            Ok(handle) => handle.config().name.clone(),
+            Err(_) => return Err(Error::process_not_found(identifier)),
         };
 
-        let (out_log, err_log, _combined_log) = self.get_log_paths(&process_name);
-
         info!("Following logs for process: {}", process_name);
         println!("==> Following logs for {} <==", process_name);
         println!("Press Ctrl+C to stop following");
         println!();
 
-        // Track file positions for both stdout and stderr
-        let mut out_position = if out_log.exists() {
-            fs::metadata(&out_log).await.map(|m| m.len()).unwrap_or(0)
-        } else {
-            0
-        };
-
-        let mut err_position = if err_log.exists() {
-            fs::metadata(&err_log).await.map(|m| m.len()).unwrap_or(0)
-        } else {
-            0
-        };
-
-        let mut interval = interval(Duration::from_millis(500)); // Check every 500ms
-
+        let mut lines = self.subscribe_process_logs(identifier).await?;
+        let mut interval = interval(Duration::from_millis(500));
         loop {
-            interval.tick().await;
-
-            // Check stdout log for new content
-            if out_log.exists() {
-                if let Ok(metadata) = fs::metadata(&out_log).await {
-                    let current_size = metadata.len();
-                    if current_size > out_position {
-                        // Read only new content from stdout log
-                        use tokio::fs::File;
-                        use tokio::io::{AsyncReadExt, AsyncSeekExt};
-
-                        if let Ok(mut file) = File::open(&out_log).await {
-                            if file
-                                .seek(tokio::io::SeekFrom::Start(out_position))
-                                .await
-                                .is_ok()
-                            {
-                                let mut buffer = Vec::new();
-                                if file.read_to_end(&mut buffer).await.is_ok() {
-                                    if let Ok(new_content) = String::from_utf8(buffer) {
-                                        if !new_content.trim().is_empty() {
-                                            for line in new_content.lines() {
-                                                println!("[stdout] {}", line);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        out_position = current_size;
-                    }
+            tokio::select! {
+                line = lines.next() => {
+                    let Some(line) = line else {
+                        break;
+                    };
+                    let stream = match line.stream {
+                        crate::logs::LogStream::Out => "stdout",
+                        crate::logs::LogStream::Err => "stderr",
+                    };
+                    println!("[{}] {}", stream, Self::humanize_log_line(&line.line, lines.format()));
                 }
-            }
-
-            // Check stderr log for new content
-            if err_log.exists() {
-                if let Ok(metadata) = fs::metadata(&err_log).await {
-                    let current_size = metadata.len();
-                    if current_size > err_position {
-                        // Read only new content from stderr log
-                        use tokio::fs::File;
-                        use tokio::io::{AsyncReadExt, AsyncSeekExt};
-
-                        if let Ok(mut file) = File::open(&err_log).await {
-                            if file
-                                .seek(tokio::io::SeekFrom::Start(err_position))
-                                .await
-                                .is_ok()
-                            {
-                                let mut buffer = Vec::new();
-                                if file.read_to_end(&mut buffer).await.is_ok() {
-                                    if let Ok(new_content) = String::from_utf8(buffer) {
-                                        if !new_content.trim().is_empty() {
-                                            for line in new_content.lines() {
-                                                println!("[stderr] {}", line);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        err_position = current_size;
+                _ = interval.tick() => {
+                    // Check if process still exists
+                    if !self.processes.read().await.contains_key(&process_id) {
+                        println!(
+                            "\nProcess {} no longer exists, stopping log following",
+                            process_name
+                        );
+                        break;
                     }
                 }
             }
+        }
 
-            // Check if process still exists
-            let processes = self.processes.read().await;
-            if !processes.contains_key(&process_id) {
-                println!(
-                    "\nProcess {} no longer exists, stopping log following",
-                    process_name
-                );
-                break;
+        Ok(())
+    }
+
+    /// Subscribe to the new output lines of a process.
+    ///
+    /// Lines are sent by the process' log shim as they are pumped, see
+    /// [`LogSubscription`]. Lines written before the call are not delivered, see
+    /// [`log_tail`](Self::log_tail) for those.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process doesn't exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmdaemon::ProcessManager;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let manager = ProcessManager::new().await?;
+    /// let mut lines = manager.subscribe_process_logs("my-app").await?;
+    /// while let Some(line) = lines.next().await {
+    ///     println!("[{}] {}", line.stream, line.line);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_process_logs(&self, identifier: &str) -> Result<LogSubscription> {
+        let process_id = self.resolve_identifier(identifier).await?;
+        let config = self
+            .handle(process_id)
+            .await
+            .map_err(|_| Error::process_not_found(identifier))?
+            .config();
+
+        let (out_log, err_log, combined_log) = self.log_paths(&config);
+        Ok(LogSubscription::new(
+            process_id,
+            config.name.clone(),
+            out_log,
+            err_log,
+            Some(crate::logs::subscriber_socket(&combined_log)),
+            config.log_format,
+        ))
+    }
+
+    /// Get the last `lines` lines one output stream of a process logged, oldest
+    /// first, including rotated segments. JSON logs are rendered as text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process doesn't exist or its log cannot be read.
+    pub async fn log_tail(
+        &self,
+        identifier: &str,
+        stream: crate::logs::LogStream,
+        lines: usize,
+    ) -> Result<Vec<String>> {
+        let process_id = self.resolve_identifier(identifier).await?;
+        let config = self
+            .handle(process_id)
+            .await
+            .map_err(|_| Error::process_not_found(identifier))?
+            .config();
+        let (out_log, err_log, _combined_log) = self.log_paths(&config);
+        let path = match stream {
+            crate::logs::LogStream::Out => out_log,
+            crate::logs::LogStream::Err => err_log,
+        };
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(crate::logs::tail_lines(&path, lines)
+            .await?
+            .iter()
+            .map(|line| Self::humanize_log_line(line, config.log_format))
+            .collect())
+    }
+
+    /// Find the lines of a process' logs that match `search`, oldest first.
+    ///
+    /// Both streams are searched unless `search.streams` says otherwise, and their
+    /// lines are interleaved by the time they were written. Time windows need the
+    /// lines to carry a timestamp (`log_date_format` or the JSON log format); lines
+    /// with no known time are skipped then. Rotated segments are searched as well,
+    /// line by line. Without a pattern, level or time window this returns the last
+    /// `search.limit` lines, which are read from the end of the logs. JSON logs are
+    /// rendered as text.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process doesn't exist or its logs cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmdaemon::logs::{LogLevel, LogSearch};
+    /// use pmdaemon::ProcessManager;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let manager = ProcessManager::new().await?;
+    /// let search = LogSearch {
+    ///     pattern: Some(regex::Regex::new("timeout|refused")?),
+    ///     since: Some(chrono::Utc::now() - chrono::Duration::minutes(10)),
+    ///     level: Some(LogLevel::Error),
+    ///     ..Default::default()
+    /// };
+    /// for entry in manager.search_logs("my-app", &search).await? {
+    ///     println!("[{}] {}", entry.stream, entry.line);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn search_logs(&self, identifier: &str, search: &LogSearch) -> Result<Vec<LogEntry>> {
+        use crate::logs::LogStream;
+
+        let process_id = self.resolve_identifier(identifier).await?;
+        let config = self
+            .handle(process_id)
+            .await
+            .map_err(|_| Error::process_not_found(identifier))?
+            .config();
+        let (out_log, err_log, _combined_log) = self.log_paths(&config);
+        let date_format = config.log_date_format.as_deref();
+        let filtered = search.pattern.is_some()
+            || search.since.is_some()
+            || search.until.is_some()
+            || search.level.is_some();
+
+        let mut entries = Vec::new();
+        for (stream, path) in [(LogStream::Out, out_log), (LogStream::Err, err_log)] {
+            if !search.includes(stream) || !path.exists() {
+                continue;
             }
-            drop(processes);
+            let lines = if filtered {
+                crate::logs::search_lines(&path, stream, config.log_format, date_format, search)
+                    .await?
+            } else {
+                let mut at = None;
+                crate::logs::tail_lines(&path, search.limit.unwrap_or(usize::MAX))
+                    .await?
+                    .into_iter()
+                    .map(|line| {
+                        if let Some(timestamp) =
+                            crate::logs::line_timestamp(&line, config.log_format, date_format)
+                        {
+                            at = Some(timestamp);
+                        }
+                        (at, Self::humanize_log_line(&line, config.log_format))
+                    })
+                    .collect()
+            };
+            entries.extend(lines.into_iter().map(|(at, line)| LogEntry {
+                at,
+                process_id,
+                process_name: config.name.clone(),
+                stream,
+                line,
+            }));
         }
 
-        Ok(())
+        // Stable, so lines without a timestamp keep their order
+        entries.sort_by_key(|entry| entry.at);
+        if let Some(limit) = search.limit {
+            entries.drain(..entries.len().saturating_sub(limit));
+        }
+        Ok(entries)
+    }
+
+    /// Find the lines matching `search` in the logs of each of `processes`, see
+    /// [`search_logs`](Self::search_logs), interleaved by the time they were written
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a process doesn't exist or its logs cannot be read.
+    pub async fn search_processes_logs(
+        &self,
+        processes: &[ProcessStatus],
+        search: &LogSearch,
+    ) -> Result<Vec<LogEntry>> {
+        let mut entries = Vec::new();
+        for process in processes {
+            entries.extend(self.search_logs(&process.id.to_string(), search).await?);
+        }
+        // Stable, so lines without a timestamp keep their order
+        entries.sort_by_key(|entry| entry.at);
+        Ok(entries)
+    }
+
+    /// Processes selected by a pattern, sorted by name: all processes without a
+    /// pattern, otherwise a process name or ID, the base name of a cluster, a
+    /// namespace or a glob over process names (`api-*`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the glob is invalid or no process matches.
+    pub async fn match_processes(&self, pattern: Option<&str>) -> Result<Vec<ProcessStatus>> {
+        let mut processes = self.list().await?;
+        processes.sort_by(|a, b| a.name.cmp(&b.name));
+        let Some(pattern) = pattern else {
+            return Ok(processes);
+        };
+
+        let glob = if pattern.contains(['*', '?', '[']) {
+            Some(glob::Pattern::new(pattern).map_err(|e| {
+                Error::invalid_argument(format!("Invalid pattern '{}': {}", pattern, e))
+            })?)
+        } else {
+            None
+        };
+        processes.retain(|process| match &glob {
+            Some(glob) => glob.matches(&process.name),
+            None => {
+                crate::events::matches_process(pattern, process.id, &process.name)
+                    || process.namespace == pattern
+            }
+        });
+        if processes.is_empty() {
+            return Err(Error::process_not_found(pattern));
+        }
+        Ok(processes)
     }
 
     /// Get process information
     pub async fn get_process_info(&self, identifier: &str) -> Result<ProcessStatus> {
         let process_id = self.resolve_identifier(identifier).await?;
 
-        let processes = self.processes.read().await;
-        if let Some(process) = processes.get(&process_id) {
-            Ok(process.status())
-        } else {
-            Err(Error::process_not_found(identifier))
+        match self.handle(process_id).await {
+            Ok(handle) => Ok(handle.status()),
+            Err(_) => Err(Error::process_not_found(identifier)),
         }
     }
 
@@ -1453,159 +2262,533 @@ impl ProcessManager {
         info!("Starting process monitoring loop");
 
         let mut interval = interval(Duration::from_secs(5)); // Check every 5 seconds
+        let mut exits = self.take_exit_events();
 
         loop {
-            interval.tick().await;
-
-            // Check process status and handle auto-restart
-            if let Err(e) = self.check_all_processes().await {
-                error!("Error during process monitoring: {}", e);
+            tokio::select! {
+                _ = interval.tick() => self.run_monitoring_cycle().await,
+                Some(event) = async { exits.as_mut()?.recv().await } => {
+                    self.handle_exit(event).await;
+                }
             }
+        }
+    }
+
+    /// Run a single pass of the monitoring loop
+    ///
+    /// Checks every process for crashes and memory limits (restarting where configured)
+    /// and refreshes CPU/memory metrics. Errors are logged rather than returned so that
+    /// long-running callers such as the daemon can keep ticking.
+    pub async fn run_monitoring_cycle(&self) {
+        // Check process status and handle auto-restart
+        if let Err(e) = self.check_all_processes().await {
+            error!("Error during process monitoring: {}", e);
+        }
+
+        // Update monitoring data
+        if let Err(e) = self.update_monitoring_data().await {
+            error!("Error updating monitoring data: {}", e);
+        }
+
+        self.rotate_logs().await;
+    }
+
+    /// Rotate the log files of every process whose `log_rotation` settings call for it
+    ///
+    /// Size limits are checked on every call, so they are enforced with the granularity
+    /// of the monitoring loop. Failures are logged and retried on the next call.
+    pub async fn rotate_logs(&self) {
+        let rotations: Vec<(ProcessConfig, crate::logs::LogRotationConfig)> = self
+            .handles()
+            .await
+            .iter()
+            .filter_map(|handle| {
+                let config = handle.config();
+                let rotation = config.log_rotation.clone()?;
+                Some((config, rotation))
+            })
+            .collect();
+
+        let now = chrono::Utc::now();
+        for (config, rotation) in rotations {
+            let (out_log, err_log, combined_log) = self.log_paths(&config);
+            for log_file in [out_log, err_log, combined_log] {
+                let size = match fs::metadata(&log_file).await {
+                    Ok(metadata) => metadata.len(),
+                    Err(_) => continue,
+                };
+                let last_rotation = *self
+                    .log_rotations
+                    .write()
+                    .await
+                    .entry(log_file.clone())
+                    .or_insert(now);
+                if !rotation.is_due(size, last_rotation, now) {
+                    continue;
+                }
 
-            // Update monitoring data
-            if let Err(e) = self.update_monitoring_data().await {
-                error!("Error updating monitoring data: {}", e);
+                match crate::logs::rotate(&log_file, &rotation).await {
+                    Ok(rotated) => {
+                        if rotated {
+                            info!("Rotated log file {:?} ({} bytes)", log_file, size);
+                        }
+                        self.log_rotations.write().await.insert(log_file, now);
+                    }
+                    Err(e) => warn!("Failed to rotate log file {:?}: {}", log_file, e),
+                }
             }
         }
     }
 
     /// Check all processes and handle auto-restart
+    ///
+    /// Every process is checked by its own actor, and due restarts run concurrently.
     pub async fn check_all_processes(&self) -> Result<()> {
-        let mut processes = self.processes.write().await;
-        let mut monitor = self.monitor.write().await;
+        let handles = self.handles().await;
         let mut to_restart = Vec::new();
 
         // Collect PIDs for monitoring data update
-        let pids: Vec<(ProcessId, u32, String)> = processes
+        let pids: Vec<(ProcessId, u32, String, Option<u64>)> = handles
             .iter()
-            .filter_map(|(id, p)| p.pid().map(|pid| (*id, pid, p.config.name.clone())))
+            .filter_map(|handle| {
+                let status = handle.status();
+                let pid = status.pid.filter(|_| status.state.is_running())?;
+                Some((
+                    handle.id(),
+                    pid,
+                    status.name,
+                    handle.config().max_memory_restart,
+                ))
+            })
             .collect();
 
         // Update monitoring data for all running processes
         if !pids.is_empty() {
-            let pid_list: Vec<u32> = pids.iter().map(|(_, pid, _)| *pid).collect();
-            let monitoring_data = monitor.update_process_metrics(&pid_list).await;
+            let pid_list: Vec<u32> = pids.iter().map(|(_, pid, _, _)| *pid).collect();
+            let monitoring_data = self
+                .monitor
+                .write()
+                .await
+                .update_process_metrics(&pid_list)
+                .await;
 
             // Check memory limits for each process
-            for (process_id, pid, process_name) in pids {
-                if let Some(process) = processes.get(&process_id) {
-                    if let Some(max_memory) = process.config.max_memory_restart {
-                        if let Some(metrics) = monitoring_data.get(&pid) {
-                            let memory_mb = metrics.memory_usage / 1024 / 1024; // Convert to MB
-                            let limit_mb = max_memory / 1024 / 1024;
-
-                            if memory_mb > limit_mb {
-                                warn!("Process {} exceeded memory limit: {}MB > {}MB, scheduling restart",
-                                      process_name, memory_mb, limit_mb);
-                                to_restart.push(process_id);
-                            } else {
-                                debug!(
-                                    "Process {} memory usage: {}MB / {}MB",
-                                    process_name, memory_mb, limit_mb
-                                );
-                            }
-                        }
+            for (process_id, pid, process_name, max_memory) in pids {
+                if let (Some(max_memory), Some(metrics)) = (max_memory, monitoring_data.get(&pid)) {
+                    let memory_mb = metrics.memory_usage / 1024 / 1024; // Convert to MB
+                    let limit_mb = max_memory / 1024 / 1024;
+
+                    if memory_mb > limit_mb {
+                        warn!(
+                            "Process {} exceeded memory limit: {}MB > {}MB, scheduling restart",
+                            process_name, memory_mb, limit_mb
+                        );
+                        to_restart.push(process_id);
+                    } else {
+                        debug!(
+                            "Process {} memory usage: {}MB / {}MB",
+                            process_name, memory_mb, limit_mb
+                        );
                     }
                 }
             }
         }
 
-        drop(monitor); // Release monitor lock before process operations
+        // Check process status and apply the restart policy to processes that exited
+        let now = chrono::Utc::now();
+        let checks = handles.iter().map(|handle| {
+            handle.call(move |process| {
+                Box::pin(async move {
+                    // Only processes with a child handle or a running PID restored from
+                    // disk can exit on their own
+                    let could_exit = process.child.is_some()
+                        || (process.is_running() && process.stored_pid.is_some());
+                    let started_at = process.started_at;
+
+                    let mut exit = None;
+                    let mut crash = None;
+                    let decision = match process.check_status().await {
+                        Ok(true) => {
+                            process.restart_state.last_seen_alive = Some(now);
+                            None
+                        }
+                        Ok(false) if could_exit => {
+                            // Recorded by the status check, with the signal of killed runs
+                            exit = process.last_exit;
+                            // Exits are only noticed on the next tick, so measure the run
+                            // up to the last time the process was seen alive
+                            let uptime = match (started_at, process.restart_state.last_seen_alive) {
+                                (Some(started), Some(seen)) => {
+                                    (seen - started).max(chrono::Duration::zero())
+                                }
+                                _ => chrono::Duration::zero(),
+                            };
+                            crash = exit
+                                .filter(|exit| CrashRecord::is_crash(&process.config, *exit))
+                                .map(|exit| CrashRecord::new(process, exit, uptime));
+                            Some(Self::apply_restart_policy(process, uptime, now))
+                        }
+                        // Restarts delayed by `restart_delay`
+                        Ok(false) => (process.state == ProcessState::Stopped
+                            && process.restart_state.is_due(now))
+                        .then_some(RestartDecision::Restart { at: now }),
+                        Err(e) => {
+                            error!(
+                                "Error checking process {} status: {}",
+                                process.config.name, e
+                            );
+                            None
+                        }
+                    };
+                    (
+                        process.id,
+                        exit,
+                        crash,
+                        decision,
+                        process.config.clone(),
+                        process.error.clone(),
+                    )
+                })
+            })
+        });
 
-        // Check process status and handle crashes
-        for (process_id, process) in processes.iter_mut() {
-            match process.check_status().await {
-                Ok(is_running) => {
-                    if !is_running && process.config.autorestart {
-                        // Process has died and should be restarted
-                        warn!(
-                            "Process {} has died, scheduling restart",
-                            process.config.name
-                        );
-                        to_restart.push(*process_id);
-                    }
-                }
-                Err(e) => {
-                    error!(
-                        "Error checking process {} status: {}",
-                        process.config.name, e
-                    );
+        let mut gave_up = Vec::new();
+        for (process_id, exit, crash, decision, config, error) in
+            join_all(checks).await.into_iter().flatten()
+        {
+            if let Some(exit) = exit {
+                self.record_event(process_id, &config.name, EventKind::Exited { exit })
+                    .await;
+            }
+            if let Some(crash) = crash {
+                self.save_crash(crash).await;
+            }
+            match decision {
+                Some(RestartDecision::Restart { at })
+                    if at <= now && !to_restart.contains(&process_id) =>
+                {
+                    to_restart.push(process_id);
                 }
+                Some(RestartDecision::GiveUp) => gave_up.push((process_id, config, error)),
+                _ => {}
+            }
+        }
+
+        for (process_id, config, error) in gave_up {
+            self.record_event(
+                process_id,
+                &config.name,
+                EventKind::Errored {
+                    message: error.unwrap_or_default(),
+                },
+            )
+            .await;
+            if let Err(e) = self.remove_pid_file(&config).await {
+                warn!("Failed to remove PID file for {}: {}", config.name, e);
             }
         }
 
         // Restart processes that need it (memory limit exceeded or crashed)
-        for process_id in to_restart {
-            if let Some(process) = processes.get_mut(&process_id) {
-                let restart_reason = if process.is_running() {
-                    "memory limit exceeded"
+        join_all(
+            to_restart
+                .into_iter()
+                .map(|process_id| self.auto_restart(process_id)),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    /// Ask the restart policy what to do with a process that exited after a run of
+    /// `uptime`, logging the decision and marking the process errored when it is
+    /// given up on
+    fn apply_restart_policy(
+        process: &mut Process,
+        uptime: chrono::Duration,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> RestartDecision {
+        let exit = process.last_exit.unwrap_or_else(ProcessExit::unknown);
+        let decision = process
+            .restart_state
+            .on_exit(&process.config, exit, uptime, now);
+        match decision {
+            RestartDecision::Restart { at } => {
+                if at <= now {
+                    warn!(
+                        "Process {} has died, scheduling restart",
+                        process.config.name
+                    );
                 } else {
-                    "process crashed"
-                };
+                    warn!(
+                        "Process {} has died, restarting in {}ms",
+                        process.config.name,
+                        (at - now).num_milliseconds()
+                    );
+                }
+            }
+            RestartDecision::GiveUp => {
+                error!(
+                    "Process {} exited {} times in a row within {}ms of starting, giving up",
+                    process.config.name,
+                    process.config.max_restarts + 1,
+                    process.config.min_uptime
+                );
+                process.set_state(ProcessState::Errored);
+                process.set_stored_pid(None);
+                process.error = Some(format!(
+                    "Too many unstable restarts ({}), not restarting again",
+                    process.restart_state.unstable_restarts
+                ));
+            }
+            RestartDecision::Skip => {
                 info!(
-                    "Auto-restarting process {} ({})",
-                    process.config.name, restart_reason
+                    "Process {} exited ({}), not restarting (restart mode: {}{})",
+                    process.config.name,
+                    exit,
+                    process.config.restart_mode,
+                    if process.config.autorestart {
+                        ""
+                    } else {
+                        ", autorestart disabled"
+                    }
                 );
+            }
+        }
+        decision
+    }
 
-                if let Err(e) = process.restart().await {
-                    error!(
-                        "Failed to auto-restart process {}: {}",
-                        process.config.name, e
-                    );
-                } else {
-                    // Update PID file and metadata for restarted process
-                    if let Some(new_pid) = process.pid() {
-                        process.set_stored_pid(Some(new_pid));
-                        let process_name = process.config.name.clone();
-                        drop(processes); // Release lock before async operation
-                        if let Err(e) = self.save_pid_file(&process_name, new_pid).await {
-                            warn!("Failed to update PID file after restart: {}", e);
-                        }
-                        // Re-acquire lock to save metadata
-                        let processes = self.processes.read().await;
-                        if let Some(process) = processes.get(&process_id) {
-                            if let Err(e) = self.save_process_metadata(process).await {
-                                warn!("Failed to update metadata after restart: {}", e);
-                            }
-                        }
-                        break; // Re-acquire lock in next iteration
+    /// React to a child process exiting, as reported by its waiter task.
+    ///
+    /// Applies the restart policy right away instead of waiting for the next
+    /// monitoring tick. Events for children that were already stopped, restarted or
+    /// handled by the monitoring loop are ignored.
+    pub async fn handle_exit(&self, event: ExitEvent) {
+        let Ok(handle) = self.handle(event.process_id).await else {
+            return;
+        };
+        let outcome = handle
+            .call(move |process| {
+                Box::pin(async move {
+                    if process.child.as_ref().map(|child| child.id()) != Some(event.pid) {
+                        debug!(
+                            "Ignoring exit of PID {} that is no longer tracked by process {}",
+                            event.pid, process.config.name
+                        );
+                        return None;
+                    }
+
+                    let started_at = process.started_at;
+                    if let Err(e) = process.check_status().await {
+                        error!(
+                            "Error checking process {} status: {}",
+                            process.config.name, e
+                        );
+                        return None;
                     }
+                    info!(
+                        "Process {} (PID {}) exited ({})",
+                        process.config.name, event.pid, event.exit
+                    );
+
+                    let uptime = started_at
+                        .map(|started| (event.at - started).max(chrono::Duration::zero()))
+                        .unwrap_or_else(chrono::Duration::zero);
+                    let crash = CrashRecord::is_crash(&process.config, event.exit)
+                        .then(|| CrashRecord::new(process, event.exit, uptime));
+                    let now = chrono::Utc::now();
+                    let decision = Self::apply_restart_policy(process, uptime, now);
+                    Some((
+                        decision,
+                        now,
+                        process.config.clone(),
+                        ProcessMetadata::of(process),
+                        crash,
+                    ))
+                })
+            })
+            .await;
+
+        let Ok(Some((decision, now, config, metadata, crash))) = outcome else {
+            return;
+        };
+        if let Some(crash) = crash {
+            self.save_crash(crash).await;
+        }
+        if let Err(e) = self.save_process_metadata(&metadata).await {
+            warn!("Failed to update metadata after exit: {}", e);
+        }
+        self.record_event(
+            event.process_id,
+            &config.name,
+            EventKind::Exited { exit: event.exit },
+        )
+        .await;
+
+        match decision {
+            RestartDecision::Restart { at } if at <= now => {
+                self.auto_restart(event.process_id).await
+            }
+            RestartDecision::GiveUp => {
+                self.record_event(
+                    event.process_id,
+                    &config.name,
+                    EventKind::Errored {
+                        message: handle.status().error.unwrap_or_default(),
+                    },
+                )
+                .await;
+                if let Err(e) = self.remove_pid_file(&config).await {
+                    warn!("Failed to remove PID file for {}: {}", config.name, e);
                 }
             }
+            _ => {}
         }
+    }
 
-        Ok(())
+    /// When the earliest delayed restart is due, if any is pending
+    pub async fn next_restart_due(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.handles()
+            .await
+            .iter()
+            .map(ProcessHandle::status)
+            .filter(|status| status.state == ProcessState::Stopped)
+            .filter_map(|status| status.next_restart)
+            .min()
     }
 
-    /// Update process monitoring data
-    pub async fn update_monitoring_data(&self) -> Result<()> {
-        let processes = self.processes.read().await;
-        let mut monitor = self.monitor.write().await;
+    /// Restart stopped processes whose delayed restart is due
+    pub async fn restart_due_processes(&self) {
+        let now = chrono::Utc::now();
+        let due: Vec<ProcessId> = self
+            .handles()
+            .await
+            .iter()
+            .map(ProcessHandle::status)
+            .filter(|status| {
+                status.state == ProcessState::Stopped
+                    && status.next_restart.is_some_and(|at| now >= at)
+            })
+            .map(|status| status.id)
+            .collect();
+        join_all(
+            due.into_iter()
+                .map(|process_id| self.auto_restart(process_id)),
+        )
+        .await;
+    }
+
+    /// Take the receiving end of the exit event channel.
+    ///
+    /// Returns `None` if it has already been taken. Long-running hosts (the daemon and
+    /// the web server) pass it to [`run_exit_loop`] so that crashed processes are
+    /// restarted as soon as they exit.
+    pub fn take_exit_events(&self) -> Option<mpsc::UnboundedReceiver<ExitEvent>> {
+        self.exit_rx
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+
+    /// Restart a process from the monitoring loop, keeping its PID file and metadata
+    /// up to date
+    async fn auto_restart(&self, process_id: ProcessId) {
+        let Ok(handle) = self.handle(process_id).await else {
+            return;
+        };
+
+        let log_files = self.log_files(&handle.config());
+        let restarted = handle
+            .call(move |process| {
+                Box::pin(async move {
+                    let reason = if process.is_running() {
+                        RestartReason::MemoryLimit
+                    } else {
+                        RestartReason::Crash
+                    };
+                    info!(
+                        "Auto-restarting process {} ({})",
+                        process.config.name, reason
+                    );
+
+                    process.restart_state.next_restart = None;
+                    if let Err(e) = process.restart_with_log_files(&log_files).await {
+                        error!(
+                            "Failed to auto-restart process {}: {}",
+                            process.config.name, e
+                        );
+                        return None;
+                    }
+                    process.record_restart(reason);
+
+                    let new_pid = process.pid()?;
+                    process.set_stored_pid(Some(new_pid));
+                    Some((
+                        process.config.clone(),
+                        new_pid,
+                        ProcessMetadata::of(process),
+                        reason,
+                    ))
+                })
+            })
+            .await;
+
+        // Update PID file and metadata for restarted process
+        if let Ok(Some((config, new_pid, metadata, reason))) = restarted {
+            self.record_event(
+                process_id,
+                &config.name,
+                EventKind::Restarted {
+                    reason,
+                    pid: Some(new_pid),
+                },
+            )
+            .await;
+            if let Err(e) = self.save_pid_file(&config, new_pid).await {
+                warn!("Failed to update PID file after restart: {}", e);
+            }
+            if let Err(e) = self.save_process_metadata(&metadata).await {
+                warn!("Failed to update metadata after restart: {}", e);
+            }
+        }
+    }
 
+    /// Update process monitoring data
+    pub async fn update_monitoring_data(&self) -> Result<()> {
         // Collect PIDs of running processes
-        let pids: Vec<u32> = processes.values().filter_map(|p| p.pid()).collect();
+        let handles: HashMap<u32, ProcessHandle> = self
+            .handles()
+            .await
+            .into_iter()
+            .filter_map(|handle| {
+                let status = handle.status();
+                let pid = status.pid.filter(|_| status.state.is_running())?;
+                Some((pid, handle))
+            })
+            .collect();
 
-        if !pids.is_empty() {
-            let monitoring_data = monitor.update_process_metrics(&pids).await;
+        if !handles.is_empty() {
+            let pids: Vec<u32> = handles.keys().copied().collect();
+            let monitoring_data = self
+                .monitor
+                .write()
+                .await
+                .update_process_metrics(&pids)
+                .await;
             debug!(
                 "Updated monitoring data for {} processes",
                 monitoring_data.len()
             );
 
-            // Drop the read lock before acquiring write lock
-            drop(processes);
-
             // Apply monitoring data back to processes
-            let mut processes = self.processes.write().await;
-            for (pid, data) in monitoring_data {
-                // Find the process with this PID and update its monitoring data
-                for process in processes.values_mut() {
+            let updates = monitoring_data.into_iter().filter_map(|(pid, data)| {
+                let handle = handles.get(&pid)?;
+                Some(handle.with(move |process| {
                     if process.pid() == Some(pid) {
                         process.update_monitoring(data.cpu_usage, data.memory_usage);
-                        break;
                     }
-                }
-            }
+                }))
+            });
+            join_all(updates).await;
         }
 
         Ok(())
@@ -1618,44 +2801,31 @@ impl ProcessManager {
     }
 
     /// Save process configuration to disk
-    async fn save_process_config(&self, process: &Process) -> Result<()> {
-        let config_file = self
-            .config_dir
-            .join(format!("{}.json", process.config.name));
-        let config_json = serde_json::to_string_pretty(&process.config)
+    async fn save_process_config(&self, config: &ProcessConfig) -> Result<()> {
+        let config_file = self.config_dir.join(format!("{}.json", config.name));
+        let config_json = serde_json::to_string_pretty(config)
             .map_err(|e| Error::config(format!("Failed to serialize config: {}", e)))?;
 
         fs::write(&config_file, config_json)
             .await
             .map_err(|e| Error::config(format!("Failed to write config file: {}", e)))?;
 
-        debug!("Saved configuration for process: {}", process.config.name);
+        debug!("Saved configuration for process: {}", config.name);
         Ok(())
     }
 
     /// Save process runtime metadata (assigned port, etc.) to disk
-    async fn save_process_metadata(&self, process: &Process) -> Result<()> {
-        use serde_json::json;
-
-        let metadata_file = self
-            .config_dir
-            .join(format!("{}.meta.json", process.config.name));
-
-        let metadata = json!({
-            "id": process.id,
-            "assigned_port": process.assigned_port,
-            "instance": process.instance,
-            "stored_pid": process.stored_pid
-        });
+    async fn save_process_metadata(&self, metadata: &ProcessMetadata) -> Result<()> {
+        let metadata_file = self.config_dir.join(format!("{}.meta.json", metadata.name));
 
-        let metadata_json = serde_json::to_string_pretty(&metadata)
+        let metadata_json = serde_json::to_string_pretty(metadata)
             .map_err(|e| Error::config(format!("Failed to serialize metadata: {}", e)))?;
 
         fs::write(&metadata_file, metadata_json)
             .await
             .map_err(|e| Error::config(format!("Failed to write metadata file: {}", e)))?;
 
-        debug!("Saved metadata for process: {}", process.config.name);
+        debug!("Saved metadata for process: {}", metadata.name);
         Ok(())
     }
 
@@ -1694,9 +2864,38 @@ impl ProcessManager {
             process.instance = Some(instance as u32);
         }
 
-        // Restore stored PID
+        // Restore stored PID and the start time recorded with it
         if let Some(pid) = metadata.get("stored_pid").and_then(|v| v.as_u64()) {
             process.stored_pid = Some(pid as u32);
+            process.stored_pid_start_time = metadata
+                .get("stored_pid_start_time")
+                .and_then(|v| v.as_u64());
+        }
+
+        // Restore how the last run ended and the restart history
+        let last_exit_code = metadata
+            .get("last_exit_code")
+            .and_then(|v| v.as_i64())
+            .map(|code| code as i32);
+        let last_exit_signal = metadata
+            .get("last_exit_signal")
+            .cloned()
+            .and_then(|v| serde_json::from_value::<ProcessSignal>(v).ok());
+        if last_exit_code.is_some() || last_exit_signal.is_some() {
+            process.last_exit = Some(ProcessExit {
+                code: last_exit_code,
+                signal: last_exit_signal,
+                unknown: false,
+            });
+        }
+        if let Some(history) = metadata.get("restart_history").cloned() {
+            match serde_json::from_value::<Vec<RestartRecord>>(history) {
+                Ok(history) => process.restart_history = history.into(),
+                Err(e) => warn!(
+                    "Ignoring invalid restart history for {}: {}",
+                    process.config.name, e
+                ),
+            }
         }
 
         debug!("Loaded metadata for process: {}", process.config.name);
@@ -1704,15 +2903,16 @@ impl ProcessManager {
     }
 
     /// Attempt to detect port from process logs
-    async fn detect_port_from_logs(&self, process_name: &str) -> Option<u16> {
-        let (out_log, _err_log, _combined_log) = self.get_log_paths(process_name);
+    async fn detect_port_from_logs(&self, config: &ProcessConfig) -> Option<u16> {
+        let (out_log, _err_log, _combined_log) = self.log_paths(config);
 
         if !out_log.exists() {
             return None;
         }
 
-        match fs::read_to_string(&out_log).await {
-            Ok(content) => {
+        // Only the end of the log is needed, which is read without loading the file
+        match crate::logs::tail_lines(&out_log, 50).await {
+            Ok(lines) => {
                 // Common patterns for port detection
                 let patterns = [
                     r"(?i)server.*(?:listening|bound|running).*(?:on|at).*:(\d+)",
@@ -1724,8 +2924,7 @@ impl ProcessManager {
 
                 for pattern in &patterns {
                     if let Ok(re) = regex::Regex::new(pattern) {
-                        for line in content.lines().rev().take(50) {
-                            // Check last 50 lines
+                        for line in lines.iter().rev() {
                             if let Some(captures) = re.captures(line) {
                                 if let Some(port_match) = captures.get(1) {
                                     if let Ok(port) = port_match.as_str().parse::<u16>() {
@@ -1744,7 +2943,7 @@ impl ProcessManager {
     }
 
     /// Load all process configurations from disk
-    async fn load_processes(&mut self) -> Result<()> {
+    async fn load_processes(&self) -> Result<()> {
         let mut entries = fs::read_dir(&self.config_dir)
             .await
             .map_err(|e| Error::config(format!("Failed to read config directory: {}", e)))?;
@@ -1773,7 +2972,7 @@ impl ProcessManager {
     }
 
     /// Load a single process configuration from disk
-    async fn load_process_config(&mut self, config_path: &PathBuf) -> Result<()> {
+    async fn load_process_config(&self, config_path: &PathBuf) -> Result<()> {
         let config_content = fs::read_to_string(config_path)
             .await
             .map_err(|e| Error::config(format!("Failed to read config file: {}", e)))?;
@@ -1783,16 +2982,23 @@ impl ProcessManager {
 
         // Create process but don't start it automatically
         let mut process = Process::new(config.clone());
-        let process_id = process.id;
+        process.notify_exits(self.exit_tx.clone());
 
-        // Load runtime metadata if it exists
+        // Load runtime metadata (including the process ID) if it exists
         self.load_process_metadata(&mut process).await?;
+        let process_id = process.id;
 
         // Check if the process is still running by checking PID files
-        if let Ok(Some(pid)) = self.read_pid_file(&config.name).await {
-            // Check if the process is actually running
-            let mut monitor = self.monitor.write().await;
-            if monitor.is_process_running(pid).await {
+        if let Ok(Some(pid)) = self.read_pid_file(&config).await {
+            // The recorded start time only applies to the PID it was recorded with
+            if process.stored_pid != Some(pid) {
+                process.stored_pid = Some(pid);
+                process.stored_pid_start_time = None;
+            }
+
+            // Check if the process is actually running and has not been replaced by
+            // an unrelated process reusing its PID
+            if process.owns_stored_pid() {
                 // Process is still running, update the process state
                 process.set_state(crate::process::ProcessState::Online);
 
@@ -1834,7 +3040,7 @@ impl ProcessManager {
 
                 // Try to detect port from logs if not already assigned
                 if process.assigned_port.is_none() {
-                    if let Some(detected_port) = self.detect_port_from_logs(&config.name).await {
+                    if let Some(detected_port) = self.detect_port_from_logs(&config).await {
                         process.assigned_port = Some(detected_port);
                         debug!(
                             "Detected port {} for process {} from logs",
@@ -1847,7 +3053,8 @@ impl ProcessManager {
             } else {
                 // PID file exists but process is not running, clean up
                 process.set_state(crate::process::ProcessState::Stopped);
-                if let Err(e) = self.remove_pid_file(&config.name).await {
+                process.set_stored_pid(None);
+                if let Err(e) = self.remove_pid_file(&config).await {
                     warn!("Failed to remove stale PID file for {}: {}", config.name, e);
                 }
             }
@@ -1861,7 +3068,7 @@ impl ProcessManager {
         let mut processes = self.processes.write().await;
         let mut name_map = self.name_to_id.write().await;
 
-        processes.insert(process_id, process);
+        processes.insert(process_id, ProcessHandle::spawn(process));
         name_map.insert(config.name, process_id);
 
         debug!("Loaded process configuration: {}", process_name);
@@ -1897,7 +3104,8 @@ impl ProcessManager {
         lines: Option<usize>,
         follow: bool,
     ) -> Result<()> {
-        let (out_log, err_log, _combined_log) = self.get_log_paths(process_name);
+        let (out_log, err_log, _combined_log) = self.log_paths_by_name(process_name).await;
+        let format = self.log_format_by_name(process_name).await;
 
         if follow {
             // Use the dedicated follow_logs method for real-time following
@@ -1909,15 +3117,9 @@ impl ProcessManager {
         // Read stdout log
         if out_log.exists() {
             println!("==> {} stdout <==", process_name);
-            if let Ok(content) = fs::read_to_string(&out_log).await {
-                let lines: Vec<&str> = content.lines().collect();
-                let start = if lines.len() > lines_to_read {
-                    lines.len() - lines_to_read
-                } else {
-                    0
-                };
-                for line in &lines[start..] {
-                    println!("{}", line);
+            if let Ok(lines) = crate::logs::tail_lines(&out_log, lines_to_read).await {
+                for line in lines {
+                    println!("{}", Self::humanize_log_line(&line, format));
                 }
             }
             println!();
@@ -1926,15 +3128,9 @@ impl ProcessManager {
         // Read stderr log
         if err_log.exists() {
             println!("==> {} stderr <==", process_name);
-            if let Ok(content) = fs::read_to_string(&err_log).await {
-                let lines: Vec<&str> = content.lines().collect();
-                let start = if lines.len() > lines_to_read {
-                    lines.len() - lines_to_read
-                } else {
-                    0
-                };
-                for line in &lines[start..] {
-                    println!("{}", line);
+            if let Ok(lines) = crate::logs::tail_lines(&err_log, lines_to_read).await {
+                for line in lines {
+                    println!("{}", Self::humanize_log_line(&line, format));
                 }
             }
         }
@@ -1944,7 +3140,7 @@ impl ProcessManager {
 
     /// Clear log files for a process
     pub async fn clear_logs(&self, process_name: &str) -> Result<()> {
-        let (out_log, err_log, _combined_log) = self.get_log_paths(process_name);
+        let (out_log, err_log, combined_log) = self.log_paths_by_name(process_name).await;
 
         if out_log.exists() {
             fs::write(&out_log, "")
@@ -1958,15 +3154,66 @@ impl ProcessManager {
                 .map_err(|e| Error::config(format!("Failed to clear stderr log: {}", e)))?;
         }
 
+        if combined_log.exists() {
+            fs::write(&combined_log, "")
+                .await
+                .map_err(|e| Error::config(format!("Failed to clear combined log: {}", e)))?;
+        }
+
+        for segment in [out_log, err_log, combined_log]
+            .iter()
+            .flat_map(|log_file| crate::logs::segments(log_file))
+        {
+            fs::remove_file(&segment).await.map_err(|e| {
+                Error::config(format!("Failed to remove rotated log {:?}: {}", segment, e))
+            })?;
+        }
+
         info!("Cleared logs for process: {}", process_name);
         Ok(())
     }
 
+    /// Get the log paths of a process by name, falling back to the default paths
+    /// for processes that are not managed (anymore)
+    async fn log_paths_by_name(&self, process_name: &str) -> (PathBuf, PathBuf, PathBuf) {
+        let process_id = self.name_to_id.read().await.get(process_name).copied();
+        let handle = match process_id {
+            Some(process_id) => self.handle(process_id).await.ok(),
+            None => None,
+        };
+        match handle {
+            Some(handle) => self.log_paths(&handle.config()),
+            None => self.get_log_paths(process_name),
+        }
+    }
+
+    /// Get the log format of a process by name, text for processes that are not
+    /// managed (anymore)
+    async fn log_format_by_name(&self, process_name: &str) -> crate::logs::LogFormat {
+        let process_id = self.name_to_id.read().await.get(process_name).copied();
+        match process_id {
+            Some(process_id) => match self.handle(process_id).await {
+                Ok(handle) => handle.config().log_format,
+                Err(_) => crate::logs::LogFormat::default(),
+            },
+            None => crate::logs::LogFormat::default(),
+        }
+    }
+
     /// Remove log files for a process
-    async fn remove_log_files(&self, process_name: &str) -> Result<()> {
-        let (out_log, err_log, combined_log) = self.get_log_paths(process_name);
+    async fn remove_log_files(&self, config: &ProcessConfig) -> Result<()> {
+        let process_name = &config.name;
+        let (out_log, err_log, combined_log) = self.log_paths(config);
 
-        for log_file in [out_log, err_log, combined_log] {
+        let mut log_files = vec![out_log, err_log, combined_log];
+        let segments: Vec<PathBuf> = log_files
+            .iter()
+            .flat_map(|log_file| crate::logs::segments(log_file))
+            .collect();
+        log_files.extend(segments);
+
+        for log_file in log_files {
+            self.log_rotations.write().await.remove(&log_file);
             if log_file.exists() {
                 if let Err(e) = fs::remove_file(&log_file).await {
                     warn!("Failed to remove log file {:?}: {}", log_file, e);
@@ -2086,6 +3333,541 @@ impl ProcessManager {
         }
     }
 
+    /// Take the receiving end of the file watch event channel.
+    ///
+    /// Returns `None` if it has already been taken. Long-running hosts (the daemon and
+    /// the web server) pass it to [`run_restart_loop`](crate::watcher::run_restart_loop)
+    /// so that file changes restart the owning process.
+    pub fn take_watch_events(&self) -> Option<mpsc::UnboundedReceiver<WatchEvent>> {
+        self.watch_rx
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+
+    /// Start watchers for all running processes that have `watch` enabled.
+    ///
+    /// Used by the daemon to resume watching processes it picked up from disk.
+    pub async fn resume_watching(&self) {
+        let process_ids: Vec<ProcessId> = self
+            .handles()
+            .await
+            .iter()
+            .filter(|handle| handle.config().watch && handle.status().state.is_running())
+            .map(ProcessHandle::id)
+            .collect();
+
+        for process_id in process_ids {
+            if self.watchers.read().await.contains_key(&process_id) {
+                continue;
+            }
+            if let Err(e) = self.start_watching(process_id).await {
+                warn!("Failed to watch files for process {}: {}", process_id, e);
+            }
+        }
+    }
+
+    /// Restart a process in response to a debounced file change event
+    pub async fn handle_watch_event(&self, event: WatchEvent) -> Result<()> {
+        // Events can still be queued after watching was stopped
+        if !self.watchers.read().await.contains_key(&event.process_id) {
+            debug!(
+                "Ignoring file change event for unwatched process {}",
+                event.process_id
+            );
+            return Ok(());
+        }
+
+        let process_name = match self.handle(event.process_id).await {
+            Ok(handle) => handle.config().name,
+            Err(_) => return Ok(()),
+        };
+
+        info!(
+            "Detected {} changed file(s) for process {}, restarting: {:?}",
+            event.paths.len(),
+            process_name,
+            event.paths
+        );
+        self.restart_by_id(event.process_id, None, RestartReason::FileChange)
+            .await
+    }
+
+    /// Start watching a process' files if its configuration enables it
+    async fn start_watching(&self, process_id: ProcessId) -> Result<()> {
+        let Ok(handle) = self.handle(process_id).await else {
+            return Ok(());
+        };
+        let config = handle.config();
+        if !config.watch {
+            return Ok(());
+        }
+
+        // Never restart because of our own log files or state directory
+        let (out_log, err_log, combined_log) = self.log_paths(&config);
+        let ignored = vec![out_log, err_log, combined_log, self.config_dir.clone()];
+
+        let watcher =
+            ProcessWatcher::for_config(process_id, &config, ignored, self.watch_tx.clone())?;
+        info!(
+            "Watching {:?} for changes to process {}",
+            watcher.roots(),
+            config.name
+        );
+        handle.with(|process| process.watching = true).await?;
+
+        self.watchers.write().await.insert(process_id, watcher);
+        Ok(())
+    }
+
+    /// Stop watching a process' files
+    async fn stop_watching(&self, process_id: ProcessId) {
+        if self.watchers.write().await.remove(&process_id).is_some() {
+            if let Ok(handle) = self.handle(process_id).await {
+                let _ = handle.with(|process| process.watching = false).await;
+                debug!(
+                    "Stopped watching files for process {}",
+                    handle.config().name
+                );
+            }
+        }
+    }
+
+    /// Wait until a process passes its health check.
+    ///
+    /// The process is held in the `starting` state meanwhile. On success it becomes
+    /// `online` and regular health monitoring begins; if it exits or does not become
+    /// healthy within its `wait_timeout`, it is stopped and marked `errored`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::HealthCheck`] if the process never becomes healthy, or a
+    /// configuration error if it has no enabled health check.
+    pub async fn wait_ready(&self, process_id: ProcessId) -> Result<()> {
+        let (health_check, timeout) = self.begin_wait_ready(process_id).await?;
+        let result = crate::health::wait_until_healthy(health_check, timeout, || {
+            self.is_starting(process_id)
+        })
+        .await;
+        self.finish_wait_ready(process_id, result).await
+    }
+
+    /// First step of [`wait_ready`](Self::wait_ready): put the process in the
+    /// `starting` state and return its health check and startup timeout.
+    ///
+    /// Together with [`is_starting`](Self::is_starting) and
+    /// [`finish_wait_ready`](Self::finish_wait_ready) this lets hosts that share the
+    /// manager behind a lock (such as the daemon) wait without holding the lock.
+    pub async fn begin_wait_ready(
+        &self,
+        process_id: ProcessId,
+    ) -> Result<(HealthCheckConfig, Duration)> {
+        let (health_check, timeout) = self
+            .handle(process_id)
+            .await?
+            .with(|process| {
+                let health_check = match &process.config.health_check {
+                    Some(health_check) if health_check.enabled => health_check.clone(),
+                    _ => {
+                        return Err(Error::config(format!(
+                            "Process '{}' has no enabled health check to wait for",
+                            process.config.name
+                        )))
+                    }
+                };
+                if !process.is_running() {
+                    return Err(Error::health_check(format!(
+                        "{}: process is not running",
+                        process.config.name
+                    )));
+                }
+
+                process.set_state(ProcessState::Starting);
+                let timeout = Duration::from_millis(process.config.wait_timeout);
+                info!(
+                    "Waiting up to {:?} for process {} to become healthy",
+                    timeout, process.config.name
+                );
+                Ok((health_check, timeout))
+            })
+            .await??;
+
+        // The readiness wait replaces regular monitoring until the process is online
+        self.stop_health_check(process_id).await;
+        Ok((health_check, timeout))
+    }
+
+    /// Check whether a process is still starting, noticing if it has exited
+    pub async fn is_starting(&self, process_id: ProcessId) -> bool {
+        let Ok(handle) = self.handle(process_id).await else {
+            return false;
+        };
+        handle
+            .call(|process| {
+                Box::pin(async move {
+                    let _ = process.check_status().await;
+                    process.state == ProcessState::Starting
+                })
+            })
+            .await
+            .unwrap_or(false)
+    }
+
+    /// Last step of [`wait_ready`](Self::wait_ready): mark the process online, or stop
+    /// it and mark it errored when it never became healthy
+    pub async fn finish_wait_ready(
+        &self,
+        process_id: ProcessId,
+        result: Result<HealthStatus>,
+    ) -> Result<()> {
+        let handle = self.handle(process_id).await?;
+        let config = handle.config();
+        let process_name = config.name.clone();
+
+        match result {
+            Ok(status) => {
+                info!("Process {} is healthy and online", process_name);
+                handle
+                    .with(|process| process.set_state(ProcessState::Online))
+                    .await?;
+                self.record_event(
+                    process_id,
+                    &process_name,
+                    EventKind::HealthChanged {
+                        from: HealthState::Unknown,
+                        to: status.state,
+                        message: None,
+                    },
+                )
+                .await;
+
+                self.start_health_check(process_id).await;
+                handle.with(|process| process.health = Some(status)).await?;
+                Ok(())
+            }
+            Err(e) => {
+                let message = match e {
+                    Error::HealthCheck { message } => message,
+                    other => other.to_string(),
+                };
+                error!(
+                    "Process {} failed to become ready: {}",
+                    process_name, message
+                );
+
+                let error = format!("Not ready: {}", message);
+                self.record_event(
+                    process_id,
+                    &process_name,
+                    EventKind::Errored {
+                        message: error.clone(),
+                    },
+                )
+                .await;
+                handle
+                    .call(|process| {
+                        Box::pin(async move {
+                            if process.is_running() {
+                                if let Err(stop_err) = process.stop().await {
+                                    warn!(
+                                        "Failed to stop process {}: {}",
+                                        process.config.name, stop_err
+                                    );
+                                }
+                            }
+                            process.set_state(ProcessState::Errored);
+                            process.set_stored_pid(None);
+                            process.error = Some(error);
+                        })
+                    })
+                    .await?;
+
+                if let Err(e) = self.remove_pid_file(&config).await {
+                    warn!("Failed to remove PID file for {}: {}", process_name, e);
+                }
+                Err(Error::health_check(format!(
+                    "{}: {}",
+                    process_name, message
+                )))
+            }
+        }
+    }
+
+    /// Take the receiving end of the health event channel.
+    ///
+    /// Returns `None` if it has already been taken. Long-running hosts (the daemon and
+    /// the web server) pass it to [`run_health_loop`](crate::health::run_health_loop)
+    /// so that check results are recorded and unhealthy processes are handled.
+    pub fn take_health_events(&self) -> Option<mpsc::UnboundedReceiver<HealthEvent>> {
+        self.health_rx
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+
+    /// Start health monitors for all running processes that have health checks enabled.
+    ///
+    /// Used by the daemon to resume checking processes it picked up from disk.
+    pub async fn resume_health_checks(&self) {
+        let process_ids: Vec<ProcessId> = self
+            .handles()
+            .await
+            .iter()
+            .filter(|handle| handle.status().state.is_running())
+            .map(ProcessHandle::id)
+            .collect();
+
+        for process_id in process_ids {
+            if !self.health_monitors.read().await.contains_key(&process_id) {
+                self.start_health_check(process_id).await;
+            }
+        }
+    }
+
+    /// Record a health check result and apply the configured action once a process
+    /// becomes unhealthy
+    pub async fn handle_health_event(&self, event: HealthEvent) -> Result<()> {
+        // Results can still be queued after the monitor was stopped
+        if !self
+            .health_monitors
+            .read()
+            .await
+            .contains_key(&event.process_id)
+        {
+            debug!(
+                "Ignoring health check result for unmonitored process {}",
+                event.process_id
+            );
+            return Ok(());
+        }
+
+        let Ok(handle) = self.handle(event.process_id).await else {
+            return Ok(());
+        };
+        let status = event.status.clone();
+        let became_unhealthy = event.became_unhealthy;
+        let (transition, unhealthy) = handle
+            .with(move |process| {
+                let healthy = status.is_healthy();
+                let previous = process
+                    .health
+                    .as_ref()
+                    .map(|health| health.state)
+                    .unwrap_or_default();
+                let transition = (status.state != previous && status.state != HealthState::Unknown)
+                    .then(|| EventKind::HealthChanged {
+                        from: previous,
+                        to: status.state,
+                        message: status.error_message.clone(),
+                    });
+                process.health = Some(status);
+
+                // Restarted `wait_ready` processes come online with their first passing
+                // check
+                if healthy && process.state == ProcessState::Starting {
+                    info!("Process {} is healthy and online", process.config.name);
+                    process.set_state(ProcessState::Online);
+                }
+
+                if !became_unhealthy || !process.is_running() {
+                    return (transition, None);
+                }
+                let action = process
+                    .config
+                    .health_check
+                    .as_ref()
+                    .map(|config| config.on_unhealthy)
+                    .unwrap_or_default();
+                (transition, Some(action))
+            })
+            .await?;
+        let process_name = handle.config().name;
+        if let Some(transition) = transition {
+            self.record_event(event.process_id, &process_name, transition)
+                .await;
+        }
+        let Some(action) = unhealthy else {
+            return Ok(());
+        };
+
+        let reason = event
+            .status
+            .error_message
+            .unwrap_or_else(|| "health check failed".to_string());
+        match action {
+            UnhealthyAction::Restart => {
+                warn!(
+                    "Process {} is unhealthy ({}), restarting",
+                    process_name, reason
+                );
+                self.restart_by_id(event.process_id, None, RestartReason::Unhealthy)
+                    .await
+            }
+            UnhealthyAction::Stop => {
+                warn!(
+                    "Process {} is unhealthy ({}), stopping",
+                    process_name, reason
+                );
+                self.stop(&event.process_id.to_string()).await.map(|_| ())
+            }
+            UnhealthyAction::Alert => {
+                error!(
+                    "Process {} is unhealthy after {} consecutive failed checks: {}",
+                    process_name, event.status.consecutive_failures, reason
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Start (or restart) health checks for a process if its configuration enables them
+    async fn start_health_check(&self, process_id: ProcessId) {
+        let Ok(handle) = self.handle(process_id).await else {
+            return;
+        };
+        let process_config = handle.config();
+        let config = match process_config.health_check {
+            Some(config) if config.enabled => config,
+            _ => return,
+        };
+
+        debug!(
+            "Checking health of process {} every {:?}",
+            process_config.name, config.interval
+        );
+        if handle
+            .with(|process| process.health = Some(HealthStatus::default()))
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        let monitor = HealthMonitor::spawn(process_id, config, self.health_tx.clone());
+        self.health_monitors
+            .write()
+            .await
+            .insert(process_id, monitor);
+    }
+
+    /// Stop health checks for a process
+    async fn stop_health_check(&self, process_id: ProcessId) {
+        if self
+            .health_monitors
+            .write()
+            .await
+            .remove(&process_id)
+            .is_some()
+        {
+            if let Ok(handle) = self.handle(process_id).await {
+                let _ = handle.with(|process| process.health = None).await;
+                debug!("Stopped health checks for process {}", handle.config().name);
+            }
+        }
+    }
+
+    /// Read lifecycle events from the journal, oldest first
+    pub async fn events(&self, filter: &EventFilter) -> Result<Vec<Event>> {
+        self.events.read(filter).await
+    }
+
+    /// Read crash records, oldest first, optionally only those of `process` (a name, an
+    /// ID or the base name of a cluster), including records of deleted processes
+    pub async fn crashes(&self, process: Option<&str>) -> Result<Vec<CrashRecord>> {
+        self.crashes.list(process).await
+    }
+
+    /// Read a single crash record by its ID
+    pub async fn crash(&self, crash_id: &str) -> Result<CrashRecord> {
+        self.crashes.get(crash_id).await
+    }
+
+    /// Save a crash record with the tails of the process' logs, logging rather than
+    /// returning failures
+    async fn save_crash(&self, mut record: CrashRecord) {
+        let (out_log, err_log, _combined_log) = self.log_paths(&record.config);
+        match crate::logs::tail_lines(&err_log, crate::CRASH_LOG_LINES).await {
+            Ok(lines) => record.stderr_tail = lines,
+            Err(e) => warn!("Failed to read error log of {}: {}", record.process_name, e),
+        }
+        match crate::logs::tail_lines(&out_log, crate::CRASH_LOG_LINES).await {
+            Ok(lines) => record.stdout_tail = lines,
+            Err(e) => warn!(
+                "Failed to read output log of {}: {}",
+                record.process_name, e
+            ),
+        }
+        match self.crashes.save(&record).await {
+            Ok(path) => info!(
+                "Saved crash record for {} to {}",
+                record.process_name,
+                path.display()
+            ),
+            Err(e) => warn!(
+                "Failed to save crash record for {}: {}",
+                record.process_name, e
+            ),
+        }
+    }
+
+    /// Append a lifecycle event to the journal, logging rather than returning failures
+    async fn record_event(&self, process_id: ProcessId, process_name: &str, kind: EventKind) {
+        let event = Event::new(process_id, process_name, kind);
+        if let Err(e) = self.events.record(&event).await {
+            warn!(
+                "Failed to record {} event for process {}: {}",
+                event.kind.name(),
+                process_name,
+                e
+            );
+        }
+    }
+
+    /// Journal entry for saving `config`: [`EventKind::Created`] when no configuration
+    /// is saved under its name yet, otherwise the settings that differ from the saved
+    /// one, if any
+    async fn config_event(&self, config: &ProcessConfig) -> Option<EventKind> {
+        let config_file = self.config_dir.join(format!("{}.json", config.name));
+        let Ok(saved) = fs::read_to_string(&config_file).await else {
+            return Some(EventKind::Created);
+        };
+        let saved: serde_json::Value = serde_json::from_str(&saved).ok()?;
+        let changes = crate::events::config_changes(&saved, &serde_json::to_value(config).ok()?);
+        (!changes.is_empty()).then_some(EventKind::ConfigChanged { changes })
+    }
+
+    /// Deallocate the ports of a process and journal the release of its assigned port
+    async fn release_ports(
+        &self,
+        process_id: ProcessId,
+        config: &ProcessConfig,
+        assigned_port: Option<u16>,
+    ) {
+        let Some(port_config) = &config.port else {
+            return;
+        };
+        self.deallocate_ports(port_config, assigned_port).await;
+        if let Some(port) = assigned_port {
+            self.record_event(process_id, &config.name, EventKind::PortReleased { port })
+                .await;
+        }
+    }
+
+    /// Get the actor handle of a process
+    async fn handle(&self, process_id: ProcessId) -> Result<ProcessHandle> {
+        self.processes
+            .read()
+            .await
+            .get(&process_id)
+            .cloned()
+            .ok_or_else(|| Error::process_not_found(process_id.to_string()))
+    }
+
+    /// Get the actor handles of all processes
+    async fn handles(&self) -> Vec<ProcessHandle> {
+        self.processes.read().await.values().cloned().collect()
+    }
+
     /// Get the number of processes
     pub async fn process_count(&self) -> usize {
         let processes = self.processes.read().await;
@@ -2105,6 +3887,34 @@ impl ProcessManager {
     }
 }
 
+/// Apply the restart policy as child processes exit.
+///
+/// Long-running hosts of a [`ProcessManager`] (the daemon and the web server) spawn
+/// this with the receiver obtained from [`ProcessManager::take_exit_events`]. Restarts
+/// delayed by `restart_delay` or exponential backoff are carried out when they fall
+/// due.
+pub async fn run_exit_loop(
+    manager: std::sync::Arc<RwLock<ProcessManager>>,
+    mut events: mpsc::UnboundedReceiver<ExitEvent>,
+) {
+    loop {
+        let next_restart = manager.read().await.next_restart_due().await;
+        let restart_in =
+            next_restart.map(|at| (at - chrono::Utc::now()).to_std().unwrap_or(Duration::ZERO));
+
+        tokio::select! {
+            event = events.recv() => match event {
+                Some(event) => manager.read().await.handle_exit(event).await,
+                None => break,
+            },
+            _ = tokio::time::sleep(restart_in.unwrap_or(Duration::MAX)), if restart_in.is_some() => {
+                manager.read().await.restart_due_processes().await;
+            }
+        }
+    }
+    info!("Process exit loop finished");
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2119,17 +3929,22 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let config_dir = temp_dir.path().to_path_buf();
 
-        let manager = ProcessManager {
-            processes: RwLock::new(HashMap::new()),
-            name_to_id: RwLock::new(HashMap::new()),
-            monitor: RwLock::new(Monitor::new()),
-            config_dir,
-            allocated_ports: RwLock::new(HashSet::new()),
-        };
+        let manager = ProcessManager::with_config_dir(config_dir).await.unwrap();
 
         (manager, temp_dir)
     }
 
+    /// Wait until `path` holds at least `lines` lines
+    async fn wait_for_lines(path: &Path, lines: usize) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while std::fs::read_to_string(path).map_or(0, |log| log.lines().count()) < lines {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
     fn create_test_config(name: &str) -> ProcessConfig {
         ProcessConfig::builder()
             .name(name)
@@ -2164,32 +3979,42 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_ensure_logs_dir() {
-        let (manager, _temp_dir) = create_test_manager().await;
-        let result = manager.ensure_logs_dir().await;
+    async fn test_ensure_log_dirs() {
+        let (manager, temp_dir) = create_test_manager().await;
+        let result = manager
+            .ensure_log_dirs(&create_test_config("test-process"))
+            .await;
         assert!(result.is_ok());
 
         let logs_dir = manager.get_logs_dir();
         assert!(logs_dir.exists());
+
+        // Directories of configured log files are created as well
+        let mut config = create_test_config("custom-logs");
+        config.error_file = Some(temp_dir.path().join("custom/nested/err.log"));
+        manager.ensure_log_dirs(&config).await.unwrap();
+        assert!(temp_dir.path().join("custom/nested").is_dir());
     }
 
     #[tokio::test]
     async fn test_save_and_read_pid_file() {
         let (manager, _temp_dir) = create_test_manager().await;
-        let process_name = "test-process";
+        let config = create_test_config("test-process");
         let pid = 12345u32;
 
         // Save PID file
-        let result = manager.save_pid_file(process_name, pid).await;
+        let result = manager.save_pid_file(&config, pid).await;
         assert!(result.is_ok());
 
         // Read PID file
-        let read_result = manager.read_pid_file(process_name).await;
+        let read_result = manager.read_pid_file(&config).await;
         assert!(read_result.is_ok());
         assert_eq!(read_result.unwrap(), Some(pid));
 
         // Test non-existent PID file
-        let missing_result = manager.read_pid_file("non-existent").await;
+        let missing_result = manager
+            .read_pid_file(&create_test_config("non-existent"))
+            .await;
         assert!(missing_result.is_ok());
         assert_eq!(missing_result.unwrap(), None);
     }
@@ -2197,18 +4022,18 @@ mod tests {
     #[tokio::test]
     async fn test_remove_pid_file() {
         let (manager, _temp_dir) = create_test_manager().await;
-        let process_name = "test-process";
+        let config = create_test_config("test-process");
         let pid = 12345u32;
 
         // Save PID file first
-        manager.save_pid_file(process_name, pid).await.unwrap();
+        manager.save_pid_file(&config, pid).await.unwrap();
 
         // Remove PID file
-        let result = manager.remove_pid_file(process_name).await;
+        let result = manager.remove_pid_file(&config).await;
         assert!(result.is_ok());
 
         // Verify it's gone
-        let read_result = manager.read_pid_file(process_name).await;
+        let read_result = manager.read_pid_file(&config).await;
         assert!(read_result.is_ok());
         assert_eq!(read_result.unwrap(), None);
     }
@@ -2341,7 +4166,7 @@ mod tests {
         {
             let mut processes = manager.processes.write().await;
             let mut name_map = manager.name_to_id.write().await;
-            processes.insert(process_id, process);
+            processes.insert(process_id, ProcessHandle::spawn(process));
             name_map.insert(config.name.clone(), process_id);
         }
 
@@ -2369,6 +4194,462 @@ mod tests {
         assert!(!logs.contains("stdout line 1"));
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_rotate_logs_of_running_process() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let config = ProcessConfig::builder()
+            .name("chatty")
+            .script("sh")
+            .args(vec!["-c", "seq 1 50; sleep 0.5; seq 51 60; sleep 30"])
+            .log_rotation(crate::logs::LogRotationConfig::default().max_size(64))
+            .build()
+            .unwrap();
+        manager.start(config).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        manager.rotate_logs().await;
+        tokio::time::sleep(Duration::from_millis(600)).await;
+
+        // The process keeps writing to the truncated file without a restart
+        let (out_log, _, _) = manager.get_log_paths("chatty");
+        let current = fs::read_to_string(&out_log).await.unwrap();
+        assert!(current.starts_with("51\n"));
+        assert!(crate::logs::segment_path(&out_log, 1, false).exists());
+
+        let logs = manager.get_logs("chatty", 15).await.unwrap();
+        let expected: String = (46..=60).map(|i| format!("{}\n", i)).collect();
+        assert!(logs.contains(&expected));
+        assert!(!logs.contains("\n45\n"));
+
+        // Restarting appends to the existing log instead of truncating it
+        manager.restart("chatty").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        let current = fs::read_to_string(&out_log).await.unwrap();
+        assert!(current.starts_with("51\n"));
+        assert!(current.contains("60\n1\n"));
+
+        manager.delete("chatty").await.unwrap();
+        assert!(!crate::logs::segment_path(&out_log, 1, false).exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_configured_log_and_pid_files() {
+        let (manager, temp_dir) = create_test_manager().await;
+        let files_dir = temp_dir.path().join("custom");
+        let config = ProcessConfig::builder()
+            .name("custom-files")
+            .script("sh")
+            .args(vec![
+                "-c",
+                "echo to-out; sleep 0.1; echo to-err >&2; sleep 30",
+            ])
+            .cwd(temp_dir.path())
+            .out_file("custom/out.log")
+            .error_file("custom/err.log")
+            .log_file("custom/nested/all.log")
+            .pid_file(files_dir.join("run/app.pid"))
+            .build()
+            .unwrap();
+        manager.start(config).await.unwrap();
+        wait_for_lines(&files_dir.join("nested/all.log"), 2).await;
+
+        let pid = fs::read_to_string(files_dir.join("run/app.pid"))
+            .await
+            .unwrap();
+        let info = manager.get_process_info("custom-files").await.unwrap();
+        assert_eq!(Some(pid.parse::<u32>().unwrap()), info.pid);
+
+        let read = |name: &str| std::fs::read_to_string(files_dir.join(name)).unwrap();
+        assert_eq!(read("out.log"), "to-out\n");
+        assert_eq!(read("err.log"), "to-err\n");
+        assert_eq!(read("nested/all.log"), "[out] to-out\n[err] to-err\n");
+        assert!(!manager.get_log_paths("custom-files").0.exists());
+
+        let logs = manager.get_logs("custom-files", 10).await.unwrap();
+        assert!(logs.contains("to-out") && logs.contains("to-err"));
+
+        manager.clear_logs("custom-files").await.unwrap();
+        assert_eq!(read("nested/all.log"), "");
+
+        manager.delete("custom-files").await.unwrap();
+        for name in ["out.log", "err.log", "nested/all.log", "run/app.pid"] {
+            assert!(!files_dir.join(name).exists(), "{} was not removed", name);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_pumped_logs_are_timestamped_and_followed() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let config = ProcessConfig::builder()
+            .name("stamped")
+            .script("sh")
+            .args(vec![
+                "-c",
+                "sleep 0.5; echo ready; echo warning >&2; sleep 30",
+            ])
+            .log_date_format("%Y-%m-%d")
+            .build()
+            .unwrap();
+        manager.start(config).await.unwrap();
+        let mut lines = manager.subscribe_process_logs("stamped").await.unwrap();
+
+        let today = chrono::Local::now().format("%Y-%m-%d").to_string();
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            let line = tokio::time::timeout(Duration::from_secs(5), lines.next())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(line.process_name, "stamped");
+            received.push((line.stream, line.line));
+        }
+        assert!(received.contains(&(crate::logs::LogStream::Out, format!("{} ready", today))));
+        assert!(received.contains(&(crate::logs::LogStream::Err, format!("{} warning", today))));
+
+        let (out_log, _err_log, combined_log) = manager.get_log_paths("stamped");
+        wait_for_lines(&combined_log, 2).await;
+        let out = fs::read_to_string(&out_log).await.unwrap();
+        assert_eq!(out, format!("{} ready\n", today));
+        let combined = fs::read_to_string(&combined_log).await.unwrap();
+        assert!(combined.contains(&format!("{} [err] warning\n", today)));
+
+        manager.delete("stamped").await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_log_subscription_keeps_every_line_across_restarts() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let config = ProcessConfig::builder()
+            .name("counter")
+            .script("sh")
+            .args(vec![
+                "-c",
+                "i=0; while true; do echo $i; i=$((i+1)); sleep 0.02; done",
+            ])
+            .build()
+            .unwrap();
+        manager.start(config).await.unwrap();
+        let mut lines = manager.subscribe_process_logs("counter").await.unwrap();
+
+        async fn next(lines: &mut crate::logs::LogSubscription) -> u64 {
+            let line = tokio::time::timeout(Duration::from_secs(5), lines.next())
+                .await
+                .unwrap()
+                .unwrap();
+            line.line.parse().unwrap()
+        }
+        let mut received = Vec::new();
+        while received.len() < 10 {
+            received.push(next(&mut lines).await);
+        }
+        manager.restart("counter").await.unwrap();
+        // The restarted counter begins again at zero; every line of both runs
+        // arrives once and in order, including those written while the
+        // subscription was reconnecting to the new log shim
+        loop {
+            let value = next(&mut lines).await;
+            let previous = *received.last().unwrap();
+            received.push(value);
+            if value == 0 {
+                break;
+            }
+            assert_eq!(value, previous + 1, "received {:?}", received);
+        }
+        for expected in 1..30 {
+            assert_eq!(next(&mut lines).await, expected, "received {:?}", received);
+        }
+
+        manager.delete("counter").await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_match_processes_and_search_logs() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        for name in ["stack-api", "stack-worker", "other"] {
+            let mut config = ProcessConfig::builder()
+                .name(name)
+                .script("sh")
+                .args(vec!["-c", "echo one; echo two; sleep 30"])
+                .log_date_format("%Y-%m-%d %H:%M:%S%.3f")
+                .build()
+                .unwrap();
+            if name != "other" {
+                config.namespace = "stack".to_string();
+            }
+            manager.start(config).await.unwrap();
+        }
+
+        let names = |processes: Vec<ProcessStatus>| -> Vec<String> {
+            processes.into_iter().map(|process| process.name).collect()
+        };
+        assert_eq!(names(manager.match_processes(None).await.unwrap()).len(), 3);
+        assert_eq!(
+            names(manager.match_processes(Some("stack-*")).await.unwrap()),
+            vec!["stack-api", "stack-worker"]
+        );
+        assert_eq!(
+            names(manager.match_processes(Some("stack")).await.unwrap()),
+            vec!["stack-api", "stack-worker"]
+        );
+        assert_eq!(
+            names(manager.match_processes(Some("other")).await.unwrap()),
+            vec!["other"]
+        );
+        assert!(manager.match_processes(Some("missing")).await.is_err());
+        assert!(manager.match_processes(Some("[")).await.is_err());
+
+        for name in ["stack-api", "stack-worker", "other"] {
+            wait_for_lines(&manager.get_log_paths(name).0, 2).await;
+        }
+        let search = |limit, since| LogSearch {
+            streams: vec![crate::logs::LogStream::Out],
+            limit,
+            since,
+            ..Default::default()
+        };
+        let last = manager
+            .search_logs("stack-api", &search(Some(1), None))
+            .await
+            .unwrap();
+        assert_eq!(last.len(), 1);
+        assert!(last[0].line.ends_with(" two"));
+        assert!(last[0].at.is_some());
+
+        let hour_ago = chrono::Utc::now() - chrono::Duration::hours(1);
+        let since = manager
+            .search_logs("stack-api", &search(None, Some(hour_ago)))
+            .await
+            .unwrap();
+        assert_eq!(since.len(), 2);
+        let later = chrono::Utc::now() + chrono::Duration::hours(1);
+        assert!(manager
+            .search_logs("stack-api", &search(None, Some(later)))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let grep = LogSearch {
+            pattern: Some(regex::Regex::new("^.* t").unwrap()),
+            until: Some(chrono::Utc::now()),
+            ..Default::default()
+        };
+        let found = manager.search_logs("stack-worker", &grep).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].process_name, "stack-worker");
+        assert!(found[0].line.ends_with(" two"));
+
+        for name in ["stack-api", "stack-worker", "other"] {
+            manager.delete(name).await.unwrap();
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_json_logs_are_humanized_when_read() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let config = ProcessConfig::builder()
+            .name("structured")
+            .script("sh")
+            .args(vec![
+                "-c",
+                r#"echo '{"level":"info","msg":"ready"}'; sleep 30"#,
+            ])
+            .log_format(crate::logs::LogFormat::Json)
+            .build()
+            .unwrap();
+        manager.start(config).await.unwrap();
+
+        let (out_log, _err_log, _combined_log) = manager.get_log_paths("structured");
+        wait_for_lines(&out_log, 1).await;
+        let entry: serde_json::Value =
+            serde_json::from_str(fs::read_to_string(&out_log).await.unwrap().trim()).unwrap();
+        assert_eq!(entry["app"], "structured");
+        assert_eq!(entry["msg"], "ready");
+        assert_eq!(entry["level"], "info");
+
+        let logs = manager.get_logs("structured", 10).await.unwrap();
+        assert!(logs.contains(" ready level=info\n"));
+        assert!(!logs.contains('{'));
+
+        manager.delete("structured").await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_signal_processes() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        for (name, instances) in [("sig-cluster", 2), ("sig-single", 1)] {
+            let mut config = ProcessConfig::builder()
+                .name(name)
+                .script("sleep")
+                .args(vec!["30"])
+                .instances(instances)
+                .build()
+                .unwrap();
+            config.namespace = "signals".to_string();
+            manager.start(config).await.unwrap();
+        }
+
+        // sleep ignores SIGWINCH
+        assert_eq!(
+            manager
+                .signal("sig-cluster", ProcessSignal::Winch)
+                .await
+                .unwrap(),
+            vec!["sig-cluster-0", "sig-cluster-1"]
+        );
+        assert_eq!(
+            manager
+                .signal_namespace("signals", ProcessSignal::Winch)
+                .await
+                .unwrap(),
+            vec!["sig-cluster-0", "sig-cluster-1", "sig-single"]
+        );
+        assert!(matches!(
+            manager.signal("missing", ProcessSignal::Hup).await,
+            Err(Error::ProcessNotFound { .. })
+        ));
+        assert!(matches!(
+            manager
+                .signal_namespace("missing", ProcessSignal::Hup)
+                .await,
+            Err(Error::ProcessNotFound { .. })
+        ));
+
+        // Stopped processes can't be signalled on their own, and are skipped in groups
+        manager.stop("sig-single").await.unwrap();
+        assert!(manager
+            .signal("sig-single", ProcessSignal::Winch)
+            .await
+            .is_err());
+        assert_eq!(
+            manager
+                .signal_namespace("signals", ProcessSignal::Winch)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let pid = manager
+            .get_process_info("sig-cluster-0")
+            .await
+            .unwrap()
+            .pid
+            .unwrap();
+        manager
+            .signal("sig-cluster-0", ProcessSignal::Term)
+            .await
+            .unwrap();
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while crate::monitoring::process_start_time(pid).is_some() {
+            assert!(std::time::Instant::now() < deadline);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        manager.delete_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cluster_instances_get_own_log_files() {
+        let (manager, temp_dir) = create_test_manager().await;
+        let config = ProcessConfig::builder()
+            .name("cluster-files")
+            .script("sleep")
+            .args(vec!["30"])
+            .instances(2)
+            .out_file(temp_dir.path().join("out.log"))
+            .build()
+            .unwrap();
+        manager.start(config).await.unwrap();
+
+        assert!(temp_dir.path().join("out-0.log").exists());
+        assert!(temp_dir.path().join("out-1.log").exists());
+        assert!(!temp_dir.path().join("out.log").exists());
+        manager.delete_all().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_lifecycle_of_restored_process() {
+        let (manager, temp_dir) = create_test_manager().await;
+        let config = ProcessConfig::builder()
+            .name("restored")
+            .script("sleep")
+            .args(vec!["30"])
+            .build()
+            .unwrap();
+        manager.start(config).await.unwrap();
+        let first_pid = manager
+            .get_process_info("restored")
+            .await
+            .unwrap()
+            .pid
+            .unwrap();
+
+        // A later CLI invocation only knows the PID stored on disk
+        let restored = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let info = restored.get_process_info("restored").await.unwrap();
+        assert_eq!(info.state, ProcessState::Online);
+        assert_eq!(info.pid, Some(first_pid));
+
+        restored.restart("restored").await.unwrap();
+        let second_pid = restored
+            .get_process_info("restored")
+            .await
+            .unwrap()
+            .pid
+            .unwrap();
+        assert_ne!(first_pid, second_pid);
+        assert_eq!(crate::monitoring::process_start_time(first_pid), None);
+
+        let third = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        third.stop("restored").await.unwrap();
+        assert_eq!(crate::monitoring::process_start_time(second_pid), None);
+        let info = third.get_process_info("restored").await.unwrap();
+        assert_eq!(info.state, ProcessState::Stopped);
+        assert_eq!(info.pid, None);
+
+        // A restored process that dies is noticed and restarted by the monitoring loop
+        third.restart("restored").await.unwrap();
+        let third_pid = third
+            .get_process_info("restored")
+            .await
+            .unwrap()
+            .pid
+            .unwrap();
+        let fourth = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        nix::sys::signal::kill(
+            nix::unistd::Pid::from_raw(third_pid as i32),
+            nix::sys::signal::Signal::SIGKILL,
+        )
+        .unwrap();
+        tick_after_exit(&fourth).await;
+        let info = fourth.get_process_info("restored").await.unwrap();
+        assert_eq!(info.state, ProcessState::Online);
+        assert_ne!(info.pid, Some(third_pid));
+
+        let fourth_pid = info.pid.unwrap();
+        drop(third);
+        let fifth = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        fifth.delete("restored").await.unwrap();
+        assert_eq!(crate::monitoring::process_start_time(fourth_pid), None);
+        drop(fourth);
+    }
+
     #[tokio::test]
     async fn test_get_logs_missing_files() {
         let (manager, _temp_dir) = create_test_manager().await;
@@ -2381,7 +4662,7 @@ mod tests {
         {
             let mut processes = manager.processes.write().await;
             let mut name_map = manager.name_to_id.write().await;
-            processes.insert(process_id, process);
+            processes.insert(process_id, ProcessHandle::spawn(process));
             name_map.insert(config.name.clone(), process_id);
         }
 
@@ -2468,7 +4749,7 @@ mod tests {
         {
             let mut processes = manager.processes.write().await;
             let mut name_map = manager.name_to_id.write().await;
-            processes.insert(process_id, process);
+            processes.insert(process_id, ProcessHandle::spawn(process));
             name_map.insert(config.name.clone(), process_id);
         }
 
@@ -2490,7 +4771,7 @@ mod tests {
         {
             let mut processes = manager.processes.write().await;
             let mut name_map = manager.name_to_id.write().await;
-            processes.insert(process_id, process);
+            processes.insert(process_id, ProcessHandle::spawn(process));
             name_map.insert(config.name.clone(), process_id);
         }
 
@@ -2519,7 +4800,7 @@ mod tests {
             for config in configs {
                 let process = Process::new(config.clone());
                 let process_id = process.id;
-                processes.insert(process_id, process);
+                processes.insert(process_id, ProcessHandle::spawn(process));
                 name_map.insert(config.name.clone(), process_id);
             }
         }
@@ -2542,7 +4823,7 @@ mod tests {
         {
             let mut processes = manager.processes.write().await;
             let mut name_map = manager.name_to_id.write().await;
-            processes.insert(process_id, process);
+            processes.insert(process_id, ProcessHandle::spawn(process));
             name_map.insert(config.name.clone(), process_id);
         }
 
@@ -2568,7 +4849,7 @@ mod tests {
         {
             let mut processes = manager.processes.write().await;
             let mut name_map = manager.name_to_id.write().await;
-            processes.insert(process_id, process);
+            processes.insert(process_id, ProcessHandle::spawn(process));
             name_map.insert(config.name.clone(), process_id);
         }
 
@@ -2595,7 +4876,7 @@ mod tests {
         let process = Process::new(config.clone());
 
         // Save config
-        let result = manager.save_process_config(&process).await;
+        let result = manager.save_process_config(&process.config).await;
         assert!(result.is_ok());
 
         // Check file exists
@@ -2616,7 +4897,10 @@ mod tests {
         let process_name = "test-process";
 
         // Create logs directory and files
-        manager.ensure_logs_dir().await.unwrap();
+        manager
+            .ensure_log_dirs(&create_test_config(process_name))
+            .await
+            .unwrap();
         let (out_log, err_log, _) = manager.get_log_paths(process_name);
 
         // Write some content to log files
@@ -2640,7 +4924,10 @@ mod tests {
         let process_name = "test-process";
 
         // Create logs directory and files
-        manager.ensure_logs_dir().await.unwrap();
+        manager
+            .ensure_log_dirs(&create_test_config(process_name))
+            .await
+            .unwrap();
         let (out_log, err_log, combined_log) = manager.get_log_paths(process_name);
 
         // Create log files
@@ -2649,7 +4936,10 @@ mod tests {
         fs::write(&combined_log, "combined content").await.unwrap();
 
         // Remove log files
-        manager.remove_log_files(process_name).await.unwrap();
+        manager
+            .remove_log_files(&create_test_config(process_name))
+            .await
+            .unwrap();
 
         // Check files are gone
         assert!(!out_log.exists());
@@ -2659,7 +4949,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_delete_all() {
-        let (mut manager, _temp_dir) = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
 
         // Create some test processes
         let config1 = create_test_config("test-process-1");
@@ -2675,8 +4965,8 @@ mod tests {
             let mut processes = manager.processes.write().await;
             let mut name_map = manager.name_to_id.write().await;
 
-            processes.insert(id1, process1);
-            processes.insert(id2, process2);
+            processes.insert(id1, ProcessHandle::spawn(process1));
+            processes.insert(id2, ProcessHandle::spawn(process2));
             name_map.insert("test-process-1".to_string(), id1);
             name_map.insert("test-process-2".to_string(), id2);
         }
@@ -2695,7 +4985,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_delete_by_status() {
-        let (mut manager, _temp_dir) = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
 
         // Create test processes with different states
         let config1 = create_test_config("stopped-process");
@@ -2715,8 +5005,8 @@ mod tests {
             let mut processes = manager.processes.write().await;
             let mut name_map = manager.name_to_id.write().await;
 
-            processes.insert(id1, process1);
-            processes.insert(id2, process2);
+            processes.insert(id1, ProcessHandle::spawn(process1));
+            processes.insert(id2, ProcessHandle::spawn(process2));
             name_map.insert("stopped-process".to_string(), id1);
             name_map.insert("online-process".to_string(), id2);
         }
@@ -2744,7 +5034,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_delete_by_invalid_status() {
-        let (mut manager, _temp_dir) = create_test_manager().await;
+        let (manager, _temp_dir) = create_test_manager().await;
 
         // Try to delete by invalid status
         let result = manager.delete_by_status("invalid-status").await;
@@ -2754,4 +5044,858 @@ mod tests {
         assert!(error_msg.contains("Invalid status"));
         assert!(error_msg.contains("invalid-status"));
     }
+
+    #[tokio::test]
+    async fn test_watch_missing_path_rejected() {
+        let (manager, temp_dir) = create_test_manager().await;
+
+        let config = ProcessConfig::builder()
+            .name("watch-missing")
+            .script("sleep")
+            .args(vec!["30"])
+            .cwd(temp_dir.path())
+            .watch(true)
+            .watch_paths(vec!["does-not-exist"])
+            .build()
+            .unwrap();
+
+        let err = manager.start(config).await.unwrap_err();
+        assert!(err.is_config_error());
+        assert_eq!(manager.process_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_watch_restarts_process_on_change() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let app_dir = TempDir::new().unwrap();
+        let mut events = manager.take_watch_events().unwrap();
+        assert!(manager.take_watch_events().is_none());
+
+        let config = ProcessConfig::builder()
+            .name("watched")
+            .script("sleep")
+            .args(vec!["30"])
+            .cwd(app_dir.path())
+            .watch(true)
+            .ignore_watch(vec!["*.tmp"])
+            .watch_delay(100)
+            .build()
+            .unwrap();
+        let process_id = manager.start(config).await.unwrap();
+        assert!(manager.get_process_info("watched").await.unwrap().watching);
+
+        fs::write(app_dir.path().join("app.js"), "changed")
+            .await
+            .unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .expect("expected a watch event")
+            .unwrap();
+        assert_eq!(event.process_id, process_id);
+
+        manager.handle_watch_event(event.clone()).await.unwrap();
+        let status = manager.get_process_info("watched").await.unwrap();
+        assert_eq!(status.restarts, 1);
+        assert!(status.watching);
+
+        // Stopping ends the watch; late events are ignored
+        manager.stop("watched").await.unwrap();
+        assert!(!manager.get_process_info("watched").await.unwrap().watching);
+        manager.handle_watch_event(event).await.unwrap();
+        let status = manager.get_process_info("watched").await.unwrap();
+        assert_eq!(status.restarts, 1);
+        assert_eq!(status.state, ProcessState::Stopped);
+    }
+
+    fn failing_health_check_config(name: &str, action: UnhealthyAction) -> ProcessConfig {
+        let health_check = crate::health::HealthCheckConfig::script("false")
+            .interval(Duration::from_millis(100))
+            .timeout(Duration::from_secs(5))
+            .retries(2)
+            .on_unhealthy(action)
+            .enabled(true);
+        ProcessConfig::builder()
+            .name(name)
+            .script("sleep")
+            .args(vec!["30"])
+            .health_check(health_check)
+            .build()
+            .unwrap()
+    }
+
+    /// Feed health events to the manager until one exhausts the retries
+    async fn drive_until_unhealthy(
+        manager: &mut ProcessManager,
+        events: &mut mpsc::UnboundedReceiver<HealthEvent>,
+    ) -> HealthEvent {
+        loop {
+            let event = tokio::time::timeout(Duration::from_secs(5), events.recv())
+                .await
+                .expect("expected a health event")
+                .unwrap();
+            manager.handle_health_event(event.clone()).await.unwrap();
+            if event.became_unhealthy {
+                return event;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_process_is_restarted() {
+        let (mut manager, _temp_dir) = create_test_manager().await;
+        let mut events = manager.take_health_events().unwrap();
+        assert!(manager.take_health_events().is_none());
+
+        let config = failing_health_check_config("unhealthy-restart", UnhealthyAction::Restart);
+        let process_id = manager.start(config).await.unwrap();
+        let status = manager.get_process_info("unhealthy-restart").await.unwrap();
+        assert!(status.health.unwrap().is_unknown());
+
+        let event = drive_until_unhealthy(&mut manager, &mut events).await;
+        assert_eq!(event.process_id, process_id);
+        assert_eq!(event.status.consecutive_failures, 2);
+
+        // Restarting starts a fresh health check
+        let status = manager.get_process_info("unhealthy-restart").await.unwrap();
+        assert_eq!(status.restarts, 1);
+        assert_eq!(status.state, ProcessState::Online);
+        assert_eq!(status.health.unwrap().total_checks, 0);
+
+        manager.stop("unhealthy-restart").await.unwrap();
+        let status = manager.get_process_info("unhealthy-restart").await.unwrap();
+        assert!(status.health.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_process_alert_and_stop_actions() {
+        let (mut manager, _temp_dir) = create_test_manager().await;
+        let mut events = manager.take_health_events().unwrap();
+
+        let config = failing_health_check_config("unhealthy-alert", UnhealthyAction::Alert);
+        manager.start(config).await.unwrap();
+        drive_until_unhealthy(&mut manager, &mut events).await;
+
+        let status = manager.get_process_info("unhealthy-alert").await.unwrap();
+        assert_eq!(status.restarts, 0);
+        assert_eq!(status.state, ProcessState::Online);
+        assert!(status.health.unwrap().is_unhealthy());
+        manager.delete("unhealthy-alert").await.unwrap();
+
+        let config = failing_health_check_config("unhealthy-stop", UnhealthyAction::Stop);
+        manager.start(config).await.unwrap();
+        drive_until_unhealthy(&mut manager, &mut events).await;
+
+        let status = manager.get_process_info("unhealthy-stop").await.unwrap();
+        assert_eq!(status.state, ProcessState::Stopped);
+        assert!(status.health.is_none());
+    }
+
+    fn wait_ready_config(name: &str, script: &str, check: &str, timeout_ms: u64) -> ProcessConfig {
+        let health_check = crate::health::HealthCheckConfig::script(check)
+            .interval(Duration::from_millis(100))
+            .timeout(Duration::from_secs(5))
+            .enabled(true);
+        ProcessConfig::builder()
+            .name(name)
+            .script(script)
+            .args(vec!["30"])
+            .health_check(health_check)
+            .wait_ready(true)
+            .wait_timeout(timeout_ms)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_start_wait_ready_success() {
+        let (manager, _temp_dir) = create_test_manager().await;
+
+        let config = wait_ready_config("ready-ok", "sleep", "true", 5000);
+        manager.start(config).await.unwrap();
+
+        let status = manager.get_process_info("ready-ok").await.unwrap();
+        assert_eq!(status.state, ProcessState::Online);
+        assert!(status.health.unwrap().is_healthy());
+        assert!(status.uptime.is_some());
+
+        manager.stop("ready-ok").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_start_wait_ready_timeout() {
+        let (manager, _temp_dir) = create_test_manager().await;
+
+        let config = wait_ready_config("ready-timeout", "sleep", "false", 300);
+        let err = manager.start(config).await.unwrap_err();
+        assert!(matches!(err, Error::HealthCheck { .. }));
+        assert!(err.to_string().contains("did not become healthy"));
+
+        let status = manager.get_process_info("ready-timeout").await.unwrap();
+        assert_eq!(status.state, ProcessState::Errored);
+        assert!(status.pid.is_none());
+        assert!(status.error.unwrap().starts_with("Not ready"));
+    }
+
+    #[tokio::test]
+    async fn test_start_wait_ready_process_exits() {
+        let (manager, _temp_dir) = create_test_manager().await;
+
+        // `echo` exits right away, long before the timeout
+        let config = wait_ready_config("ready-exits", "echo", "false", 30000);
+        let started = std::time::Instant::now();
+        let err = manager.start(config).await.unwrap_err();
+        assert!(err.to_string().contains("exited before becoming healthy"));
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_wait_ready_requires_health_check() {
+        let (manager, _temp_dir) = create_test_manager().await;
+
+        let config = ProcessConfig::builder()
+            .name("not-checked")
+            .script("sleep")
+            .args(vec!["30"])
+            .wait_ready(true)
+            .build()
+            .unwrap();
+        assert!(manager.start(config).await.unwrap_err().is_config_error());
+    }
+
+    fn cluster_config(name: &str, instances: u32) -> ProcessConfig {
+        let mut config = ProcessConfig::builder()
+            .name(name)
+            .script("sleep")
+            .args(vec!["30"])
+            .instances(instances)
+            .build()
+            .unwrap();
+        config.min_uptime = 100;
+        config
+    }
+
+    async fn instance_pids(manager: &ProcessManager, names: &[&str]) -> Vec<(ProcessId, u32)> {
+        let mut pids = Vec::new();
+        for name in names {
+            let status = manager.get_process_info(name).await.unwrap();
+            assert_eq!(status.state, ProcessState::Online);
+            pids.push((status.id, status.pid.unwrap()));
+        }
+        pids
+    }
+
+    #[tokio::test]
+    async fn test_reload_cluster_replaces_instances() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        manager.start(cluster_config("rolling", 2)).await.unwrap();
+        let names = ["rolling-0", "rolling-1"];
+        let before = instance_pids(&manager, &names).await;
+
+        // The base name rolls through every instance
+        manager.reload("rolling").await.unwrap();
+        let after = instance_pids(&manager, &names).await;
+        for ((old_id, old_pid), (new_id, new_pid)) in before.iter().zip(&after) {
+            assert_eq!(old_id, new_id);
+            assert_ne!(old_pid, new_pid);
+        }
+        for name in names {
+            let status = manager.get_process_info(name).await.unwrap();
+            assert_eq!(status.restarts, 1);
+            assert_eq!(status.instance, Some(name.ends_with('1') as u32));
+        }
+
+        // A single instance can be reloaded on its own
+        manager.reload("rolling-1").await.unwrap();
+        let single = instance_pids(&manager, &names).await;
+        assert_eq!(single[0], after[0]);
+        assert_ne!(single[1].1, after[1].1);
+
+        assert!(manager
+            .reload_with_port("rolling", Some(PortConfig::Single(9000)))
+            .await
+            .is_err());
+        assert!(manager
+            .reload("missing")
+            .await
+            .unwrap_err()
+            .is_process_error());
+        manager.delete_all().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_reload_cluster_aborts_on_unready_instance() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (manager, temp_dir) = create_test_manager().await;
+        let flag = temp_dir.path().join("healthy");
+        std::fs::write(&flag, "").unwrap();
+        let script = temp_dir.path().join("check.sh");
+        std::fs::write(&script, format!("#!/bin/sh\ntest -f {}\n", flag.display())).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut config = cluster_config("guarded", 2);
+        config.health_check = Some(
+            crate::health::HealthCheckConfig::script(&script)
+                .interval(Duration::from_millis(100))
+                .timeout(Duration::from_secs(5))
+                .enabled(true),
+        );
+        config.wait_timeout = 300;
+        manager.start(config).await.unwrap();
+        let names = ["guarded-0", "guarded-1"];
+        let before = instance_pids(&manager, &names).await;
+
+        // New instances never pass the check, so the first one aborts the reload
+        std::fs::remove_file(&flag).unwrap();
+        let err = manager.reload("guarded").await.unwrap_err();
+        assert!(err.to_string().contains("failed to become ready"));
+
+        let after = instance_pids(&manager, &names).await;
+        assert_eq!(before, after);
+        manager.delete_all().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_reload_cluster_on_fixed_ports() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let base = (20000..30000)
+            .step_by(2)
+            .find(|&port| {
+                std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+                    && std::net::TcpListener::bind(("127.0.0.1", port + 1)).is_ok()
+            })
+            .unwrap();
+
+        let mut config = cluster_config("served", 2);
+        config.script = "sh".to_string();
+        config.args = vec![
+            "-c".to_string(),
+            "exec python3 -m http.server $PORT --bind 127.0.0.1".to_string(),
+        ];
+        config.port = Some(PortConfig::Range(base, base + 1));
+        // Long enough for a replacement that cannot bind its port to exit
+        config.min_uptime = 1500;
+        manager.start(config).await.unwrap();
+        let names = ["served-0", "served-1"];
+        // Let the old instances bind their ports
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        let before = instance_pids(&manager, &names).await;
+
+        manager.reload("served").await.unwrap();
+        let after = instance_pids(&manager, &names).await;
+        for (instance, ((_, old_pid), (_, new_pid))) in before.iter().zip(&after).enumerate() {
+            assert_ne!(old_pid, new_pid);
+            let port = base + instance as u16;
+            assert_eq!(
+                manager
+                    .get_process_info(names[instance])
+                    .await
+                    .unwrap()
+                    .assigned_port,
+                Some(port)
+            );
+            assert!(std::net::TcpStream::connect(("127.0.0.1", port)).is_ok());
+        }
+        manager.delete_all().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_reload_cluster_on_fixed_ports_restores_failed_instance() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (manager, temp_dir) = create_test_manager().await;
+        let base = (30000..40000)
+            .step_by(2)
+            .find(|&port| {
+                std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+                    && std::net::TcpListener::bind(("127.0.0.1", port + 1)).is_ok()
+            })
+            .unwrap();
+        let flag = temp_dir.path().join("healthy");
+        std::fs::write(&flag, "").unwrap();
+        let script = temp_dir.path().join("check.sh");
+        std::fs::write(&script, format!("#!/bin/sh\ntest -f {}\n", flag.display())).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut config = cluster_config("restored", 2);
+        config.script = "sh".to_string();
+        config.args = vec![
+            "-c".to_string(),
+            "exec python3 -m http.server $PORT --bind 127.0.0.1".to_string(),
+        ];
+        config.port = Some(PortConfig::Range(base, base + 1));
+        config.health_check = Some(
+            crate::health::HealthCheckConfig::script(&script)
+                .interval(Duration::from_millis(100))
+                .timeout(Duration::from_secs(5))
+                .enabled(true),
+        );
+        config.wait_timeout = 300;
+        manager.start(config).await.unwrap();
+        let names = ["restored-0", "restored-1"];
+        let before = instance_pids(&manager, &names).await;
+
+        // The replacement of the first instance never becomes ready
+        std::fs::remove_file(&flag).unwrap();
+        let err = manager.reload("restored").await.unwrap_err();
+        assert!(err.to_string().contains("failed to become ready"));
+
+        // ...so the old instance runs again on its port
+        let status = manager.get_process_info("restored-0").await.unwrap();
+        assert_eq!(status.state, ProcessState::Online);
+        assert_eq!(status.assigned_port, Some(base));
+        assert_ne!(status.pid, Some(before[0].1));
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert!(std::net::TcpStream::connect(("127.0.0.1", base)).is_ok());
+
+        // The reload stopped at the first instance
+        let after = instance_pids(&manager, &names).await;
+        assert_eq!(before[1], after[1]);
+        manager.delete_all().await.unwrap();
+    }
+
+    fn crashing_config(
+        name: &str,
+        max_restarts: u32,
+        min_uptime: u64,
+        delay: u64,
+    ) -> ProcessConfig {
+        let mut config = ProcessConfig::builder()
+            .name(name)
+            .script("false")
+            .build()
+            .unwrap();
+        config.max_restarts = max_restarts;
+        config.min_uptime = min_uptime;
+        config.restart_delay = delay;
+        config
+    }
+
+    /// Run monitoring ticks until the process has exited and been handled
+    async fn tick_after_exit(manager: &ProcessManager) {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        manager.check_all_processes().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_unstable_restarts_mark_errored() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        manager
+            .start(crashing_config("crash-loop", 2, 1000, 0))
+            .await
+            .unwrap();
+
+        for expected in 1..=2 {
+            tick_after_exit(&manager).await;
+            let status = manager.get_process_info("crash-loop").await.unwrap();
+            assert_eq!(status.restarts, expected);
+            assert_eq!(status.unstable_restarts, expected);
+        }
+
+        tick_after_exit(&manager).await;
+        let status = manager.get_process_info("crash-loop").await.unwrap();
+        assert_eq!(status.state, ProcessState::Errored);
+        assert_eq!(status.restarts, 2);
+        assert!(status.error.unwrap().contains("unstable restarts"));
+
+        // Errored processes stay down
+        tick_after_exit(&manager).await;
+        let status = manager.get_process_info("crash-loop").await.unwrap();
+        assert_eq!(status.state, ProcessState::Errored);
+        assert_eq!(status.restarts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_restart_delay_between_attempts() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        manager
+            .start(crashing_config("delayed", 5, 0, 400))
+            .await
+            .unwrap();
+
+        // The exit is noticed but the restart waits for restart_delay
+        tick_after_exit(&manager).await;
+        manager.check_all_processes().await.unwrap();
+        let status = manager.get_process_info("delayed").await.unwrap();
+        assert_eq!(status.state, ProcessState::Stopped);
+        assert_eq!(status.restarts, 0);
+
+        tokio::time::sleep(Duration::from_millis(400)).await;
+        manager.check_all_processes().await.unwrap();
+        let status = manager.get_process_info("delayed").await.unwrap();
+        assert_eq!(status.restarts, 1);
+        // Runs longer than min_uptime (0ms here) are stable
+        assert_eq!(status.unstable_restarts, 0);
+        manager.delete_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_stop_cancels_pending_restart() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        manager
+            .start(crashing_config("cancelled", 5, 0, 200))
+            .await
+            .unwrap();
+
+        tick_after_exit(&manager).await;
+        manager.stop("cancelled").await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        manager.check_all_processes().await.unwrap();
+        let status = manager.get_process_info("cancelled").await.unwrap();
+        assert_eq!(status.state, ProcessState::Stopped);
+        assert_eq!(status.restarts, 0);
+
+        // Processes stopped by hand are never restarted
+        let mut config = create_test_config("stopped");
+        config.script = "sleep".to_string();
+        config.args = vec!["30".to_string()];
+        manager.start(config).await.unwrap();
+        manager.stop("stopped").await.unwrap();
+        manager.check_all_processes().await.unwrap();
+        let status = manager.get_process_info("stopped").await.unwrap();
+        assert_eq!(status.state, ProcessState::Stopped);
+        assert_eq!(status.restarts, 0);
+    }
+
+    #[tokio::test]
+    async fn test_exp_backoff_restart_delay() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let mut config = crashing_config("backoff", 5, 1000, 0);
+        config.exp_backoff_restart_delay = Some(200);
+        manager.start(config).await.unwrap();
+
+        tick_after_exit(&manager).await;
+        let status = manager.get_process_info("backoff").await.unwrap();
+        assert_eq!(status.backoff_delay, Some(200));
+        assert!(status.next_restart.is_some());
+        assert_eq!(status.restarts, 0);
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        manager.check_all_processes().await.unwrap();
+        assert_eq!(
+            manager.get_process_info("backoff").await.unwrap().restarts,
+            1
+        );
+
+        // The next crash doubles the delay
+        tick_after_exit(&manager).await;
+        let status = manager.get_process_info("backoff").await.unwrap();
+        assert_eq!(status.backoff_delay, Some(400));
+        assert_eq!(status.restarts, 1);
+    }
+
+    fn exiting_config(
+        name: &str,
+        command: &str,
+        mode: crate::restart::RestartMode,
+    ) -> ProcessConfig {
+        ProcessConfig::builder()
+            .name(name)
+            .script("sh")
+            .args(vec!["-c", command])
+            .restart_mode(mode)
+            .stop_exit_codes(vec![3])
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_restart_mode_and_stop_exit_codes() {
+        use crate::restart::RestartMode;
+
+        let (manager, _temp_dir) = create_test_manager().await;
+        let cases = [
+            // Batch jobs that finish successfully stay stopped
+            ("batch", "exit 0", RestartMode::OnFailure, 0),
+            ("failing", "exit 1", RestartMode::OnFailure, 1),
+            ("clean-stop", "exit 3", RestartMode::Always, 0),
+            ("abnormal-exit", "exit 1", RestartMode::OnAbnormal, 0),
+            ("killed", "kill -9 $$", RestartMode::OnAbnormal, 1),
+            ("never", "kill -9 $$", RestartMode::Never, 0),
+        ];
+        for (name, command, mode, _) in cases {
+            manager
+                .start(exiting_config(name, command, mode))
+                .await
+                .unwrap();
+        }
+
+        tick_after_exit(&manager).await;
+        for (name, _, mode, restarts) in cases {
+            let status = manager.get_process_info(name).await.unwrap();
+            assert_eq!(status.restarts, restarts, "{} ({})", name, mode);
+        }
+        manager.delete_all().await.unwrap();
+    }
+
+    async fn next_event(events: &mut mpsc::UnboundedReceiver<ExitEvent>) -> ExitEvent {
+        tokio::time::timeout(Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap()
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_exit_event_restarts_without_polling() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let mut events = manager.take_exit_events().unwrap();
+        assert!(manager.take_exit_events().is_none());
+        let mut config = crashing_config("crashing", 5, 0, 0);
+        config.script = "sh".to_string();
+        config.args = vec!["-c".to_string(), "exit 2".to_string()];
+        manager.start(config).await.unwrap();
+
+        let event = next_event(&mut events).await;
+        assert_eq!(event.exit.code, Some(2));
+        manager.handle_exit(event.clone()).await;
+        let status = manager.get_process_info("crashing").await.unwrap();
+        assert_eq!(status.restarts, 1);
+
+        // Replaying the event for the old child is a no-op
+        manager.handle_exit(event).await;
+        assert_eq!(
+            manager.get_process_info("crashing").await.unwrap().restarts,
+            1
+        );
+        manager.delete("crashing").await.unwrap();
+
+        // Delayed restarts fall due without a monitoring tick
+        let mut config = crashing_config("delayed-exit", 5, 0, 200);
+        config.script = "true".to_string();
+        manager.start(config).await.unwrap();
+        loop {
+            let event = next_event(&mut events).await;
+            let delayed = manager.get_process_info("delayed-exit").await.unwrap().id;
+            manager.handle_exit(event.clone()).await;
+            if event.process_id == delayed {
+                break;
+            }
+        }
+        let due = manager.next_restart_due().await.unwrap();
+        assert!(due > chrono::Utc::now());
+        manager.restart_due_processes().await;
+        assert_eq!(
+            manager
+                .get_process_info("delayed-exit")
+                .await
+                .unwrap()
+                .restarts,
+            0
+        );
+        tokio::time::sleep((due - chrono::Utc::now()).to_std().unwrap_or_default()).await;
+        manager.restart_due_processes().await;
+        assert_eq!(
+            manager
+                .get_process_info("delayed-exit")
+                .await
+                .unwrap()
+                .restarts,
+            1
+        );
+        manager.delete_all().await.unwrap();
+    }
+
+    /// Names of the journaled events of a process, oldest first
+    async fn event_names(manager: &ProcessManager, process: &str) -> Vec<&'static str> {
+        let filter = EventFilter {
+            process: Some(process.to_string()),
+            ..EventFilter::default()
+        };
+        manager
+            .events(&filter)
+            .await
+            .unwrap()
+            .iter()
+            .map(|event| event.kind.name())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_events_are_journaled() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let config = ProcessConfig::builder()
+            .name("journaled")
+            .script("sleep")
+            .args(vec!["30"])
+            .port(PortConfig::Single(18457))
+            .build()
+            .unwrap();
+        let process_id = manager.start(config).await.unwrap();
+        manager.restart("journaled").await.unwrap();
+        manager.stop("journaled").await.unwrap();
+        manager.delete("journaled").await.unwrap();
+
+        assert_eq!(
+            event_names(&manager, "journaled").await,
+            vec![
+                "created",
+                "port_allocated",
+                "started",
+                "restarted",
+                "stopped",
+                "port_released",
+                "deleted"
+            ]
+        );
+        let events = manager.events(&EventFilter::default()).await.unwrap();
+        assert!(events.iter().all(|event| event.process_id == process_id));
+        assert!(matches!(
+            events[3].kind,
+            EventKind::Restarted {
+                reason: RestartReason::Manual,
+                pid: Some(_)
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_crash_events_are_journaled() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        manager
+            .start(crashing_config("journaled-crash", 1, 1000, 0))
+            .await
+            .unwrap();
+        tick_after_exit(&manager).await;
+        tick_after_exit(&manager).await;
+
+        assert_eq!(
+            event_names(&manager, "journaled-crash").await,
+            vec![
+                "created",
+                "started",
+                "exited",
+                "restarted",
+                "exited",
+                "errored"
+            ]
+        );
+        let events = manager.events(&EventFilter::default()).await.unwrap();
+        assert_eq!(
+            events[2].kind,
+            EventKind::Exited {
+                exit: ProcessExit {
+                    code: Some(1),
+                    signal: None,
+                    unknown: false
+                }
+            }
+        );
+        assert!(matches!(
+            events[3].kind,
+            EventKind::Restarted {
+                reason: RestartReason::Crash,
+                pid: Some(_)
+            }
+        ));
+        manager.delete_all().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_signal_exits_found_by_status_checks_are_journaled() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        let mut config = ProcessConfig::builder()
+            .name("killed")
+            .script("sh")
+            .args(vec!["-c", "kill -KILL $$"])
+            .build()
+            .unwrap();
+        config.autorestart = false;
+        manager.start(config).await.unwrap();
+        tick_after_exit(&manager).await;
+
+        let killed = ProcessExit {
+            code: None,
+            signal: Some(ProcessSignal::Kill),
+            unknown: false,
+        };
+        let events = manager.events(&EventFilter::default()).await.unwrap();
+        assert!(events
+            .iter()
+            .any(|event| event.kind == EventKind::Exited { exit: killed }));
+        let crashes = manager.crashes(None).await.unwrap();
+        assert_eq!(crashes.len(), 1);
+        assert_eq!(crashes[0].exit, killed);
+        manager.delete_all().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_crashes_are_recorded() {
+        let (manager, _temp_dir) = create_test_manager().await;
+        for (name, code) in [("crash-capture", 3), ("clean-exit", 0)] {
+            let mut config = ProcessConfig::builder()
+                .name(name)
+                .script("sh")
+                .args(vec![
+                    "-c",
+                    &format!("echo starting; echo failed to bind >&2; exit {}", code),
+                ])
+                .build()
+                .unwrap();
+            config.autorestart = false;
+            manager.start(config).await.unwrap();
+        }
+        tick_after_exit(&manager).await;
+
+        let crashes = manager.crashes(None).await.unwrap();
+        assert_eq!(crashes.len(), 1);
+        let crash = manager.crash(&crashes[0].id).await.unwrap();
+        assert_eq!(crash.process_name, "crash-capture");
+        assert_eq!(crash.exit.code, Some(3));
+        assert_eq!(crash.stderr_tail, vec!["failed to bind".to_string()]);
+        assert_eq!(crash.stdout_tail, vec!["starting".to_string()]);
+        assert_eq!(crash.config.script, "sh");
+        assert!(manager
+            .crashes(Some("clean-exit"))
+            .await
+            .unwrap()
+            .is_empty());
+
+        // Crash records outlive the process
+        manager.delete_all().await.unwrap();
+        assert_eq!(
+            manager.crashes(Some("crash-capture")).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restart_history_is_persisted() {
+        let (manager, temp_dir) = create_test_manager().await;
+        manager
+            .start(crashing_config("history-crash", 1, 1000, 0))
+            .await
+            .unwrap();
+        tick_after_exit(&manager).await;
+
+        let info = manager.get_process_info("history-crash").await.unwrap();
+        assert_eq!(info.last_restart_reason, Some(RestartReason::Crash));
+        assert_eq!(info.restart_history.len(), 1);
+        assert_eq!(info.restart_history[0].exit_code, Some(1));
+        manager.stop("history-crash").await.unwrap();
+
+        let restored = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let restored_info = restored.get_process_info("history-crash").await.unwrap();
+        assert_eq!(restored_info.restart_history, info.restart_history);
+        assert_eq!(
+            restored_info.last_restart_reason,
+            Some(RestartReason::Crash)
+        );
+        assert_eq!(
+            restored_info.last_exit_code,
+            manager
+                .get_process_info("history-crash")
+                .await
+                .unwrap()
+                .last_exit_code
+        );
+        manager.delete_all().await.unwrap();
+    }
 }
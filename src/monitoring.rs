@@ -3,7 +3,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use sysinfo::{CpuExt, Pid, ProcessExt, System, SystemExt};
+use sysinfo::{CpuExt, Pid, PidExt, ProcessExt, System, SystemExt};
 use tracing::{debug, warn};
 
 /// System-wide monitoring data
@@ -222,6 +222,51 @@ impl Default for Monitor {
     }
 }
 
+/// Start time (seconds since the Unix epoch) of a live process
+///
+/// Returns `None` if there is no process with this PID or it has exited and is only
+/// waiting to be reaped. Comparing start times tells a process apart from a later
+/// one that reuses its PID.
+pub fn process_start_time(pid: u32) -> Option<u64> {
+    let pid = Pid::from(pid as usize);
+    let mut system = System::new();
+    system.refresh_process(pid);
+    system
+        .process(pid)
+        .filter(|process| process.status() != sysinfo::ProcessStatus::Zombie)
+        .map(|process| process.start_time())
+}
+
+/// Running descendants of the process with `pid`, with their start times
+///
+/// Children come before their own children. Zombies are left out.
+pub fn process_descendants(pid: u32) -> Vec<(u32, u64)> {
+    let mut system = System::new();
+    system.refresh_processes();
+
+    let mut children: HashMap<Pid, Vec<Pid>> = HashMap::new();
+    for (child_pid, process) in system.processes() {
+        if let Some(parent) = process.parent() {
+            children.entry(parent).or_default().push(*child_pid);
+        }
+    }
+
+    let mut descendants = Vec::new();
+    let mut queue = std::collections::VecDeque::from([Pid::from(pid as usize)]);
+    while let Some(parent) = queue.pop_front() {
+        for child_pid in children.remove(&parent).unwrap_or_default() {
+            let Some(process) = system.process(child_pid) else {
+                continue;
+            };
+            if process.status() != sysinfo::ProcessStatus::Zombie {
+                descendants.push((child_pid.as_u32(), process.start_time()));
+            }
+            queue.push_back(child_pid);
+        }
+    }
+    descendants
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -384,6 +429,46 @@ mod tests {
         assert!(!monitor.is_process_running(fake_pid).await);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_process_start_time() {
+        let start_time = process_start_time(process::id()).unwrap();
+        let now = Utc::now().timestamp() as u64;
+        assert!(start_time <= now && start_time > now - 3600);
+        assert_eq!(process_start_time(999999), None);
+
+        // An exited child that has not been reaped yet no longer counts
+        let mut child = process::Command::new("true").spawn().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(200));
+        assert_eq!(process_start_time(child.id()), None);
+        child.wait().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_process_descendants() {
+        let mut child = process::Command::new("sh")
+            .args(["-c", "sh -c 'sleep 30' & sleep 30 & wait"])
+            .spawn()
+            .unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(300));
+
+        let descendants = process_descendants(child.id());
+        assert_eq!(descendants.len(), 3);
+        assert!(process_descendants(descendants[2].0).is_empty());
+        for (pid, start_time) in &descendants {
+            assert_eq!(process_start_time(*pid), Some(*start_time));
+        }
+
+        for (pid, _) in descendants {
+            process::Command::new("kill")
+                .arg(pid.to_string())
+                .status()
+                .unwrap();
+        }
+        child.wait().unwrap();
+    }
+
     #[test]
     fn test_get_cached_metrics() {
         let mut monitor = Monitor::new();
@@ -2,18 +2,29 @@
 
 use crate::config::ProcessConfig;
 use crate::error::{Error, Result};
+use crate::health::HealthStatus;
+use crate::logs::{subscriber_socket, LogFiles, LogFormat, LogShim};
+use crate::restart::{RestartReason, RestartRecord, RestartState};
+use crate::signals::{ProcessSignal, SignalHandler};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
-use tokio::fs::File;
 use tokio::process::{Child, Command};
+use tokio::sync::{mpsc, oneshot, watch};
 use tracing::{debug, error, info, warn};
 use uuid::Uuid;
 
 /// Unique identifier for a process
 pub type ProcessId = Uuid;
 
+/// Time a process restored from disk gets to disappear after `SIGKILL`
+const KILL_WAIT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Interval for polling processes that pmdaemon cannot wait for
+const EXIT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
 /// Current state of a process
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -32,6 +43,13 @@ pub enum ProcessState {
     Restarting,
 }
 
+impl ProcessState {
+    /// Whether a process in this state is running
+    pub fn is_running(self) -> bool {
+        matches!(self, ProcessState::Online | ProcessState::Starting)
+    }
+}
+
 impl std::fmt::Display for ProcessState {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -45,6 +63,163 @@ impl std::fmt::Display for ProcessState {
     }
 }
 
+/// How a process exited
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessExit {
+    /// Exit code, when the process exited on its own
+    pub code: Option<i32>,
+    /// Signal that terminated the process (Unix only)
+    pub signal: Option<ProcessSignal>,
+    /// Whether the exit could not be observed, e.g. for processes restored from a
+    /// PID file, which are not children of this PMDaemon. `code` and `signal` are
+    /// then both `None`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unknown: bool,
+}
+
+impl ProcessExit {
+    /// Exit whose status could not be observed
+    pub fn unknown() -> Self {
+        Self {
+            code: None,
+            signal: None,
+            unknown: true,
+        }
+    }
+}
+
+impl From<std::process::ExitStatus> for ProcessExit {
+    fn from(status: std::process::ExitStatus) -> Self {
+        #[cfg(unix)]
+        let signal = {
+            use std::os::unix::process::ExitStatusExt;
+            status.signal().and_then(ProcessSignal::from_number)
+        };
+        #[cfg(not(unix))]
+        let signal = None;
+
+        Self {
+            code: status.code(),
+            signal,
+            unknown: false,
+        }
+    }
+}
+
+impl std::fmt::Display for ProcessExit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.code, self.signal) {
+            (Some(code), _) => write!(f, "exit code {}", code),
+            (None, Some(signal)) => write!(f, "killed by {}", signal),
+            (None, None) if self.unknown => write!(f, "unknown exit status"),
+            (None, None) => write!(f, "killed by an unknown signal"),
+        }
+    }
+}
+
+/// Sent by the waiter task of a child process as soon as the child exits
+#[derive(Debug, Clone)]
+pub struct ExitEvent {
+    /// Process the child belonged to
+    pub process_id: ProcessId,
+    /// System PID of the child
+    pub pid: u32,
+    /// How the child exited
+    pub exit: ProcessExit,
+    /// When the exit was noticed
+    pub at: DateTime<Utc>,
+}
+
+/// Handle to a spawned child process.
+///
+/// The child itself is owned by a dedicated waiter task that awaits its exit,
+/// publishes it through the handle and sends an [`ExitEvent`] right away. Dropping
+/// the handle leaves the child running.
+#[derive(Debug)]
+pub struct ChildHandle {
+    /// System PID of the child
+    pid: u32,
+    /// Exit status, set by the waiter task once the child has exited
+    exit: watch::Receiver<Option<ProcessExit>>,
+    /// Asks the waiter task to kill the child
+    kill: Option<oneshot::Sender<()>>,
+}
+
+impl ChildHandle {
+    /// Hand `child` to a new waiter task
+    fn spawn(
+        mut child: Child,
+        process_id: ProcessId,
+        events: Option<mpsc::UnboundedSender<ExitEvent>>,
+    ) -> Self {
+        let pid = child.id().unwrap_or_default();
+        let (exit_tx, exit) = watch::channel(None);
+        let (kill, mut kill_rx) = oneshot::channel::<()>();
+
+        tokio::spawn(async move {
+            let status = tokio::select! {
+                status = child.wait() => status,
+                Ok(()) = &mut kill_rx => {
+                    if let Err(e) = child.start_kill() {
+                        warn!("Failed to kill PID {}: {}", pid, e);
+                    }
+                    child.wait().await
+                }
+            };
+            let exit = match status {
+                Ok(status) => ProcessExit::from(status),
+                Err(e) => {
+                    error!("Failed to wait for PID {}: {}", pid, e);
+                    ProcessExit::unknown()
+                }
+            };
+            debug!("PID {} exited ({})", pid, exit);
+
+            exit_tx.send_replace(Some(exit));
+            if let Some(events) = events {
+                let _ = events.send(ExitEvent {
+                    process_id,
+                    pid,
+                    exit,
+                    at: Utc::now(),
+                });
+            }
+        });
+
+        Self {
+            pid,
+            exit,
+            kill: Some(kill),
+        }
+    }
+
+    /// System PID of the child
+    pub fn id(&self) -> u32 {
+        self.pid
+    }
+
+    /// How the child exited, or `None` while it is still running
+    pub fn exit(&self) -> Option<ProcessExit> {
+        *self.exit.borrow()
+    }
+
+    /// Wait for the child to exit
+    pub async fn wait(&mut self) -> ProcessExit {
+        match self.exit.wait_for(Option::is_some).await {
+            Ok(exit) => exit.unwrap_or_else(ProcessExit::unknown),
+            // The waiter task is gone without a result, e.g. on runtime shutdown
+            Err(_) => ProcessExit::unknown(),
+        }
+    }
+
+    /// Forcefully kill the child
+    pub fn kill(&mut self) {
+        if let Some(kill) = self.kill.take() {
+            let _ = kill.send(());
+        }
+    }
+}
+
 /// Process status information for external consumption.
 ///
 /// This struct represents the current status and metrics of a managed process.
@@ -73,6 +248,15 @@ impl std::fmt::Display for ProcessState {
 ///     namespace: "default".to_string(),
 ///     instance: None,
 ///     assigned_port: Some(3000),
+///     watching: false,
+///     health: None,
+///     unstable_restarts: 0,
+///     backoff_delay: None,
+///     next_restart: None,
+///     last_restart_reason: None,
+///     last_exit_code: None,
+///     last_exit_signal: None,
+///     restart_history: Vec::new(),
 /// };
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -115,6 +299,56 @@ pub struct ProcessStatus {
 
     /// Port assigned to this process (if any)
     pub assigned_port: Option<u16>,
+
+    /// Whether file changes are currently being watched for this process
+    #[serde(default)]
+    pub watching: bool,
+
+    /// Latest health check result (`None` when no health check is running)
+    #[serde(default)]
+    pub health: Option<HealthStatus>,
+
+    /// Consecutive automatic restarts after runs shorter than `min_uptime`
+    #[serde(default)]
+    pub unstable_restarts: u32,
+
+    /// Current exponential backoff restart delay in milliseconds (if enabled)
+    #[serde(default)]
+    pub backoff_delay: Option<u64>,
+
+    /// When the next automatic restart is due, if one is scheduled
+    #[serde(default)]
+    pub next_restart: Option<DateTime<Utc>>,
+
+    /// Why the process was last restarted
+    #[serde(default)]
+    pub last_restart_reason: Option<RestartReason>,
+
+    /// Exit code of the last run, kept across restarts
+    #[serde(default)]
+    pub last_exit_code: Option<i32>,
+
+    /// Signal that ended the last run, if it was killed
+    #[serde(default)]
+    pub last_exit_signal: Option<ProcessSignal>,
+
+    /// Most recent restarts, oldest first
+    #[serde(default)]
+    pub restart_history: Vec<RestartRecord>,
+}
+
+/// Outcome of stopping a process
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StopReport {
+    /// Exit code of the process (`None` when it was terminated by a signal or
+    /// could not be waited for)
+    pub exit_code: Option<i32>,
+
+    /// Whether the process had to be killed with SIGKILL
+    pub forced: bool,
+
+    /// PIDs of processes it started that outlived it and were killed
+    pub reaped: Vec<u32>,
 }
 
 /// Internal process representation for lifecycle management.
@@ -165,8 +399,11 @@ pub struct Process {
     /// Current process state (starting, online, stopped, etc.)
     pub state: ProcessState,
 
-    /// System process handle for direct control (if running)
-    pub child: Option<Child>,
+    /// Handle to the spawned child process (if running)
+    pub child: Option<ChildHandle>,
+
+    /// Where the waiter tasks of spawned children report exits
+    exit_events: Option<mpsc::UnboundedSender<ExitEvent>>,
 
     /// Timestamp when the process was last started
     pub started_at: Option<DateTime<Utc>>,
@@ -177,6 +414,13 @@ pub struct Process {
     /// Exit code from the last process termination
     pub exit_code: Option<i32>,
 
+    /// How the last run of the process ended, kept across restarts
+    pub last_exit: Option<ProcessExit>,
+
+    /// Most recent restarts, oldest first, at most
+    /// [`RESTART_HISTORY_LIMIT`](crate::RESTART_HISTORY_LIMIT) of them
+    pub restart_history: VecDeque<RestartRecord>,
+
     /// Error message if the process is in an error state
     pub error: Option<String>,
 
@@ -189,10 +433,38 @@ pub struct Process {
     /// Stored PID for processes restored from disk (when child handle is unavailable)
     pub stored_pid: Option<u32>,
 
+    /// Start time of `stored_pid` (seconds since the Unix epoch), used to make sure the
+    /// PID has not been reused by another process before signalling it
+    pub stored_pid_start_time: Option<u64>,
+
+    /// Whether the process manager is watching this process' files for changes
+    pub watching: bool,
+
+    /// Latest health check result, maintained by the process manager's health monitor
+    pub health: Option<HealthStatus>,
+
+    /// Automatic restart bookkeeping (unstable restarts, pending restart)
+    pub restart_state: RestartState,
+
     /// Real-time monitoring data (CPU, memory, etc.)
     pub monitoring: ProcessMonitoring,
 }
 
+/// Open a log file for appending, so that restarts and log rotation keep earlier output
+async fn open_log(path: &Path, stream: &str) -> Result<tokio::fs::File> {
+    crate::logs::open_append(path)
+        .await
+        .map_err(|e| Error::config(format!("Failed to create {} log file: {}", stream, e)))
+}
+
+/// Stdio for an output stream: its log file, or nowhere when there is none
+async fn log_stdio(file: Option<tokio::fs::File>) -> Stdio {
+    match file {
+        Some(file) => file.into_std().await.into(),
+        None => Stdio::null(),
+    }
+}
+
 /// Real-time monitoring data for a process.
 ///
 /// This struct contains the current resource usage metrics for a process,
@@ -251,13 +523,20 @@ impl Process {
             config,
             state: ProcessState::Stopped,
             child: None,
+            exit_events: None,
             started_at: None,
             restarts: 0,
             exit_code: None,
+            last_exit: None,
+            restart_history: VecDeque::new(),
             error: None,
             instance: None,
             assigned_port: None,
             stored_pid: None,
+            stored_pid_start_time: None,
+            watching: false,
+            health: None,
+            restart_state: RestartState::default(),
             monitoring: ProcessMonitoring::default(),
         }
     }
@@ -291,7 +570,12 @@ impl Process {
             id: self.id,
             name: self.config.name.clone(),
             state: self.state,
-            pid: self.child.as_ref().and_then(|c| c.id()).or(self.stored_pid),
+            pid: self
+                .child
+                .as_ref()
+                .filter(|child| child.exit().is_none())
+                .map(ChildHandle::id)
+                .or(self.stored_pid),
             uptime: self.started_at,
             restarts: self.restarts,
             cpu_usage: self.monitoring.cpu_usage,
@@ -301,12 +585,35 @@ impl Process {
             namespace: self.config.namespace.clone(),
             instance: self.instance,
             assigned_port: self.assigned_port,
+            watching: self.watching,
+            health: self.health.clone(),
+            unstable_restarts: self.restart_state.unstable_restarts,
+            backoff_delay: self.restart_state.backoff_delay,
+            next_restart: self.restart_state.next_restart,
+            last_restart_reason: self.restart_history.back().map(|record| record.reason),
+            last_exit_code: self.last_exit.and_then(|exit| exit.code),
+            last_exit_signal: self.last_exit.and_then(|exit| exit.signal),
+            restart_history: self.restart_history.iter().cloned().collect(),
         }
     }
 
+    /// Add a restart to the restart history, together with how the previous run
+    /// ended, dropping the oldest entry once the history is full
+    pub fn record_restart(&mut self, reason: RestartReason) {
+        if self.restart_history.len() >= crate::RESTART_HISTORY_LIMIT {
+            self.restart_history.pop_front();
+        }
+        self.restart_history.push_back(RestartRecord {
+            at: Utc::now(),
+            reason,
+            exit_code: self.last_exit.and_then(|exit| exit.code),
+            exit_signal: self.last_exit.and_then(|exit| exit.signal),
+        });
+    }
+
     /// Check if process is running
     pub fn is_running(&self) -> bool {
-        matches!(self.state, ProcessState::Online | ProcessState::Starting)
+        self.state.is_running()
     }
 
     /// Set process state
@@ -326,7 +633,8 @@ impl Process {
     ///
     /// This is the core method for starting a process. It spawns the configured
     /// command with all specified arguments, environment variables, and working
-    /// directory. Output can be redirected to log files or captured for processing.
+    /// directory. Output can be redirected to log files; output without a log file
+    /// is discarded.
     ///
     /// # Arguments
     ///
@@ -338,7 +646,7 @@ impl Process {
     /// 1. Validates the process is not already running
     /// 2. Sets state to `Starting`
     /// 3. Configures the command with args, environment, and working directory
-    /// 4. Sets up stdio redirection (files or pipes)
+    /// 4. Sets up stdio redirection to the log files
     /// 5. Spawns the process
     /// 6. Sets state to `Online` on success or `Errored` on failure
     ///
@@ -370,17 +678,57 @@ impl Process {
     /// Returns an error if:
     /// - Process is already running
     /// - Command/script is not found
-    /// - Log files cannot be created
+    /// - Log files cannot be opened
     /// - Process spawn fails for any reason
     pub async fn start_with_logs(
         &mut self,
         out_log: Option<PathBuf>,
         err_log: Option<PathBuf>,
+    ) -> Result<()> {
+        self.spawn(out_log, err_log, None).await
+    }
+
+    /// Start the process with its output going to `log_files`.
+    ///
+    /// Like [`start_with_logs`](Self::start_with_logs), except that when a combined log
+    /// is given, stdout and stderr are piped to a [`LogShim`] that copies them line by
+    /// line to their own log and to the combined log, with the timestamps of
+    /// `log_date_format` and in `log_format`. The shim outlives the caller and exits
+    /// when the process closes its output. If no shim can be started (see
+    /// [`LogShim::program`]), the process writes to its own logs directly and the
+    /// combined log stays empty.
+    pub async fn start_with_log_files(&mut self, log_files: &LogFiles) -> Result<()> {
+        self.spawn(
+            Some(log_files.out.clone()),
+            Some(log_files.err.clone()),
+            log_files.combined.clone(),
+        )
+        .await
+    }
+
+    async fn spawn(
+        &mut self,
+        out_log: Option<PathBuf>,
+        err_log: Option<PathBuf>,
+        combined_log: Option<PathBuf>,
     ) -> Result<()> {
         if self.is_running() {
             return Err(Error::ProcessAlreadyRunning(self.config.name.clone()));
         }
 
+        // Resolve the configured user/group before spawning so that
+        // misconfigurations surface as a clear error instead of a failed exec
+        let credentials =
+            match resolve_credentials(self.config.user.as_deref(), self.config.group.as_deref()) {
+                Ok(credentials) => credentials,
+                Err(e) => {
+                    error!("Cannot start process {}: {}", self.config.name, e);
+                    self.set_state(ProcessState::Errored);
+                    self.error = Some(e.to_string());
+                    return Err(e);
+                }
+            };
+
         info!("Starting process: {}", self.config.name);
         self.set_state(ProcessState::Starting);
 
@@ -404,31 +752,34 @@ impl Process {
             }
         }
 
-        // Configure stdio with log file redirection
-        if let Some(out_path) = out_log {
-            // Create/open stdout log file
-            let stdout_file = File::create(&out_path)
-                .await
-                .map_err(|e| Error::config(format!("Failed to create stdout log file: {}", e)))?;
-            cmd.stdout(stdout_file.into_std().await);
-            debug!("Redirecting stdout to: {:?}", out_path);
-        } else {
-            cmd.stdout(Stdio::piped());
-        }
-
-        if let Some(err_path) = err_log {
-            // Create/open stderr log file
-            let stderr_file = File::create(&err_path)
-                .await
-                .map_err(|e| Error::config(format!("Failed to create stderr log file: {}", e)))?;
-            cmd.stderr(stderr_file.into_std().await);
-            debug!("Redirecting stderr to: {:?}", err_path);
-        } else {
-            cmd.stderr(Stdio::piped());
-        }
+        // Configure stdio with log file redirection, through a log shim when the
+        // output also goes to a combined log
+        let (stdout, stderr) = self
+            .output_stdio(
+                out_log.as_deref(),
+                err_log.as_deref(),
+                combined_log.as_deref(),
+            )
+            .await?;
+        cmd.stdout(stdout);
+        cmd.stderr(stderr);
 
         cmd.stdin(Stdio::null());
 
+        // Drop privileges to the configured user/group in the child
+        if let Some(credentials) = &credentials {
+            for (key, value) in credentials.login_env() {
+                if !self.config.env.contains_key(key) {
+                    cmd.env(key, value);
+                }
+            }
+            credentials.apply(&mut cmd);
+            debug!(
+                "Process {} will run as uid={} gid={} groups={:?}",
+                self.config.name, credentials.uid, credentials.gid, credentials.groups
+            );
+        }
+
         // Configure process to run independently (detached from parent)
         #[cfg(unix)]
         {
@@ -451,9 +802,14 @@ impl Process {
                     self.config.name,
                     child.id().unwrap_or(0)
                 );
-                // Store the child process handle
-                self.child = Some(child);
-                self.set_state(ProcessState::Online);
+                // Hand the child to its waiter task
+                self.child = Some(ChildHandle::spawn(child, self.id, self.exit_events.clone()));
+                // With `wait_ready` the process is online once its health check passes
+                if self.config.ready_check().is_some() {
+                    self.set_state(ProcessState::Starting);
+                } else {
+                    self.set_state(ProcessState::Online);
+                }
                 self.error = None;
 
                 // Note: Process is now detached and will continue running independently
@@ -476,133 +832,399 @@ impl Process {
     }
 
     /// Stop the process gracefully
-    pub async fn stop(&mut self) -> Result<()> {
+    ///
+    /// The stop signals go to the process' whole process group, so processes it
+    /// started are stopped along with it. Descendants still running once the grace
+    /// period is over are killed and listed in the returned [`StopReport`].
+    pub async fn stop(&mut self) -> Result<StopReport> {
         if !self.is_running() {
-            return Ok(());
+            return Ok(StopReport::default());
         }
 
         info!("Stopping process: {}", self.config.name);
         self.set_state(ProcessState::Stopping);
 
+        let mut report = StopReport::default();
         if let Some(mut child) = self.child.take() {
-            // Try graceful shutdown first
-            #[cfg(unix)]
-            {
-                use nix::sys::signal::{self, Signal};
-                use nix::unistd::Pid;
-
-                if let Some(pid) = child.id() {
-                    let pid = Pid::from_raw(pid as i32);
-                    if let Err(e) = signal::kill(pid, Signal::SIGTERM) {
-                        warn!(
-                            "Failed to send SIGTERM to process {}: {}",
-                            self.config.name, e
-                        );
-                    } else {
-                        debug!("Sent SIGTERM to process {}", self.config.name);
+            let pid = child.id();
+            let mut descendants = Vec::new();
+            let mut deadline = tokio::time::Instant::now();
+            let mut exit = child.exit();
+            if exit.is_none() {
+                for (signal, timeout) in self.config.stop_sequence() {
+                    add_descendants(&mut descendants, pid);
+                    self.send_stop_signal(pid, signal);
+                    deadline = tokio::time::Instant::now() + timeout;
+                    match tokio::time::timeout_at(deadline, child.wait()).await {
+                        Ok(status) => {
+                            exit = Some(status);
+                            break;
+                        }
+                        Err(_) => debug!(
+                            "Process {} still running {:?} after {}",
+                            self.config.name, timeout, signal
+                        ),
                     }
                 }
             }
 
-            // Wait for graceful shutdown with timeout
-            let timeout = tokio::time::Duration::from_secs(10);
-            match tokio::time::timeout(timeout, child.wait()).await {
-                Ok(Ok(exit_status)) => {
+            match exit {
+                Some(exit) => {
                     info!(
                         "Process {} stopped gracefully with exit code: {:?}",
-                        self.config.name,
-                        exit_status.code()
+                        self.config.name, exit.code
                     );
-                    self.exit_code = exit_status.code();
+                    self.exit_code = exit.code;
+                    self.last_exit = Some(exit);
                 }
-                Ok(Err(e)) => {
-                    error!(
-                        "Error waiting for process {} to stop: {}",
-                        self.config.name, e
-                    );
-                    return Err(Error::ProcessStopFailed {
-                        name: self.config.name.clone(),
-                        reason: e.to_string(),
-                    });
-                }
-                Err(_) => {
+                None => {
                     warn!(
                         "Process {} did not stop gracefully, killing forcefully",
                         self.config.name
                     );
-                    if let Err(e) = child.kill().await {
-                        error!("Failed to kill process {}: {}", self.config.name, e);
-                        return Err(Error::ProcessStopFailed {
-                            name: self.config.name.clone(),
-                            reason: e.to_string(),
-                        });
-                    }
-                    if let Ok(exit_status) = child.wait().await {
-                        self.exit_code = exit_status.code();
-                    }
+                    report.forced = true;
+                    add_descendants(&mut descendants, pid);
+                    self.send_stop_signal(pid, ProcessSignal::Kill);
+                    child.kill();
+                    let exit = child.wait().await;
+                    self.exit_code = exit.code;
+                    self.last_exit = Some(exit);
                 }
             }
+            report.reaped = self.reap_descendants(descendants, deadline).await;
+        } else if let Some(pid) = self.stored_pid {
+            report = self.stop_stored_pid(pid).await?;
         }
 
+        report.exit_code = self.exit_code;
         self.set_state(ProcessState::Stopped);
+        self.set_stored_pid(None);
         self.started_at = None;
+        Ok(report)
+    }
+
+    /// Stop a process restored from disk, which can be signalled but not waited for
+    async fn stop_stored_pid(&mut self, pid: u32) -> Result<StopReport> {
+        let mut report = StopReport::default();
+        let Some(start_time) = self.stored_pid_start_time() else {
+            info!(
+                "Process {} (PID {}) is no longer running",
+                self.config.name, pid
+            );
+            return Ok(report);
+        };
+        self.exit_code = None;
+        self.last_exit = Some(ProcessExit::unknown());
+
+        let mut descendants = Vec::new();
+        let mut deadline = tokio::time::Instant::now();
+        for (signal, timeout) in self.config.stop_sequence() {
+            add_descendants(&mut descendants, pid);
+            self.send_stop_signal(pid, signal);
+            deadline = tokio::time::Instant::now() + timeout;
+            if wait_for_exit(pid, start_time, timeout).await {
+                info!("Process {} stopped gracefully", self.config.name);
+                report.reaped = self.reap_descendants(descendants, deadline).await;
+                return Ok(report);
+            }
+        }
+
+        warn!(
+            "Process {} did not stop gracefully, killing forcefully",
+            self.config.name
+        );
+        report.forced = true;
+        add_descendants(&mut descendants, pid);
+        self.send_stop_signal(pid, ProcessSignal::Kill);
+        if !wait_for_exit(pid, start_time, KILL_WAIT_TIMEOUT).await {
+            return Err(Error::ProcessStopFailed {
+                name: self.config.name.clone(),
+                reason: format!("PID {} is still running after being killed", pid),
+            });
+        }
+        report.reaped = self.reap_descendants(descendants, deadline).await;
+        Ok(report)
+    }
+
+    /// Send one signal of the stop sequence, logging rather than failing on errors
+    ///
+    /// The process may already be exiting, so a failed signal is not fatal; the
+    /// sequence continues and ends with a forced kill.
+    fn send_stop_signal(&self, pid: u32, signal: ProcessSignal) {
+        let handler = SignalHandler::new();
+        let result = if leads_process_group(pid) {
+            handler.send_signal_to_group(pid, signal)
+        } else {
+            handler.send_signal(pid, signal)
+        };
+        match result {
+            Ok(()) => debug!(
+                "Sent {} to process {} (PID {})",
+                signal, self.config.name, pid
+            ),
+            Err(e) => warn!(
+                "Failed to send {} to process {} (PID {}): {}",
+                signal, self.config.name, pid, e
+            ),
+        }
+    }
+
+    /// Kill descendants of a stopped process that are still running at `deadline`
+    ///
+    /// Returns the PIDs that had to be killed.
+    async fn reap_descendants(
+        &self,
+        descendants: Vec<(u32, u64)>,
+        deadline: tokio::time::Instant,
+    ) -> Vec<u32> {
+        let alive = |descendants: &[(u32, u64)]| -> Vec<(u32, u64)> {
+            descendants
+                .iter()
+                .copied()
+                .filter(|(pid, start_time)| {
+                    crate::monitoring::process_start_time(*pid) == Some(*start_time)
+                })
+                .collect()
+        };
+
+        let mut survivors = alive(&descendants);
+        while !survivors.is_empty() && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+            survivors = alive(&survivors);
+        }
+        if survivors.is_empty() {
+            return Vec::new();
+        }
+
+        let pids: Vec<u32> = survivors.iter().map(|(pid, _)| *pid).collect();
+        warn!(
+            "Killing {} process(es) left behind by {}: {:?}",
+            pids.len(),
+            self.config.name,
+            pids
+        );
+        let handler = SignalHandler::new();
+        for (pid, start_time) in survivors {
+            if let Err(e) = handler.send_signal(pid, ProcessSignal::Kill) {
+                warn!("Failed to kill leftover process {}: {}", pid, e);
+            } else if !wait_for_exit(pid, start_time, KILL_WAIT_TIMEOUT).await {
+                warn!("Leftover process {} is still running after SIGKILL", pid);
+            }
+        }
+        pids
+    }
+
+    /// Start time of the stored PID if it is alive and still belongs to this process
+    ///
+    /// A PID whose start time differs from the one recorded with it has been reused
+    /// by an unrelated process. PIDs recorded by older versions have no start time
+    /// and are trusted as long as they are alive.
+    fn stored_pid_start_time(&self) -> Option<u64> {
+        let pid = self.stored_pid?;
+        let start_time = crate::monitoring::process_start_time(pid)?;
+        match self.stored_pid_start_time {
+            Some(expected) if start_time.abs_diff(expected) > 1 => {
+                warn!(
+                    "PID {} of process {} now belongs to another process, ignoring it",
+                    pid, self.config.name
+                );
+                None
+            }
+            _ => Some(start_time),
+        }
+    }
+
+    /// Whether the stored PID is alive and still belongs to this process
+    pub fn owns_stored_pid(&self) -> bool {
+        self.stored_pid_start_time().is_some()
+    }
+
+    /// Send a signal to the running process
+    ///
+    /// Only the process itself is signalled, not the processes it started.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process is not running or the signal cannot be sent.
+    pub fn send_signal(&self, signal: ProcessSignal) -> Result<()> {
+        let pid = match &self.child {
+            Some(child) => Some(child.id()).filter(|_| child.exit().is_none()),
+            None => self
+                .stored_pid
+                .filter(|_| self.is_running() && self.owns_stored_pid()),
+        };
+        let Some(pid) = pid else {
+            return Err(Error::process(format!(
+                "Process {} is not running",
+                self.config.name
+            )));
+        };
+        SignalHandler::new().send_signal(pid, signal)?;
+        info!(
+            "Sent {} to process {} (PID {})",
+            signal, self.config.name, pid
+        );
         Ok(())
     }
 
     /// Restart the process
     pub async fn restart(&mut self) -> Result<()> {
+        self.restart_into(None).await
+    }
+
+    /// Restart the process with the output of the new run going to `log_files`
+    ///
+    /// See [`start_with_log_files`](Self::start_with_log_files).
+    pub async fn restart_with_log_files(&mut self, log_files: &LogFiles) -> Result<()> {
+        self.restart_into(Some(log_files)).await
+    }
+
+    async fn restart_into(&mut self, log_files: Option<&LogFiles>) -> Result<()> {
         info!("Restarting process: {}", self.config.name);
-        self.set_state(ProcessState::Restarting);
 
         // Stop if running
         if self.is_running() {
             self.stop().await?;
         }
+        self.set_state(ProcessState::Restarting);
 
         // Increment restart counter
         self.restarts += 1;
 
         // Start again
-        self.start().await
+        match log_files {
+            Some(log_files) => self.start_with_log_files(log_files).await,
+            None => self.start().await,
+        }
     }
 
     /// Check if the process is still alive
+    ///
+    /// Processes restored from disk are checked through their stored PID. As they are
+    /// not our children, their exit code is unknown.
     pub async fn check_status(&mut self) -> Result<bool> {
-        if let Some(child) = &mut self.child {
-            match child.try_wait() {
-                Ok(Some(exit_status)) => {
-                    // Process has exited
-                    info!(
-                        "Process {} exited with status: {:?}",
-                        self.config.name,
-                        exit_status.code()
-                    );
-                    self.exit_code = exit_status.code();
-                    self.set_state(ProcessState::Stopped);
-                    self.child = None;
-                    self.started_at = None;
-                    Ok(false)
-                }
-                Ok(None) => {
-                    // Process is still running
-                    Ok(true)
-                }
-                Err(e) => {
-                    error!("Error checking process {} status: {}", self.config.name, e);
-                    self.set_state(ProcessState::Errored);
-                    self.error = Some(format!("Status check failed: {}", e));
-                    self.child = None;
-                    Ok(false)
-                }
+        if let Some(child) = &self.child {
+            let Some(exit) = child.exit() else {
+                return Ok(true);
+            };
+            info!("Process {} exited ({})", self.config.name, exit);
+            self.exit_code = exit.code;
+            self.last_exit = Some(exit);
+            self.set_state(ProcessState::Stopped);
+            self.child = None;
+            self.started_at = None;
+            Ok(false)
+        } else if self.is_running() && self.stored_pid.is_some() {
+            if self.owns_stored_pid() {
+                return Ok(true);
             }
+            info!(
+                "Process {} (PID {:?}) is no longer running",
+                self.config.name, self.stored_pid
+            );
+            // Not a child of ours, so how it exited cannot be known
+            self.exit_code = None;
+            self.last_exit = Some(ProcessExit::unknown());
+            self.set_state(ProcessState::Stopped);
+            self.set_stored_pid(None);
+            self.started_at = None;
+            Ok(false)
         } else {
             Ok(false)
         }
     }
 
     /// Get the process PID if running
+    ///
+    /// Falls back to the stored PID for running processes restored from disk.
     pub fn pid(&self) -> Option<u32> {
-        self.child.as_ref().and_then(|c| c.id())
+        self.child
+            .as_ref()
+            .filter(|child| child.exit().is_none())
+            .map(ChildHandle::id)
+            .or(self.stored_pid.filter(|_| self.is_running()))
+    }
+
+    /// Report exits of children spawned from now on to `events`
+    pub fn notify_exits(&mut self, events: mpsc::UnboundedSender<ExitEvent>) {
+        self.exit_events = Some(events);
+    }
+
+    /// Stdio for the output streams: pipes to a log shim when the output also goes to
+    /// a combined log, otherwise the log files, or nowhere for streams without one
+    async fn output_stdio(
+        &self,
+        out_log: Option<&Path>,
+        err_log: Option<&Path>,
+        combined_log: Option<&Path>,
+    ) -> Result<(Stdio, Stdio)> {
+        // Open every log up front, so that logs that cannot be written fail the start
+        let out = match out_log {
+            Some(path) => Some(open_log(path, "stdout").await?),
+            None => None,
+        };
+        let err = match err_log {
+            Some(path) => Some(open_log(path, "stderr").await?),
+            None => None,
+        };
+
+        if let Some(combined_log) = combined_log {
+            open_log(combined_log, "combined").await?;
+            let shim = LogShim {
+                process_id: self.id,
+                process_name: self.config.name.clone(),
+                instance: self.instance,
+                date_format: self.config.log_date_format.clone(),
+                format: self.config.log_format,
+                out: out_log.map(Path::to_path_buf),
+                err: err_log.map(Path::to_path_buf),
+                combined: Some(combined_log.to_path_buf()),
+                socket: Some(subscriber_socket(combined_log)),
+            };
+            if let Some(stdio) = self.start_log_shim(&shim, LogShim::program())? {
+                return Ok(stdio);
+            }
+        }
+
+        debug!("Redirecting output to: {:?} and {:?}", out_log, err_log);
+        Ok((log_stdio(out).await, log_stdio(err).await))
+    }
+
+    /// Start `shim` with `program` and return the stdio of the streams it pumps, or
+    /// `None` when the output has to go to the log files directly instead
+    fn start_log_shim(
+        &self,
+        shim: &LogShim,
+        program: Option<PathBuf>,
+    ) -> Result<Option<(Stdio, Stdio)>> {
+        let started = match program {
+            Some(program) => shim.spawn(&program).map(|stdio| (program, stdio)),
+            None => Err(Error::config(format!(
+                "no pmdaemon executable found to run it (set {})",
+                crate::LOG_SHIM_ENV
+            ))),
+        };
+        match started {
+            Ok((program, stdio)) => {
+                debug!(
+                    "Pumping output through {:?} to: {:?}, {:?} and {:?}",
+                    program, shim.out, shim.err, shim.combined
+                );
+                Ok(Some(stdio))
+            }
+            // Raw output in a JSON log would break the consumers it is written for
+            Err(e) if shim.format == LogFormat::Json => Err(Error::config(format!(
+                "Cannot write JSON logs for {} without its log shim: {}",
+                self.config.name, e
+            ))),
+            Err(e) => {
+                warn!(
+                    "Failed to start the log shim of {}, writing its output without timestamps or the combined log: {}",
+                    self.config.name, e
+                );
+                Ok(None)
+            }
+        }
     }
 
     /// Set the assigned port for this process
@@ -616,7 +1238,12 @@ impl Process {
     }
 
     /// Set the stored PID for this process (used when restoring from disk)
+    ///
+    /// Records the start time of the PID's current process along with it.
     pub fn set_stored_pid(&mut self, pid: Option<u32>) {
+        if pid != self.stored_pid || self.stored_pid_start_time.is_none() {
+            self.stored_pid_start_time = pid.and_then(crate::monitoring::process_start_time);
+        }
         self.stored_pid = pid;
     }
 
@@ -639,11 +1266,246 @@ impl Process {
     }
 
     /// Check if process should be auto-restarted
+    ///
+    /// Only consecutive unstable restarts (runs shorter than `min_uptime`) count
+    /// towards `max_restarts`.
     pub fn should_auto_restart(&self) -> bool {
-        self.config.autorestart
-            && self.state == ProcessState::Stopped
-            && self.config.max_restarts > self.restarts
+        self.state == ProcessState::Stopped
+            && self
+                .config
+                .should_restart_after(self.last_exit.unwrap_or_else(ProcessExit::unknown))
+            && self.config.max_restarts > self.restart_state.unstable_restarts
+    }
+}
+
+/// Add running descendants of `pid` that are not known yet to `descendants`
+fn add_descendants(descendants: &mut Vec<(u32, u64)>, pid: u32) {
+    for descendant in crate::monitoring::process_descendants(pid) {
+        if !descendants.contains(&descendant) {
+            descendants.push(descendant);
+        }
+    }
+}
+
+/// Whether `pid` leads its own process group, as processes spawned by pmdaemon do
+#[cfg(unix)]
+fn leads_process_group(pid: u32) -> bool {
+    let pid = nix::unistd::Pid::from_raw(pid as i32);
+    nix::unistd::getpgid(Some(pid)) == Ok(pid)
+}
+
+/// Whether `pid` leads its own process group (process trees on other platforms)
+#[cfg(not(unix))]
+fn leads_process_group(_pid: u32) -> bool {
+    true
+}
+
+/// Wait until the process with `pid` that started at `start_time` has exited
+///
+/// Returns `false` if it is still running after `timeout`.
+async fn wait_for_exit(pid: u32, start_time: u64, timeout: std::time::Duration) -> bool {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        match crate::monitoring::process_start_time(pid) {
+            Some(current) if current == start_time => {}
+            _ => return true,
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(EXIT_POLL_INTERVAL).await;
+    }
+}
+
+/// Identity a process is spawned with when `user` and/or `group` are configured
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SpawnCredentials {
+    /// Target user ID
+    uid: u32,
+    /// Target primary group ID
+    gid: u32,
+    /// Supplementary group IDs
+    groups: Vec<u32>,
+    /// Target user name and home directory (when a user was configured)
+    user: Option<(String, PathBuf)>,
+}
+
+impl SpawnCredentials {
+    /// Login environment variables for the target user
+    fn login_env(&self) -> Vec<(&'static str, String)> {
+        match &self.user {
+            Some((name, home)) => vec![
+                ("USER", name.clone()),
+                ("LOGNAME", name.clone()),
+                ("HOME", home.to_string_lossy().into_owned()),
+            ],
+            None => Vec::new(),
+        }
+    }
+
+    /// Switch groups and user in the child between fork and exec
+    #[cfg(unix)]
+    fn apply(&self, cmd: &mut Command) {
+        let uid = self.uid as libc::uid_t;
+        let gid = self.gid as libc::gid_t;
+        let groups: Vec<libc::gid_t> = self.groups.iter().map(|g| *g as libc::gid_t).collect();
+
+        // SAFETY: the closure runs in the forked child before exec and only performs
+        // async-signal-safe system calls on data allocated before the fork.
+        // Groups must be set before the user ID, while we still have the privilege to.
+        unsafe {
+            cmd.pre_exec(move || {
+                if libc::setgroups(groups.len() as _, groups.as_ptr()) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                if libc::setgid(gid) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                if libc::setuid(uid) != 0 {
+                    return Err(std::io::Error::last_os_error());
+                }
+                Ok(())
+            });
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn apply(&self, _cmd: &mut Command) {}
+}
+
+/// Resolve configured user/group names (or numeric IDs) to spawn credentials.
+///
+/// Returns `Ok(None)` when no user or group is configured, or when the requested
+/// identity is the one pmdaemon already runs as.
+///
+/// # Errors
+///
+/// Returns [`Error::PermissionDenied`] if the user or group does not exist, or if
+/// pmdaemon is not running as root but a different identity was requested.
+#[cfg(unix)]
+fn resolve_credentials(
+    user: Option<&str>,
+    group: Option<&str>,
+) -> Result<Option<SpawnCredentials>> {
+    use nix::unistd::{getegid, geteuid, Gid, Group, Uid, User};
+
+    if user.is_none() && group.is_none() {
+        return Ok(None);
+    }
+
+    let target_user = match user {
+        Some(name) => {
+            let lookup = match name.parse::<u32>() {
+                Ok(uid) => User::from_uid(Uid::from_raw(uid)),
+                Err(_) => User::from_name(name),
+            };
+            let found = lookup.map_err(|e| {
+                Error::permission_denied(format!("failed to look up user '{}': {}", name, e))
+            })?;
+            Some(found.ok_or_else(|| {
+                Error::permission_denied(format!("user '{}' does not exist", name))
+            })?)
+        }
+        None => None,
+    };
+
+    let gid = match group {
+        Some(name) => {
+            let lookup = match name.parse::<u32>() {
+                Ok(gid) => Group::from_gid(Gid::from_raw(gid)),
+                Err(_) => Group::from_name(name),
+            };
+            let found = lookup.map_err(|e| {
+                Error::permission_denied(format!("failed to look up group '{}': {}", name, e))
+            })?;
+            found
+                .ok_or_else(|| {
+                    Error::permission_denied(format!("group '{}' does not exist", name))
+                })?
+                .gid
+        }
+        None => target_user.as_ref().map(|u| u.gid).unwrap_or_else(getegid),
+    };
+    let uid = target_user.as_ref().map(|u| u.uid).unwrap_or_else(geteuid);
+
+    if !geteuid().is_root() {
+        if uid == geteuid() && gid == getegid() {
+            // Already running as the requested identity
+            return Ok(None);
+        }
+        return Err(Error::permission_denied(format!(
+            "pmdaemon must run as root to start processes as {} (running as uid {})",
+            match (user, group) {
+                (Some(user), Some(group)) => format!("user '{}' and group '{}'", user, group),
+                (Some(user), None) => format!("user '{}'", user),
+                _ => format!("group '{}'", group.unwrap_or_default()),
+            },
+            geteuid()
+        )));
+    }
+
+    let groups = match &target_user {
+        Some(user) => supplementary_groups(user, gid)?,
+        None => vec![gid],
+    };
+
+    Ok(Some(SpawnCredentials {
+        uid: uid.as_raw(),
+        gid: gid.as_raw(),
+        groups: groups.into_iter().map(|g| g.as_raw()).collect(),
+        user: target_user.map(|u| (u.name, u.dir)),
+    }))
+}
+
+/// Supplementary groups of `user`, always including the primary group `gid`
+#[cfg(unix)]
+fn supplementary_groups(
+    user: &nix::unistd::User,
+    gid: nix::unistd::Gid,
+) -> Result<Vec<nix::unistd::Gid>> {
+    #[cfg(not(any(
+        target_os = "aix",
+        target_os = "illumos",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "redox"
+    )))]
+    {
+        let name = std::ffi::CString::new(user.name.as_str())
+            .map_err(|_| Error::permission_denied(format!("invalid user name '{}'", user.name)))?;
+        nix::unistd::getgrouplist(&name, gid).map_err(|e| {
+            Error::permission_denied(format!(
+                "failed to look up groups of user '{}': {}",
+                user.name, e
+            ))
+        })
+    }
+
+    #[cfg(any(
+        target_os = "aix",
+        target_os = "illumos",
+        target_os = "ios",
+        target_os = "macos",
+        target_os = "redox"
+    ))]
+    {
+        let _ = user;
+        Ok(vec![gid])
+    }
+}
+
+/// Running processes as another user is only supported on Unix
+#[cfg(not(unix))]
+fn resolve_credentials(
+    user: Option<&str>,
+    group: Option<&str>,
+) -> Result<Option<SpawnCredentials>> {
+    if user.is_none() && group.is_none() {
+        return Ok(None);
     }
+    Err(Error::permission_denied(
+        "running processes as another user or group is not supported on this platform",
+    ))
 }
 
 #[cfg(test)]
@@ -652,6 +1514,23 @@ mod tests {
     use crate::config::ProcessConfig;
     use pretty_assertions::assert_eq;
 
+    /// Start `process` with its stdout logged in `dir` and wait for its first line
+    #[cfg(unix)]
+    async fn start_and_wait_for_output(process: &mut Process, dir: &Path) {
+        let out_log = dir.join(format!("{}-out.log", process.id));
+        process
+            .start_with_logs(Some(out_log.clone()), None)
+            .await
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while std::fs::metadata(&out_log).map_or(0, |m| m.len()) == 0 {
+                tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
     fn create_test_config() -> ProcessConfig {
         ProcessConfig::builder()
             .name("test-process")
@@ -705,6 +1584,7 @@ mod tests {
         assert!(process.error.is_none());
         assert!(process.instance.is_none());
         assert!(process.assigned_port.is_none());
+        assert!(!process.watching);
     }
 
     #[test]
@@ -728,6 +1608,7 @@ mod tests {
         assert_eq!(status.assigned_port, Some(8080));
         assert_eq!(status.instance, Some(1));
         assert_eq!(status.namespace, "default");
+        assert!(!status.watching);
     }
 
     #[test]
@@ -846,12 +1727,16 @@ mod tests {
         // Should auto-restart when stopped and under restart limit
         assert!(process.should_auto_restart());
 
-        // Should not auto-restart when at restart limit
+        // Manual restarts do not count towards the limit
         process.restarts = 5;
+        assert!(process.should_auto_restart());
+
+        // Should not auto-restart when at the unstable restart limit
+        process.restart_state.unstable_restarts = 5;
         assert!(!process.should_auto_restart());
 
         // Should not auto-restart when autorestart is disabled
-        process.restarts = 0;
+        process.restart_state.unstable_restarts = 0;
         process.config.autorestart = false;
         assert!(!process.should_auto_restart());
 
@@ -916,6 +1801,164 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stop_stored_pid() {
+        let mut sleeper = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let mut process = Process::new(create_test_config());
+        process.set_state(ProcessState::Online);
+        process.set_stored_pid(Some(sleeper.id()));
+        assert!(process.stored_pid_start_time.is_some());
+        assert_eq!(process.pid(), Some(sleeper.id()));
+        assert!(process.check_status().await.unwrap());
+
+        process.stop().await.unwrap();
+        assert_eq!(process.state, ProcessState::Stopped);
+        assert_eq!(process.stored_pid, None);
+        assert!(sleeper.try_wait().unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_stored_pid_reused_by_another_process() {
+        // The test process stands in for an unrelated process that reused the PID
+        let mut process = Process::new(create_test_config());
+        process.set_state(ProcessState::Online);
+        process.stored_pid = Some(std::process::id());
+        process.stored_pid_start_time = Some(1);
+        assert!(!process.owns_stored_pid());
+
+        // Neither stopping nor status checks touch the unrelated process
+        process.stop().await.unwrap();
+        assert_eq!(process.state, ProcessState::Stopped);
+
+        process.set_state(ProcessState::Online);
+        process.stored_pid = Some(std::process::id());
+        process.stored_pid_start_time = Some(1);
+        assert!(!process.check_status().await.unwrap());
+        assert_eq!(process.state, ProcessState::Stopped);
+        assert_eq!(process.stored_pid, None);
+    }
+
+    #[tokio::test]
+    async fn test_stored_pid_exit_is_unknown() {
+        let mut sleeper = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let mut config = create_test_config();
+        config.restart_mode = crate::restart::RestartMode::OnFailure;
+        let mut process = Process::new(config);
+        process.set_state(ProcessState::Online);
+        process.set_stored_pid(Some(sleeper.id()));
+
+        sleeper.kill().unwrap();
+        sleeper.wait().unwrap();
+        assert!(!process.check_status().await.unwrap());
+        assert_eq!(process.last_exit, Some(ProcessExit::unknown()));
+        assert_eq!(
+            process.last_exit.unwrap().to_string(),
+            "unknown exit status"
+        );
+
+        // It may have exited cleanly, so conditional modes leave it alone
+        assert!(!process.should_auto_restart());
+        process.config.restart_mode = crate::restart::RestartMode::Always;
+        assert!(process.should_auto_restart());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stop_escalates_through_kill_chain() {
+        use crate::signals::KillStep;
+
+        // Ignores SIGTERM, exits with 3 on SIGINT and with 4 on SIGQUIT
+        let script = "trap '' TERM; trap 'exit 3' INT; trap 'exit 4' QUIT; \
+                      echo ready; while true; do sleep 0.05; done";
+        let stubborn = |builder: crate::config::ProcessConfigBuilder| {
+            builder
+                .name("stubborn")
+                .script("sh")
+                .args(vec!["-c".to_string(), script.to_string()])
+                .build()
+                .unwrap()
+        };
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let start = |config| async {
+            let mut process = Process::new(config);
+            // Wait for the traps to be installed before signalling
+            start_and_wait_for_output(&mut process, temp_dir.path()).await;
+            process
+        };
+
+        let mut process = start(stubborn(ProcessConfig::builder().kill_chain(vec![
+            KillStep::new(ProcessSignal::Term).timeout(200),
+            KillStep::new(ProcessSignal::Int),
+        ])))
+        .await;
+        process.stop().await.unwrap();
+        assert_eq!(process.exit_code, Some(3));
+
+        let mut process = start(stubborn(
+            ProcessConfig::builder().kill_signal(ProcessSignal::Quit),
+        ))
+        .await;
+        process.stop().await.unwrap();
+        assert_eq!(process.exit_code, Some(4));
+
+        // SIGKILL once kill_timeout runs out
+        let started = std::time::Instant::now();
+        let mut process = start(stubborn(ProcessConfig::builder().kill_timeout(200))).await;
+        process.stop().await.unwrap();
+        assert_eq!(process.exit_code, None);
+        assert_eq!(process.state, ProcessState::Stopped);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stop_signals_process_group_and_reaps_leftovers() {
+        async fn start_wrapper(script: &str) -> (Process, Vec<u32>) {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let config = ProcessConfig::builder()
+                .name("wrapper")
+                .script("sh")
+                .args(vec!["-c".to_string(), script.to_string()])
+                .kill_timeout(300)
+                .build()
+                .unwrap();
+            let mut process = Process::new(config);
+            start_and_wait_for_output(&mut process, temp_dir.path()).await;
+            let pid = process.pid().unwrap();
+            let children = crate::monitoring::process_descendants(pid)
+                .into_iter()
+                .map(|(pid, _)| pid)
+                .collect();
+            (process, children)
+        }
+
+        // Grandchildren in the process group get the stop signal too
+        let (mut process, children) = start_wrapper("sleep 30 & echo ready; wait").await;
+        assert_eq!(children.len(), 1);
+        let report = process.stop().await.unwrap();
+        assert!(!report.forced);
+        assert!(report.reaped.is_empty());
+        assert_eq!(crate::monitoring::process_start_time(children[0]), None);
+
+        // A grandchild that ignores SIGTERM is killed once the grace period is over
+        let (mut process, children) = start_wrapper(
+            "sh -c \"trap '' TERM; echo ready; while true; do sleep 0.05; done\" & wait",
+        )
+        .await;
+        let stubborn = children[0];
+        let report = process.stop().await.unwrap();
+        assert!(!report.forced);
+        assert!(report.reaped.contains(&stubborn));
+        assert_eq!(crate::monitoring::process_start_time(stubborn), None);
+    }
+
     #[tokio::test]
     async fn test_process_check_status_no_child() {
         let config = create_test_config();
@@ -938,6 +1981,26 @@ mod tests {
         assert_eq!(status.name, deserialized.name);
         assert_eq!(status.state, deserialized.state);
         assert_eq!(status.restarts, deserialized.restarts);
+
+        // Statuses from builds without the restart and health fields still parse
+        let mut older = serde_json::to_value(&status).unwrap();
+        let fields = older.as_object_mut().unwrap();
+        for field in [
+            "watching",
+            "health",
+            "unstable_restarts",
+            "backoff_delay",
+            "next_restart",
+            "last_restart_reason",
+            "last_exit_code",
+            "last_exit_signal",
+            "restart_history",
+        ] {
+            assert!(fields.remove(field).is_some(), "{} is serialized", field);
+        }
+        let deserialized: ProcessStatus = serde_json::from_value(older).unwrap();
+        assert_eq!(deserialized.unstable_restarts, 0);
+        assert!(deserialized.last_restart_reason.is_none());
     }
 
     #[test]
@@ -965,6 +2028,30 @@ mod tests {
         assert_eq!(process.status().restarts, 5);
     }
 
+    #[test]
+    fn test_restart_history_is_bounded() {
+        let mut process = Process::new(create_test_config());
+        process.last_exit = Some(ProcessExit {
+            code: Some(3),
+            signal: None,
+            unknown: false,
+        });
+        process.record_restart(RestartReason::Manual);
+        for _ in 0..crate::RESTART_HISTORY_LIMIT {
+            process.record_restart(RestartReason::Crash);
+        }
+
+        let status = process.status();
+        assert_eq!(status.restart_history.len(), crate::RESTART_HISTORY_LIMIT);
+        assert!(status
+            .restart_history
+            .iter()
+            .all(|record| record.reason == RestartReason::Crash));
+        assert_eq!(status.last_restart_reason, Some(RestartReason::Crash));
+        assert_eq!(status.last_exit_code, Some(3));
+        assert_eq!(status.restart_history[0].exit_code, Some(3));
+    }
+
     #[test]
     fn test_restart_counter_in_status_display() {
         let config = create_test_config();
@@ -1006,4 +2093,197 @@ mod tests {
         // Status should always reflect the current restart count
         assert_eq!(process.status().restarts, 7);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_credentials_none() {
+        assert_eq!(resolve_credentials(None, None).unwrap(), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_credentials_unknown_user_and_group() {
+        let err = resolve_credentials(Some("pmdaemon-no-such-user"), None).unwrap_err();
+        assert!(matches!(err, Error::PermissionDenied { .. }));
+        assert!(err.to_string().contains("does not exist"));
+
+        let err = resolve_credentials(None, Some("pmdaemon-no-such-group")).unwrap_err();
+        assert!(matches!(err, Error::PermissionDenied { .. }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_credentials_current_user() {
+        use nix::unistd::{geteuid, User};
+
+        let me = User::from_uid(geteuid()).unwrap().unwrap();
+        let credentials = resolve_credentials(Some(&me.name), None).unwrap();
+
+        if geteuid().is_root() {
+            let credentials = credentials.unwrap();
+            assert_eq!(credentials.uid, 0);
+            assert!(credentials.groups.contains(&credentials.gid));
+            assert_eq!(credentials.login_env()[0], ("USER", me.name.clone()));
+        } else {
+            // Nothing to change when already running as the requested user
+            assert!(credentials.is_none());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_credentials_requires_root() {
+        if nix::unistd::geteuid().is_root() {
+            return;
+        }
+        let err = resolve_credentials(Some("root"), None).unwrap_err();
+        assert!(matches!(err, Error::PermissionDenied { .. }));
+        assert!(err.to_string().contains("must run as root"));
+    }
+
+    #[tokio::test]
+    async fn test_process_start_unknown_user_fails_before_spawn() {
+        let mut config = create_test_config();
+        config.user = Some("pmdaemon-no-such-user".to_string());
+        let mut process = Process::new(config);
+
+        let err = process.start().await.unwrap_err();
+        assert!(matches!(err, Error::PermissionDenied { .. }));
+        assert_eq!(process.state, ProcessState::Errored);
+        assert!(process.child.is_none());
+        assert!(process.error.is_some());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_process_start_drops_privileges() {
+        use nix::unistd::{geteuid, User};
+
+        if !geteuid().is_root() {
+            return;
+        }
+        let Ok(Some(nobody)) = User::from_name("nobody") else {
+            return;
+        };
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let out_log = temp_dir.path().join("out.log");
+        let err_log = temp_dir.path().join("err.log");
+
+        let config = ProcessConfig::builder()
+            .name("as-nobody")
+            .script("sh")
+            .args(vec!["-c", "id -u; echo $USER"])
+            .build()
+            .unwrap();
+        let mut process = Process::new(config);
+        process.config.user = Some("nobody".to_string());
+
+        process
+            .start_with_logs(Some(out_log.clone()), Some(err_log))
+            .await
+            .unwrap();
+        process.child.as_mut().unwrap().wait().await;
+
+        let output = std::fs::read_to_string(&out_log).unwrap();
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some(nobody.uid.to_string().as_str()));
+        assert_eq!(lines.next(), Some("nobody"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_exit_events_carry_code_and_signal() {
+        let (events_tx, mut events) = mpsc::unbounded_channel();
+        for (command, code, signal) in [
+            ("exit 7", Some(7), None),
+            ("kill -TERM $$", None, Some(ProcessSignal::Term)),
+        ] {
+            let config = ProcessConfig::builder()
+                .name("exiting")
+                .script("sh")
+                .args(vec!["-c", command])
+                .build()
+                .unwrap();
+            let mut process = Process::new(config);
+            process.notify_exits(events_tx.clone());
+            process.start().await.unwrap();
+            let pid = process.pid().unwrap();
+
+            let event = tokio::time::timeout(std::time::Duration::from_secs(5), events.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(event.process_id, process.id);
+            assert_eq!(event.pid, pid);
+            assert_eq!(
+                event.exit,
+                ProcessExit {
+                    code,
+                    signal,
+                    unknown: false
+                }
+            );
+
+            // The exit is visible without waiting for a status check
+            assert_eq!(process.pid(), None);
+            assert!(!process.check_status().await.unwrap());
+            assert_eq!(process.exit_code, code);
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_json_logs_require_the_log_shim() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = ProcessConfig::builder()
+            .name("structured")
+            .script("true")
+            .log_format(LogFormat::Json)
+            .build()
+            .unwrap();
+        let process = Process::new(config);
+        let mut shim = LogShim {
+            process_id: process.id,
+            process_name: "structured".to_string(),
+            instance: None,
+            date_format: None,
+            format: LogFormat::Json,
+            out: None,
+            err: None,
+            combined: Some(temp_dir.path().join("all.log")),
+            socket: None,
+        };
+        let missing = Some(temp_dir.path().join("no-such-pmdaemon"));
+
+        assert!(process.start_log_shim(&shim, None).is_err());
+        assert!(process.start_log_shim(&shim, missing.clone()).is_err());
+
+        // Text output goes to the log files without the shim
+        shim.format = LogFormat::Text;
+        assert!(process.start_log_shim(&shim, missing).unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_output_without_log_files_is_discarded() {
+        let (events_tx, mut events) = mpsc::unbounded_channel();
+        // Far more output than a pipe buffer holds, followed by a last line
+        let config = ProcessConfig::builder()
+            .name("chatty")
+            .script("sh")
+            .args(vec!["-c", "head -c 1000000 /dev/zero; echo; echo done"])
+            .build()
+            .unwrap();
+        let mut process = Process::new(config);
+        process.notify_exits(events_tx);
+        process.start().await.unwrap();
+
+        // The process is not blocked on a full pipe
+        let event = tokio::time::timeout(std::time::Duration::from_secs(5), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(event.exit.code, Some(0));
+    }
 }
@@ -0,0 +1,471 @@
+//! Automatic restart policy for processes that exit on their own.
+//!
+//! When a managed process exits without being asked to, the manager asks the
+//! process' [`RestartState`] what to do next, as soon as the exit is reported (or
+//! on the next monitoring tick for processes restored from disk). Exits that happen
+//! before the process has been up for `min_uptime` count as *unstable restarts*;
+//! once more than `max_restarts` of them happen in a row the process is given up on
+//! and marked errored instead of being restarted again. A stable run resets the
+//! count.
+//! Restarts are scheduled `restart_delay` after the exit, or with an exponential
+//! backoff when `exp_backoff_restart_delay` is set: the delay doubles after each
+//! consecutive crash up to `max_exp_backoff_restart_delay` and starts over once a
+//! run outlasts `min_uptime`.
+//!
+//! Whether an exit calls for a restart at all depends on the process' [`RestartMode`]
+//! and its `stop_exit_codes`, which mark exit codes as a clean stop.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use chrono::{Duration, Utc};
+//! use pmdaemon::restart::{RestartDecision, RestartState};
+//! use pmdaemon::{ProcessConfig, ProcessExit};
+//!
+//! let mut config = ProcessConfig::builder()
+//!     .name("worker")
+//!     .script("worker.sh")
+//!     .build()
+//!     .unwrap();
+//! config.max_restarts = 1;
+//! config.min_uptime = 1000;
+//!
+//! let crashed = ProcessExit {
+//!     code: Some(1),
+//!     ..Default::default()
+//! };
+//! let mut state = RestartState::default();
+//! let now = Utc::now();
+//!
+//! // The first crash within min_uptime is retried...
+//! let decision = state.on_exit(&config, crashed, Duration::milliseconds(10), now);
+//! assert_eq!(decision, RestartDecision::Restart { at: now });
+//!
+//! // ...the second one exceeds max_restarts
+//! let decision = state.on_exit(&config, crashed, Duration::milliseconds(10), now);
+//! assert_eq!(decision, RestartDecision::GiveUp);
+//! ```
+
+use crate::config::ProcessConfig;
+use crate::error::{Error, Result};
+use crate::process::ProcessExit;
+use crate::signals::ProcessSignal;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// When a process that exited on its own is restarted, modelled on systemd's
+/// `Restart=` setting.
+///
+/// Exit codes listed in `stop_exit_codes` never trigger a restart, whatever the mode.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmdaemon::restart::RestartMode;
+/// use pmdaemon::ProcessExit;
+///
+/// let failed = ProcessExit {
+///     code: Some(1),
+///     ..Default::default()
+/// };
+/// let mode: RestartMode = "on-failure".parse().unwrap();
+/// assert!(mode.should_restart(failed));
+/// assert!(!mode.should_restart(ProcessExit::unknown()));
+/// assert_eq!(RestartMode::default(), RestartMode::Always);
+/// ```
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartMode {
+    /// Restart after every exit
+    #[default]
+    Always,
+
+    /// Restart after a nonzero exit code or when killed by a signal
+    ///
+    /// Exits whose status is unknown are not restarted.
+    OnFailure,
+
+    /// Restart only when killed by a signal
+    ///
+    /// Exits whose status is unknown are not restarted.
+    OnAbnormal,
+
+    /// Never restart automatically
+    Never,
+}
+
+impl RestartMode {
+    /// Whether `exit` calls for a restart
+    pub fn should_restart(self, exit: ProcessExit) -> bool {
+        match self {
+            RestartMode::Always => true,
+            RestartMode::OnFailure => !exit.unknown && exit.code != Some(0),
+            RestartMode::OnAbnormal => !exit.unknown && exit.code.is_none(),
+            RestartMode::Never => false,
+        }
+    }
+}
+
+impl fmt::Display for RestartMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestartMode::Always => write!(f, "always"),
+            RestartMode::OnFailure => write!(f, "on-failure"),
+            RestartMode::OnAbnormal => write!(f, "on-abnormal"),
+            RestartMode::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl FromStr for RestartMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "always" => Ok(RestartMode::Always),
+            "on-failure" => Ok(RestartMode::OnFailure),
+            "on-abnormal" => Ok(RestartMode::OnAbnormal),
+            "never" => Ok(RestartMode::Never),
+            _ => Err(Error::config(format!(
+                "Invalid restart mode '{}' (expected always, on-failure, on-abnormal or never)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Why a process was restarted
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartReason {
+    /// The process exited on its own and the restart policy called for a restart
+    Crash,
+    /// The process exceeded `max_memory_restart`
+    MemoryLimit,
+    /// The process failed its health check with `on_unhealthy` set to restart
+    Unhealthy,
+    /// Files watched for the process changed
+    FileChange,
+    /// A user asked for the restart
+    Manual,
+    /// The process was replaced by a new instance during a cluster reload
+    Reload,
+}
+
+impl fmt::Display for RestartReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestartReason::Crash => write!(f, "crash"),
+            RestartReason::MemoryLimit => write!(f, "memory-limit"),
+            RestartReason::Unhealthy => write!(f, "unhealthy"),
+            RestartReason::FileChange => write!(f, "file-change"),
+            RestartReason::Manual => write!(f, "manual"),
+            RestartReason::Reload => write!(f, "reload"),
+        }
+    }
+}
+
+/// A restart in the restart history of a process
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RestartRecord {
+    /// When the process was restarted
+    pub at: DateTime<Utc>,
+    /// Why it was restarted
+    pub reason: RestartReason,
+    /// Exit code of the run that ended with the restart
+    pub exit_code: Option<i32>,
+    /// Signal that ended the run, if it was killed
+    pub exit_signal: Option<ProcessSignal>,
+}
+
+/// What to do with a process that exited on its own
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestartDecision {
+    /// Restart the process once `at` has passed
+    Restart {
+        /// When the restart is due
+        at: DateTime<Utc>,
+    },
+    /// Too many unstable restarts in a row; the process should be marked errored
+    GiveUp,
+    /// The exit does not call for a restart (clean stop, restart mode or
+    /// `autorestart` disabled)
+    Skip,
+}
+
+/// Automatic restart bookkeeping for a single process
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RestartState {
+    /// Consecutive restarts after runs shorter than `min_uptime`
+    pub unstable_restarts: u32,
+
+    /// When the next scheduled restart is due, if one is pending
+    pub next_restart: Option<DateTime<Utc>>,
+
+    /// Current exponential backoff delay in milliseconds (only with
+    /// `exp_backoff_restart_delay`)
+    pub backoff_delay: Option<u64>,
+
+    /// Last time the process was observed running.
+    ///
+    /// Exits of processes restored from disk are noticed by polling, so this is used
+    /// as a conservative estimate of when such a process exited.
+    pub last_seen_alive: Option<DateTime<Utc>>,
+}
+
+impl RestartState {
+    /// Decide what happens after the process exited with `exit` following a run of
+    /// `uptime`.
+    ///
+    /// Updates the unstable restart count and schedules the restart (if any) relative
+    /// to `now`.
+    pub fn on_exit(
+        &mut self,
+        config: &ProcessConfig,
+        exit: ProcessExit,
+        uptime: Duration,
+        now: DateTime<Utc>,
+    ) -> RestartDecision {
+        self.last_seen_alive = None;
+        self.next_restart = None;
+
+        if !config.should_restart_after(exit) {
+            return RestartDecision::Skip;
+        }
+
+        if uptime < Duration::milliseconds(config.min_uptime as i64) {
+            if self.unstable_restarts >= config.max_restarts {
+                return RestartDecision::GiveUp;
+            }
+            self.unstable_restarts += 1;
+        } else {
+            self.unstable_restarts = 0;
+            self.backoff_delay = None;
+        }
+
+        let delay = match config.exp_backoff_restart_delay {
+            Some(initial) => {
+                let delay = match self.backoff_delay {
+                    Some(current) => current
+                        .saturating_mul(2)
+                        .min(config.max_exp_backoff_restart_delay.max(initial)),
+                    None => initial,
+                };
+                self.backoff_delay = Some(delay);
+                delay
+            }
+            None => config.restart_delay,
+        };
+
+        let at = now + Duration::milliseconds(delay as i64);
+        self.next_restart = Some(at);
+        RestartDecision::Restart { at }
+    }
+
+    /// Whether a scheduled restart is due at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_restart.is_some_and(|at| now >= at)
+    }
+
+    /// Forget pending restarts and the unstable restart count.
+    ///
+    /// Called when the process is stopped or restarted by hand.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn config(max_restarts: u32, min_uptime: u64, restart_delay: u64) -> ProcessConfig {
+        let mut config = ProcessConfig::builder()
+            .name("test")
+            .script("echo")
+            .build()
+            .unwrap();
+        config.max_restarts = max_restarts;
+        config.min_uptime = min_uptime;
+        config.restart_delay = restart_delay;
+        config
+    }
+
+    fn exited(code: i32) -> ProcessExit {
+        ProcessExit {
+            code: Some(code),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_unstable_restarts_give_up_after_max() {
+        let config = config(3, 1000, 0);
+        let mut state = RestartState::default();
+        let now = Utc::now();
+
+        for expected in 1..=3 {
+            let decision = state.on_exit(&config, exited(1), Duration::milliseconds(100), now);
+            assert_eq!(decision, RestartDecision::Restart { at: now });
+            assert_eq!(state.unstable_restarts, expected);
+        }
+        assert_eq!(
+            state.on_exit(&config, exited(1), Duration::milliseconds(100), now),
+            RestartDecision::GiveUp
+        );
+        assert_eq!(state.next_restart, None);
+    }
+
+    #[test]
+    fn test_stable_run_resets_count() {
+        let config = config(3, 1000, 0);
+        let mut state = RestartState::default();
+        let now = Utc::now();
+
+        state.on_exit(&config, exited(1), Duration::milliseconds(100), now);
+        state.on_exit(&config, exited(1), Duration::milliseconds(100), now);
+        assert_eq!(state.unstable_restarts, 2);
+
+        state.on_exit(&config, exited(1), Duration::seconds(5), now);
+        assert_eq!(state.unstable_restarts, 0);
+    }
+
+    #[test]
+    fn test_restart_delay_schedules_restart() {
+        let config = config(3, 1000, 2000);
+        let mut state = RestartState::default();
+        let now = Utc::now();
+
+        let decision = state.on_exit(&config, exited(1), Duration::seconds(5), now);
+        let due = now + Duration::seconds(2);
+        assert_eq!(decision, RestartDecision::Restart { at: due });
+        assert!(!state.is_due(now));
+        assert!(!state.is_due(now + Duration::seconds(1)));
+        assert!(state.is_due(due));
+
+        state.reset();
+        assert!(!state.is_due(due));
+    }
+
+    #[test]
+    fn test_exp_backoff_doubles_up_to_cap() {
+        let mut config = config(100, 1000, 5000);
+        config.exp_backoff_restart_delay = Some(4000);
+        let mut state = RestartState::default();
+        let now = Utc::now();
+
+        // Replaces restart_delay and doubles after each consecutive crash
+        for expected in [4000, 8000, 15000, 15000] {
+            let decision = state.on_exit(&config, exited(1), Duration::milliseconds(10), now);
+            let at = now + Duration::milliseconds(expected);
+            assert_eq!(decision, RestartDecision::Restart { at });
+            assert_eq!(state.backoff_delay, Some(expected as u64));
+        }
+
+        // A run past min_uptime starts over at the initial delay
+        state.on_exit(&config, exited(1), Duration::seconds(2), now);
+        assert_eq!(state.backoff_delay, Some(4000));
+
+        // The cap is configurable
+        config.max_exp_backoff_restart_delay = 10000;
+        let mut state = RestartState::default();
+        for expected in [4000, 8000, 10000, 10000] {
+            state.on_exit(&config, exited(1), Duration::milliseconds(10), now);
+            assert_eq!(state.backoff_delay, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_autorestart_disabled() {
+        let mut config = config(3, 1000, 0);
+        config.autorestart = false;
+        let mut state = RestartState::default();
+
+        let decision = state.on_exit(&config, exited(1), Duration::zero(), Utc::now());
+        assert_eq!(decision, RestartDecision::Skip);
+        assert_eq!(state.unstable_restarts, 0);
+    }
+
+    #[test]
+    fn test_restart_modes() {
+        use RestartMode::*;
+
+        let killed = ProcessExit {
+            signal: Some(ProcessSignal::Kill),
+            ..Default::default()
+        };
+        for (mode, clean, failed, signaled, unknown) in [
+            (Always, true, true, true, true),
+            (OnFailure, false, true, true, false),
+            (OnAbnormal, false, false, true, false),
+            (Never, false, false, false, false),
+        ] {
+            assert_eq!(mode.should_restart(exited(0)), clean, "{mode}");
+            assert_eq!(mode.should_restart(exited(2)), failed, "{mode}");
+            assert_eq!(mode.should_restart(killed), signaled, "{mode}");
+            assert_eq!(
+                mode.should_restart(ProcessExit::unknown()),
+                unknown,
+                "{mode}"
+            );
+            assert_eq!(mode.to_string().parse::<RestartMode>().unwrap(), mode);
+        }
+        assert!("sometimes"
+            .parse::<RestartMode>()
+            .unwrap_err()
+            .is_config_error());
+
+        let mode: RestartMode = serde_json::from_str(r#""on-failure""#).unwrap();
+        assert_eq!(mode, OnFailure);
+    }
+
+    #[test]
+    fn test_clean_exit_is_not_restarted() {
+        let mut config = config(3, 1000, 0);
+        config.restart_mode = RestartMode::OnFailure;
+        config.stop_exit_codes = vec![3];
+        let mut state = RestartState::default();
+        let now = Utc::now();
+
+        let uptime = Duration::seconds(5);
+        assert_eq!(
+            state.on_exit(&config, exited(0), uptime, now),
+            RestartDecision::Skip
+        );
+        assert_eq!(
+            state.on_exit(&config, exited(3), uptime, now),
+            RestartDecision::Skip
+        );
+        assert_eq!(
+            state.on_exit(&config, exited(1), uptime, now),
+            RestartDecision::Restart { at: now }
+        );
+
+        // stop_exit_codes apply in every mode
+        config.restart_mode = RestartMode::Always;
+        assert_eq!(
+            state.on_exit(&config, exited(3), uptime, now),
+            RestartDecision::Skip
+        );
+    }
+
+    #[test]
+    fn test_zero_max_restarts_never_retries_unstable_exit() {
+        let config = config(0, 1000, 0);
+        let mut state = RestartState::default();
+        let now = Utc::now();
+
+        assert_eq!(
+            state.on_exit(&config, exited(1), Duration::zero(), now),
+            RestartDecision::GiveUp
+        );
+        // Stable runs are still restarted
+        assert_eq!(
+            state.on_exit(&config, exited(1), Duration::seconds(2), now),
+            RestartDecision::Restart { at: now }
+        );
+    }
+}
@@ -50,6 +50,7 @@ use crate::error::{Error, Result};
 use nix::sys::signal::{kill, Signal};
 #[cfg(unix)]
 use nix::unistd::Pid;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -171,14 +172,7 @@ impl SignalHandler {
     #[cfg(unix)]
     pub fn send_signal(&self, pid: u32, signal: ProcessSignal) -> Result<()> {
         let pid = Pid::from_raw(pid as i32);
-        let signal = match signal {
-            ProcessSignal::Term => Signal::SIGTERM,
-            ProcessSignal::Kill => Signal::SIGKILL,
-            ProcessSignal::Int => Signal::SIGINT,
-            ProcessSignal::Quit => Signal::SIGQUIT,
-            ProcessSignal::Usr1 => Signal::SIGUSR1,
-            ProcessSignal::Usr2 => Signal::SIGUSR2,
-        };
+        let signal = signal.to_nix()?;
 
         debug!("Sending signal {} to PID {}", signal, pid);
         kill(pid, signal).map_err(|e| {
@@ -190,6 +184,41 @@ impl SignalHandler {
         Ok(())
     }
 
+    /// Send a signal to every process in a process group.
+    ///
+    /// Processes managed by PMDaemon lead their own process group, whose ID is the
+    /// leader's PID, so this reaches the processes they started as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the process group doesn't exist or permission is denied.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use pmdaemon::signals::{SignalHandler, ProcessSignal};
+    ///
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let handler = SignalHandler::new();
+    /// handler.send_signal_to_group(1234, ProcessSignal::Term)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(unix)]
+    pub fn send_signal_to_group(&self, pgid: u32, signal: ProcessSignal) -> Result<()> {
+        let pgid = Pid::from_raw(pgid as i32);
+        let signal = signal.to_nix()?;
+
+        debug!("Sending signal {} to process group {}", signal, pgid);
+        nix::sys::signal::killpg(pgid, signal).map_err(|e| {
+            Error::signal(format!(
+                "Failed to send signal {} to process group {}: {}",
+                signal, pgid, e
+            ))
+        })?;
+        Ok(())
+    }
+
     /// Send a signal to a process (Windows implementation).
     ///
     /// On Windows, this provides limited signal functionality using process termination.
@@ -227,6 +256,31 @@ impl SignalHandler {
         }
     }
 
+    /// Send a signal to a process and its descendants (Windows implementation).
+    ///
+    /// Windows has no process groups, so the whole process tree is terminated.
+    #[cfg(windows)]
+    pub fn send_signal_to_group(&self, pid: u32, signal: ProcessSignal) -> Result<()> {
+        use std::process::Command;
+
+        if !signal.is_termination_signal() {
+            return self.send_signal(pid, signal);
+        }
+        debug!("Terminating process tree of PID {} (Windows)", pid);
+        let output = Command::new("taskkill")
+            .args(&["/PID", &pid.to_string(), "/T", "/F"])
+            .output()
+            .map_err(|e| Error::signal(format!("Failed to execute taskkill: {}", e)))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(Error::signal(format!(
+                "Failed to kill process tree {}: {}",
+                pid, stderr
+            )));
+        }
+        Ok(())
+    }
+
     /// Setup signal handlers for graceful shutdown.
     ///
     /// Configures signal handlers to catch SIGTERM and SIGINT signals, allowing
@@ -481,7 +535,11 @@ impl SignalHandler {
 }
 
 /// Process signals that can be sent
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///
+/// Serialized as the signal name, e.g. `"SIGTERM"`; any name accepted by
+/// [`FromStr`] can be deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub enum ProcessSignal {
     /// SIGTERM - Graceful termination
     Term,
@@ -495,6 +553,12 @@ pub enum ProcessSignal {
     Usr1,
     /// SIGUSR2 - User signal 2
     Usr2,
+    /// SIGHUP - Hangup, commonly used to reload configuration
+    Hup,
+    /// SIGWINCH - Window size change, used by some servers to stop workers gracefully
+    Winch,
+    /// Any other signal, by number
+    Number(i32),
 }
 
 impl Default for SignalHandler {
@@ -512,6 +576,9 @@ impl std::fmt::Display for ProcessSignal {
             ProcessSignal::Quit => write!(f, "SIGQUIT"),
             ProcessSignal::Usr1 => write!(f, "SIGUSR1"),
             ProcessSignal::Usr2 => write!(f, "SIGUSR2"),
+            ProcessSignal::Hup => write!(f, "SIGHUP"),
+            ProcessSignal::Winch => write!(f, "SIGWINCH"),
+            ProcessSignal::Number(number) => write!(f, "{}", number),
         }
     }
 }
@@ -527,11 +594,31 @@ impl FromStr for ProcessSignal {
             "QUIT" | "SIGQUIT" => Ok(ProcessSignal::Quit),
             "USR1" | "SIGUSR1" => Ok(ProcessSignal::Usr1),
             "USR2" | "SIGUSR2" => Ok(ProcessSignal::Usr2),
-            _ => Err(format!("Invalid signal: {}", s)),
+            "HUP" | "SIGHUP" => Ok(ProcessSignal::Hup),
+            "WINCH" | "SIGWINCH" => Ok(ProcessSignal::Winch),
+            number => number
+                .parse()
+                .ok()
+                .and_then(ProcessSignal::from_number)
+                .ok_or_else(|| format!("Invalid signal: {}", s)),
         }
     }
 }
 
+impl TryFrom<String> for ProcessSignal {
+    type Error = String;
+
+    fn try_from(s: String) -> std::result::Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<ProcessSignal> for String {
+    fn from(signal: ProcessSignal) -> Self {
+        signal.to_string()
+    }
+}
+
 impl ProcessSignal {
     /// Get all available signals
     pub fn all() -> Vec<ProcessSignal> {
@@ -542,9 +629,58 @@ impl ProcessSignal {
             ProcessSignal::Quit,
             ProcessSignal::Usr1,
             ProcessSignal::Usr2,
+            ProcessSignal::Hup,
+            ProcessSignal::Winch,
         ]
     }
 
+    /// The signal with the given number, using the named variant where there is one
+    ///
+    /// Returns `None` for numbers that are not a valid signal on this platform.
+    #[cfg(unix)]
+    pub fn from_number(number: i32) -> Option<ProcessSignal> {
+        let signal = Signal::try_from(number).ok()?;
+        Some(
+            ProcessSignal::all()
+                .into_iter()
+                .find(|named| named.to_nix().ok() == Some(signal))
+                .unwrap_or(ProcessSignal::Number(number)),
+        )
+    }
+
+    /// The signal with the given number, using the named variant where there is one
+    ///
+    /// Only the numbers shared by all platforms have a named variant here.
+    #[cfg(not(unix))]
+    pub fn from_number(number: i32) -> Option<ProcessSignal> {
+        match number {
+            1 => Some(ProcessSignal::Hup),
+            2 => Some(ProcessSignal::Int),
+            3 => Some(ProcessSignal::Quit),
+            9 => Some(ProcessSignal::Kill),
+            15 => Some(ProcessSignal::Term),
+            1..=64 => Some(ProcessSignal::Number(number)),
+            _ => None,
+        }
+    }
+
+    /// The corresponding `nix` signal
+    #[cfg(unix)]
+    fn to_nix(self) -> Result<Signal> {
+        Ok(match self {
+            ProcessSignal::Term => Signal::SIGTERM,
+            ProcessSignal::Kill => Signal::SIGKILL,
+            ProcessSignal::Int => Signal::SIGINT,
+            ProcessSignal::Quit => Signal::SIGQUIT,
+            ProcessSignal::Usr1 => Signal::SIGUSR1,
+            ProcessSignal::Usr2 => Signal::SIGUSR2,
+            ProcessSignal::Hup => Signal::SIGHUP,
+            ProcessSignal::Winch => Signal::SIGWINCH,
+            ProcessSignal::Number(number) => Signal::try_from(number)
+                .map_err(|e| Error::signal(format!("Invalid signal {}: {}", number, e)))?,
+        })
+    }
+
     /// Check if this is a termination signal
     pub fn is_termination_signal(&self) -> bool {
         matches!(
@@ -559,6 +695,74 @@ impl ProcessSignal {
     }
 }
 
+/// One step of a stop escalation chain.
+///
+/// The signal is sent and the process gets `timeout` milliseconds (the process'
+/// `kill_timeout` when unset) to exit before the next step is taken.
+///
+/// Steps can be parsed from `SIGNAL[:DURATION]`, e.g. `TERM:5s` or `KILL`.
+///
+/// # Examples
+///
+/// ```rust
+/// use pmdaemon::signals::{KillStep, ProcessSignal};
+///
+/// let step: KillStep = "QUIT:30s".parse().unwrap();
+/// assert_eq!(step.signal, ProcessSignal::Quit);
+/// assert_eq!(step.timeout, Some(30_000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KillStep {
+    /// Signal to send
+    pub signal: ProcessSignal,
+    /// Time to wait for the process to exit (ms, default: `kill_timeout`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+impl KillStep {
+    /// Create a step that waits `kill_timeout` after sending `signal`
+    pub fn new(signal: ProcessSignal) -> Self {
+        Self {
+            signal,
+            timeout: None,
+        }
+    }
+
+    /// Set the time to wait after sending the signal (in milliseconds)
+    pub fn timeout(mut self, timeout_ms: u64) -> Self {
+        self.timeout = Some(timeout_ms);
+        self
+    }
+}
+
+impl std::fmt::Display for KillStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.timeout {
+            Some(timeout) => write!(f, "{}:{}ms", self.signal, timeout),
+            None => write!(f, "{}", self.signal),
+        }
+    }
+}
+
+impl FromStr for KillStep {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (signal, timeout) = match s.split_once(':') {
+            Some((signal, timeout)) => (signal, Some(timeout)),
+            None => (s, None),
+        };
+        let signal: ProcessSignal = signal.trim().parse().map_err(Error::config)?;
+        let mut step = KillStep::new(signal);
+        if let Some(timeout) = timeout {
+            let timeout = crate::config::parse_duration_string(timeout.trim())?;
+            step = step.timeout(timeout.as_millis() as u64);
+        }
+        Ok(step)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -600,6 +804,9 @@ mod tests {
         assert_eq!(ProcessSignal::Quit.to_string(), "SIGQUIT");
         assert_eq!(ProcessSignal::Usr1.to_string(), "SIGUSR1");
         assert_eq!(ProcessSignal::Usr2.to_string(), "SIGUSR2");
+        assert_eq!(ProcessSignal::Hup.to_string(), "SIGHUP");
+        assert_eq!(ProcessSignal::Winch.to_string(), "SIGWINCH");
+        assert_eq!(ProcessSignal::Number(12).to_string(), "12");
     }
 
     #[test]
@@ -626,13 +833,15 @@ mod tests {
     #[test]
     fn test_process_signal_all() {
         let signals = ProcessSignal::all();
-        assert_eq!(signals.len(), 6);
+        assert_eq!(signals.len(), 8);
         assert!(signals.contains(&ProcessSignal::Term));
         assert!(signals.contains(&ProcessSignal::Kill));
         assert!(signals.contains(&ProcessSignal::Int));
         assert!(signals.contains(&ProcessSignal::Quit));
         assert!(signals.contains(&ProcessSignal::Usr1));
         assert!(signals.contains(&ProcessSignal::Usr2));
+        assert!(signals.contains(&ProcessSignal::Hup));
+        assert!(signals.contains(&ProcessSignal::Winch));
     }
 
     #[test]
@@ -657,8 +866,40 @@ mod tests {
         assert_eq!("USR2".parse::<ProcessSignal>(), Ok(ProcessSignal::Usr2));
         assert_eq!("SIGUSR2".parse::<ProcessSignal>(), Ok(ProcessSignal::Usr2));
 
+        assert_eq!("HUP".parse::<ProcessSignal>(), Ok(ProcessSignal::Hup));
+        assert_eq!("sighup".parse::<ProcessSignal>(), Ok(ProcessSignal::Hup));
+        assert_eq!("WINCH".parse::<ProcessSignal>(), Ok(ProcessSignal::Winch));
+        assert_eq!(
+            "SIGWINCH".parse::<ProcessSignal>(),
+            Ok(ProcessSignal::Winch)
+        );
+
         assert!("INVALID".parse::<ProcessSignal>().is_err());
         assert!("".parse::<ProcessSignal>().is_err());
+        assert!("0".parse::<ProcessSignal>().is_err());
+        assert!("-15".parse::<ProcessSignal>().is_err());
+    }
+
+    #[test]
+    fn test_process_signal_from_number() {
+        // Numbers of named signals parse to the named variant
+        assert_eq!("1".parse::<ProcessSignal>(), Ok(ProcessSignal::Hup));
+        assert_eq!("9".parse::<ProcessSignal>(), Ok(ProcessSignal::Kill));
+        assert_eq!("15".parse::<ProcessSignal>(), Ok(ProcessSignal::Term));
+        assert_eq!(ProcessSignal::from_number(1000), None);
+
+        #[cfg(unix)]
+        {
+            let pipe = nix::sys::signal::Signal::SIGPIPE as i32;
+            assert_eq!(
+                pipe.to_string().parse::<ProcessSignal>(),
+                Ok(ProcessSignal::Number(pipe))
+            );
+            assert_eq!(
+                ProcessSignal::from_number(nix::sys::signal::Signal::SIGWINCH as i32),
+                Some(ProcessSignal::Winch)
+            );
+        }
     }
 
     #[test]
@@ -681,6 +922,35 @@ mod tests {
         assert!(ProcessSignal::Usr2.is_user_signal());
     }
 
+    #[test]
+    fn test_process_signal_serde() {
+        let json = serde_json::to_string(&ProcessSignal::Quit).unwrap();
+        assert_eq!(json, r#""SIGQUIT""#);
+        let signal: ProcessSignal = serde_json::from_str(r#""int""#).unwrap();
+        assert_eq!(signal, ProcessSignal::Int);
+        assert!(serde_json::from_str::<ProcessSignal>(r#""SIGFOO""#).is_err());
+    }
+
+    #[test]
+    fn test_kill_step_parsing() {
+        assert_eq!(
+            "TERM".parse::<KillStep>().unwrap(),
+            KillStep::new(ProcessSignal::Term)
+        );
+        assert_eq!(
+            "sigint:2s".parse::<KillStep>().unwrap(),
+            KillStep::new(ProcessSignal::Int).timeout(2000)
+        );
+        assert!("FOO:1s".parse::<KillStep>().unwrap_err().is_config_error());
+        assert!("TERM:soon".parse::<KillStep>().is_err());
+
+        let step = KillStep::new(ProcessSignal::Quit).timeout(500);
+        assert_eq!(step.to_string().parse::<KillStep>().unwrap(), step);
+
+        let step: KillStep = serde_json::from_str(r#"{"signal": "SIGTERM"}"#).unwrap();
+        assert_eq!(step, KillStep::new(ProcessSignal::Term));
+    }
+
     #[tokio::test]
     async fn test_send_signal_to_nonexistent_process() {
         let handler = SignalHandler::new();
@@ -0,0 +1,462 @@
+//! File watching for automatic restarts.
+//!
+//! When a process is started with `watch: true`, PMDaemon watches its working
+//! directory (or the explicit `watch_paths`) and restarts the process whenever files
+//! change. Paths matching any of the `ignore_watch` glob patterns are skipped, and
+//! bursts of events (e.g. a `git checkout` or an editor writing several files) are
+//! debounced into a single restart once the tree has been quiet for `watch_delay`
+//! milliseconds. Changes that never stop (a build tool or a log file in the tree)
+//! still restart the process once [`max_debounce_wait`] has passed since the first
+//! of them.
+//!
+//! ## Ignore Patterns
+//!
+//! Each pattern is matched against the path relative to the watched directory and
+//! against every individual path component, so all of these work as expected:
+//!
+//! - `node_modules` - ignores the directory at any depth
+//! - `*.log` - ignores log files anywhere in the tree
+//! - `build/**/*.js` - ignores generated files below `build/`
+//!
+//! ## Examples
+//!
+//! ```rust,no_run
+//! use pmdaemon::watcher::{IgnoreRules, ProcessWatcher};
+//! use std::time::Duration;
+//! use tokio::sync::mpsc;
+//! use uuid::Uuid;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let (tx, mut rx) = mpsc::unbounded_channel();
+//! let rules = IgnoreRules::new(&["node_modules".to_string(), "*.log".to_string()])?;
+//!
+//! let _watcher = ProcessWatcher::new(
+//!     Uuid::new_v4(),
+//!     vec!["./src".into()],
+//!     rules,
+//!     Duration::from_millis(500),
+//!     tx,
+//! )?;
+//!
+//! while let Some(event) = rx.recv().await {
+//!     println!("Changed: {:?}", event.paths);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::config::ProcessConfig;
+use crate::error::{Error, Result};
+use crate::manager::ProcessManager;
+use crate::process::ProcessId;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+use tracing::{debug, info, warn};
+
+/// A debounced batch of file changes for a single process
+#[derive(Debug, Clone)]
+pub struct WatchEvent {
+    /// Process whose watched files changed
+    pub process_id: ProcessId,
+    /// Paths that changed during the debounce window
+    pub paths: Vec<PathBuf>,
+}
+
+/// Ignore rules applied to file change events.
+///
+/// Combines `ignore_watch` glob patterns with absolute paths that must never trigger
+/// a restart (such as the process' own log files, which would otherwise cause a
+/// restart loop when they live inside the watched directory).
+#[derive(Debug, Clone, Default)]
+pub struct IgnoreRules {
+    /// Compiled glob patterns
+    patterns: Vec<glob::Pattern>,
+    /// Absolute paths (files or directories) that are always ignored
+    paths: Vec<PathBuf>,
+}
+
+impl IgnoreRules {
+    /// Compile a list of glob patterns
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if any pattern is not a valid glob.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|pattern| {
+                let trimmed = pattern.trim().trim_end_matches('/');
+                glob::Pattern::new(trimmed).map_err(|e| {
+                    Error::config(format!("Invalid ignore_watch pattern '{}': {}", pattern, e))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self {
+            patterns,
+            paths: Vec::new(),
+        })
+    }
+
+    /// Always ignore `path` and everything below it
+    pub fn ignore_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.paths.push(path.into());
+        self
+    }
+
+    /// Check whether a changed `path` below the watched `root` should be ignored
+    pub fn is_ignored(&self, root: &Path, path: &Path) -> bool {
+        if self.paths.iter().any(|ignored| path.starts_with(ignored)) {
+            return true;
+        }
+
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        self.patterns.iter().any(|pattern| {
+            pattern.matches_path(relative)
+                || relative
+                    .components()
+                    .any(|component| pattern.matches(&component.as_os_str().to_string_lossy()))
+        })
+    }
+}
+
+/// Watches the files of a single process and emits debounced [`WatchEvent`]s.
+///
+/// Watching stops when the `ProcessWatcher` is dropped.
+pub struct ProcessWatcher {
+    /// Watched directories
+    roots: Vec<PathBuf>,
+    /// Underlying filesystem watcher (kept alive for the lifetime of this struct)
+    _watcher: RecommendedWatcher,
+    /// Debounce task forwarding events to the manager
+    task: JoinHandle<()>,
+}
+
+impl std::fmt::Debug for ProcessWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProcessWatcher")
+            .field("roots", &self.roots)
+            .finish()
+    }
+}
+
+impl ProcessWatcher {
+    /// Start watching `roots` recursively.
+    ///
+    /// Must be called from within a Tokio runtime. Events for paths matching
+    /// `rules` are dropped; the rest are collected until no new event has arrived
+    /// for `delay` and then sent to `events` as a single [`WatchEvent`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a configuration error if a root cannot be watched (for example
+    /// because it does not exist).
+    pub fn new(
+        process_id: ProcessId,
+        roots: Vec<PathBuf>,
+        rules: IgnoreRules,
+        delay: Duration,
+        events: mpsc::UnboundedSender<WatchEvent>,
+    ) -> Result<Self> {
+        let (raw_tx, raw_rx) = mpsc::unbounded_channel::<PathBuf>();
+
+        let callback_roots = roots.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let event = match res {
+                Ok(event) => event,
+                Err(e) => {
+                    warn!("File watch error: {}", e);
+                    return;
+                }
+            };
+
+            // Reads and metadata-only access never warrant a restart
+            if matches!(event.kind, EventKind::Access(_)) {
+                return;
+            }
+
+            for path in event.paths {
+                let root = callback_roots
+                    .iter()
+                    .find(|root| path.starts_with(root))
+                    .map(PathBuf::as_path)
+                    .unwrap_or_else(|| Path::new(""));
+                if !rules.is_ignored(root, &path) {
+                    let _ = raw_tx.send(path);
+                }
+            }
+        })
+        .map_err(|e| Error::config(format!("Failed to create file watcher: {}", e)))?;
+
+        for root in &roots {
+            watcher
+                .watch(root, RecursiveMode::Recursive)
+                .map_err(|e| Error::config(format!("Failed to watch {:?}: {}", root, e)))?;
+            debug!("Watching {:?} for process {}", root, process_id);
+        }
+
+        let task = tokio::spawn(debounce(
+            process_id,
+            raw_rx,
+            delay,
+            max_debounce_wait(delay),
+            events,
+        ));
+
+        Ok(Self {
+            roots,
+            _watcher: watcher,
+            task,
+        })
+    }
+
+    /// Start watching according to a process configuration.
+    ///
+    /// Watches `watch_paths` (relative entries are resolved against the working
+    /// directory) or the working directory itself when none are given, ignoring
+    /// `ignore_watch` patterns plus any `extra_ignored` absolute paths.
+    pub fn for_config(
+        process_id: ProcessId,
+        config: &ProcessConfig,
+        extra_ignored: Vec<PathBuf>,
+        events: mpsc::UnboundedSender<WatchEvent>,
+    ) -> Result<Self> {
+        let mut rules = IgnoreRules::new(&config.ignore_watch)?;
+        for path in extra_ignored {
+            rules = rules.ignore_path(path);
+        }
+
+        Self::new(
+            process_id,
+            config.watch_roots(),
+            rules,
+            Duration::from_millis(config.watch_delay),
+            events,
+        )
+    }
+
+    /// Get the watched directories
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+}
+
+impl Drop for ProcessWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Longest a batch of changes is held back while changes keep arriving, measured
+/// from the first of them: ten times the quiet period `delay`, and at least 5 seconds
+pub fn max_debounce_wait(delay: Duration) -> Duration {
+    delay.saturating_mul(10).max(Duration::from_secs(5))
+}
+
+/// Collect raw change notifications into debounced batches
+async fn debounce(
+    process_id: ProcessId,
+    mut raw: mpsc::UnboundedReceiver<PathBuf>,
+    delay: Duration,
+    max_wait: Duration,
+    events: mpsc::UnboundedSender<WatchEvent>,
+) {
+    while let Some(first) = raw.recv().await {
+        let mut paths = vec![first];
+        let deadline = tokio::time::Instant::now() + max_wait;
+
+        // Keep extending the window until the tree has been quiet for `delay`, or
+        // until `max_wait` has passed since the first change
+        let mut closed = false;
+        while tokio::time::Instant::now() < deadline {
+            let quiet = (tokio::time::Instant::now() + delay).min(deadline);
+            match tokio::time::timeout_at(quiet, raw.recv()).await {
+                Ok(Some(path)) => {
+                    if !paths.contains(&path) {
+                        paths.push(path);
+                    }
+                }
+                Ok(None) => {
+                    closed = true;
+                    break;
+                }
+                Err(_) => break,
+            }
+        }
+
+        if events.send(WatchEvent { process_id, paths }).is_err() || closed {
+            return;
+        }
+    }
+}
+
+/// Restart processes as debounced watch events arrive.
+///
+/// Long-running hosts of a [`ProcessManager`] (the daemon and the web server) spawn
+/// this with the receiver obtained from [`ProcessManager::take_watch_events`].
+pub async fn run_restart_loop(
+    manager: Arc<RwLock<ProcessManager>>,
+    mut events: mpsc::UnboundedReceiver<WatchEvent>,
+) {
+    while let Some(event) = events.recv().await {
+        let manager = manager.read().await;
+        if let Err(e) = manager.handle_watch_event(event).await {
+            warn!("Failed to restart process after file change: {}", e);
+        }
+    }
+    info!("File watch restart loop finished");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+    use uuid::Uuid;
+
+    fn rules(patterns: &[&str]) -> IgnoreRules {
+        IgnoreRules::new(&patterns.iter().map(|p| p.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn test_ignore_rules_component_match() {
+        let rules = rules(&["node_modules", "*.log"]);
+        let root = Path::new("/app");
+
+        assert!(rules.is_ignored(root, Path::new("/app/node_modules/pkg/index.js")));
+        assert!(rules.is_ignored(root, Path::new("/app/logs/server.log")));
+        assert!(!rules.is_ignored(root, Path::new("/app/src/server.js")));
+    }
+
+    #[test]
+    fn test_ignore_rules_relative_glob() {
+        let rules = rules(&["build/**/*.js", "tmp/"]);
+        let root = Path::new("/app");
+
+        assert!(rules.is_ignored(root, Path::new("/app/build/static/main.js")));
+        assert!(rules.is_ignored(root, Path::new("/app/tmp/cache")));
+        assert!(!rules.is_ignored(root, Path::new("/app/src/build.js")));
+    }
+
+    #[test]
+    fn test_ignore_rules_absolute_paths() {
+        let rules = IgnoreRules::default().ignore_path("/app/out.log");
+        let root = Path::new("/app");
+
+        assert!(rules.is_ignored(root, Path::new("/app/out.log")));
+        assert!(!rules.is_ignored(root, Path::new("/app/out.txt")));
+    }
+
+    #[test]
+    fn test_ignore_rules_invalid_pattern() {
+        let err = IgnoreRules::new(&["[".to_string()]).unwrap_err();
+        assert!(err.is_config_error());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_missing_root() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let result = ProcessWatcher::new(
+            Uuid::new_v4(),
+            vec![PathBuf::from("/definitely/not/a/real/path")],
+            IgnoreRules::default(),
+            Duration::from_millis(50),
+            tx,
+        );
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_watcher_debounces_and_ignores() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let process_id = Uuid::new_v4();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+
+        let _watcher = ProcessWatcher::new(
+            process_id,
+            vec![root.clone()],
+            rules(&["*.log"]),
+            Duration::from_millis(200),
+            tx,
+        )
+        .unwrap();
+
+        // Ignored changes alone never produce an event
+        std::fs::write(root.join("server.log"), "noise").unwrap();
+        let quiet = tokio::time::timeout(Duration::from_millis(500), rx.recv()).await;
+        assert!(quiet.is_err());
+
+        // A burst of changes is delivered as a single event
+        for i in 0..5 {
+            std::fs::write(root.join(format!("file-{}.js", i)), "change").unwrap();
+        }
+
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("expected a watch event")
+            .unwrap();
+        assert_eq!(event.process_id, process_id);
+        assert!(event
+            .paths
+            .iter()
+            .all(|p| !p.to_string_lossy().ends_with(".log")));
+        assert!(event.paths.iter().any(|p| p.ends_with("file-0.js")));
+
+        let extra = tokio::time::timeout(Duration::from_millis(500), rx.recv()).await;
+        assert!(extra.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_debounce_bounds_the_window() {
+        let process_id = Uuid::new_v4();
+        let (raw_tx, raw_rx) = mpsc::unbounded_channel();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        tokio::spawn(debounce(
+            process_id,
+            raw_rx,
+            Duration::from_millis(100),
+            Duration::from_millis(500),
+            tx,
+        ));
+
+        // Changes every 20ms never leave the tree quiet, yet are delivered
+        let started = std::time::Instant::now();
+        let writer = tokio::spawn(async move {
+            for i in 0..=100 {
+                raw_tx
+                    .send(PathBuf::from(format!("app.log.{}", i)))
+                    .unwrap();
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+        });
+        let event = tokio::time::timeout(Duration::from_secs(2), rx.recv())
+            .await
+            .expect("continuous changes should not postpone the event forever")
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(event.paths.len() > 5);
+
+        // Closing the channel still delivers what was collected
+        writer.await.unwrap();
+        let mut last = None;
+        while let Some(event) = rx.recv().await {
+            last = Some(event);
+        }
+        let last = last.expect("paths collected before the channel closed are sent");
+        assert!(last.paths.contains(&PathBuf::from("app.log.100")));
+
+        assert_eq!(
+            max_debounce_wait(Duration::from_millis(100)),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            max_debounce_wait(Duration::from_secs(2)),
+            Duration::from_secs(20)
+        );
+    }
+}
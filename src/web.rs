@@ -2,9 +2,13 @@
 
 use crate::config::PortConfig;
 use crate::error::{Error, Result};
+use crate::events::EventFilter;
+use crate::logs::{humanize_json_line, LogFormat, LogStream};
 use crate::manager::ProcessManager;
 use crate::monitoring::{Monitor, SystemMetrics};
 use crate::process::{ProcessState, ProcessStatus};
+use crate::signals::ProcessSignal;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::IntoResponse;
 use axum::{
     extract::{
@@ -48,6 +52,53 @@ pub struct LogsQuery {
     pub follow: Option<bool>,
 }
 
+/// Query parameters for streaming logs
+#[derive(Debug, Deserialize)]
+pub struct LogStreamQuery {
+    /// Only lines of this stream (`out` or `err`); both when omitted
+    pub stream: Option<LogStream>,
+    /// Number of recent lines per stream to send before following new output
+    pub lines: Option<usize>,
+}
+
+/// Query parameters for searching logs
+#[derive(Debug, Deserialize)]
+pub struct LogSearchQuery {
+    /// Only lines matching this regular expression
+    pub grep: Option<String>,
+    /// Only lines written since this time: a duration before now (`30m`, `2h`, `7d`)
+    /// or an RFC 3339 timestamp
+    pub since: Option<String>,
+    /// Only lines written up to this time, in the same format as `since`
+    pub until: Option<String>,
+    /// Only lines of at least this level (`trace`, `debug`, `info`, `warn`, `error`,
+    /// `fatal`)
+    pub level: Option<String>,
+    /// Only lines of this stream (`out` or `err`); both when omitted
+    pub stream: Option<LogStream>,
+    /// Maximum number of (most recent) lines to return
+    pub limit: Option<usize>,
+}
+
+/// Query parameters for lifecycle events
+#[derive(Debug, Deserialize)]
+pub struct EventsQuery {
+    /// Only events of this process (name or ID) or of this cluster's instances
+    pub process: Option<String>,
+    /// Only events since this time: a duration before now (`30m`, `2h`, `7d`) or an
+    /// RFC 3339 timestamp
+    pub since: Option<String>,
+    /// Maximum number of (most recent) events to return
+    pub limit: Option<usize>,
+}
+
+/// Query parameters for crash records
+#[derive(Debug, Deserialize)]
+pub struct CrashesQuery {
+    /// Maximum number of (most recent) crash records to return
+    pub limit: Option<usize>,
+}
+
 /// Request body for process actions
 #[derive(Debug, Deserialize)]
 pub struct ProcessActionRequest {
@@ -57,6 +108,16 @@ pub struct ProcessActionRequest {
     pub port_range: Option<(u16, u16)>,
 }
 
+/// Request body for sending a signal
+#[derive(Debug, Deserialize)]
+pub struct SignalRequest {
+    /// Signal name or number, e.g. `SIGHUP`, `USR2` or `10`
+    pub signal: String,
+    /// Treat the path identifier as a namespace and signal all of its processes
+    #[serde(default)]
+    pub namespace: bool,
+}
+
 /// WebSocket message types
 #[derive(Debug, Clone, Serialize)]
 #[serde(tag = "type")]
@@ -64,7 +125,7 @@ pub enum WebSocketMessage {
     /// Process status update
     ProcessUpdate {
         /// Updated process status information
-        process: ProcessStatus,
+        process: Box<ProcessStatus>,
     },
     /// System metrics update
     SystemUpdate {
@@ -183,6 +244,30 @@ impl WebServer {
             }
         });
 
+        // Restart crashed processes as soon as they exit
+        if let Some(events) = self.state.manager.read().await.take_exit_events() {
+            tokio::spawn(crate::manager::run_exit_loop(
+                Arc::clone(&self.state.manager),
+                events,
+            ));
+        }
+
+        // Restart watched processes on file changes
+        if let Some(events) = self.state.manager.read().await.take_watch_events() {
+            tokio::spawn(crate::watcher::run_restart_loop(
+                Arc::clone(&self.state.manager),
+                events,
+            ));
+        }
+
+        // Record health check results and handle unhealthy processes
+        if let Some(events) = self.state.manager.read().await.take_health_events() {
+            tokio::spawn(crate::health::run_health_loop(
+                Arc::clone(&self.state.manager),
+                events,
+            ));
+        }
+
         axum::serve(listener, app)
             .await
             .map_err(|e| Error::web_server(format!("Server error: {}", e)))?;
@@ -235,7 +320,14 @@ impl WebServer {
             .route("/api/processes/:id/stop", post(stop_process))
             .route("/api/processes/:id/restart", post(restart_process))
             .route("/api/processes/:id/reload", post(reload_process))
+            .route("/api/processes/:id/signal", post(signal_process))
             .route("/api/processes/:id/logs", get(get_process_logs))
+            .route("/api/processes/:id/logs/stream", get(stream_process_logs))
+            .route("/api/processes/:id/logs/search", get(search_process_logs))
+            .route("/api/processes/:id/health", get(get_process_health))
+            .route("/api/processes/:id/crashes", get(list_process_crashes))
+            // Lifecycle event journal
+            .route("/api/events", get(list_events))
             // System information
             .route("/api/system", get(system_info))
             .route("/api/status", get(status_info))
@@ -366,12 +458,131 @@ async fn get_process(State(state): State<AppState>, Path(id): Path<String>) -> i
     }
 }
 
+/// Get the latest health check result for a process
+async fn get_process_health(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match state.manager.read().await.get_process_info(&id).await {
+        Ok(status) => Json(json!({
+            "success": true,
+            "data": {
+                "name": status.name,
+                "health": status.health
+            }
+        }))
+        .into_response(),
+        Err(e) => {
+            warn!("Process not found: {}", id);
+            (
+                StatusCode::NOT_FOUND,
+                Json(json!({
+                    "success": false,
+                    "error": "Process not found",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// List lifecycle events from the journal, oldest first
+async fn list_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
+) -> impl IntoResponse {
+    let since = match query
+        .since
+        .as_deref()
+        .map(|since| crate::events::parse_since(since, chrono::Utc::now()))
+        .transpose()
+    {
+        Ok(since) => since,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid since parameter",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    };
+    let filter = EventFilter {
+        process: query.process,
+        since,
+        limit: Some(query.limit.unwrap_or(100)),
+    };
+
+    match state.manager.read().await.events(&filter).await {
+        Ok(events) => Json(json!({
+            "success": true,
+            "data": {
+                "events": events,
+                "count": events.len()
+            }
+        }))
+        .into_response(),
+        Err(e) => {
+            error!("Failed to read events: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "success": false,
+                    "error": "Failed to read events",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// List crash records of a process (or of a cluster's instances), oldest first.
+///
+/// Records of deleted processes are still returned.
+async fn list_process_crashes(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<CrashesQuery>,
+) -> impl IntoResponse {
+    match state.manager.read().await.crashes(Some(&id)).await {
+        Ok(mut crashes) => {
+            let limit = query.limit.unwrap_or(crate::CRASH_RECORDS_LIMIT);
+            crashes.drain(..crashes.len().saturating_sub(limit));
+            Json(json!({
+                "success": true,
+                "data": {
+                    "crashes": crashes,
+                    "count": crashes.len()
+                }
+            }))
+            .into_response()
+        }
+        Err(e) => {
+            error!("Failed to read crash records: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({
+                    "success": false,
+                    "error": "Failed to read crash records",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Delete a process
 async fn delete_process(
     State(state): State<AppState>,
     Path(id): Path<String>,
 ) -> impl IntoResponse {
-    match state.manager.write().await.delete(&id).await {
+    match state.manager.read().await.delete(&id).await {
         Ok(()) => Json(json!({
             "success": true,
             "message": format!("Process '{}' deleted successfully", id)
@@ -431,10 +642,13 @@ async fn start_process(State(state): State<AppState>, Path(id): Path<String>) ->
 
 /// Stop a process
 async fn stop_process(State(state): State<AppState>, Path(id): Path<String>) -> impl IntoResponse {
-    match state.manager.write().await.stop(&id).await {
-        Ok(()) => Json(json!({
+    match state.manager.read().await.stop(&id).await {
+        Ok(report) => Json(json!({
             "success": true,
-            "message": format!("Process '{}' stopped successfully", id)
+            "message": format!("Process '{}' stopped successfully", id),
+            "exit_code": report.exit_code,
+            "forced": report.forced,
+            "reaped": report.reaped
         }))
         .into_response(),
         Err(e) => {
@@ -471,12 +685,12 @@ async fn restart_process(
     let result = if let Some(port_config) = port_config {
         state
             .manager
-            .write()
+            .read()
             .await
             .restart_with_port(&id, Some(port_config))
             .await
     } else {
-        state.manager.write().await.restart(&id).await
+        state.manager.read().await.restart(&id).await
     };
 
     match result {
@@ -519,12 +733,12 @@ async fn reload_process(
     let result = if let Some(port_config) = port_config {
         state
             .manager
-            .write()
+            .read()
             .await
             .reload_with_port(&id, Some(port_config))
             .await
     } else {
-        state.manager.write().await.reload(&id).await
+        state.manager.read().await.reload(&id).await
     };
 
     match result {
@@ -548,6 +762,61 @@ async fn reload_process(
     }
 }
 
+/// Send a signal to a process, every instance of a cluster, or a namespace
+async fn signal_process(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<SignalRequest>,
+) -> impl IntoResponse {
+    let signal: ProcessSignal = match request.signal.parse() {
+        Ok(signal) => signal,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid signal",
+                    "message": e
+                })),
+            )
+                .into_response()
+        }
+    };
+
+    let manager = state.manager.read().await;
+    let result = if request.namespace {
+        manager.signal_namespace(&id, signal).await
+    } else {
+        manager.signal(&id, signal).await
+    };
+
+    match result {
+        Ok(processes) => Json(json!({
+            "success": true,
+            "message": format!("Sent {} to '{}'", signal, id),
+            "signal": signal.to_string(),
+            "processes": processes
+        }))
+        .into_response(),
+        Err(e) => {
+            error!("Failed to send {} to {}: {}", signal, id, e);
+            let status = match e {
+                Error::ProcessNotFound { .. } => StatusCode::NOT_FOUND,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            (
+                status,
+                Json(json!({
+                    "success": false,
+                    "error": "Failed to send signal",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
 /// Get process logs
 async fn get_process_logs(
     State(state): State<AppState>,
@@ -585,6 +854,184 @@ async fn get_process_logs(
     }
 }
 
+/// Search process logs by pattern, time window, level and stream
+async fn search_process_logs(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<LogSearchQuery>,
+) -> impl IntoResponse {
+    let search = match log_search(query) {
+        Ok(search) => search,
+        Err(e) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": "Invalid log search",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    };
+
+    match state.manager.read().await.search_logs(&id, &search).await {
+        Ok(entries) => Json(json!({
+            "success": true,
+            "data": {
+                "entries": entries,
+                "count": entries.len()
+            }
+        }))
+        .into_response(),
+        Err(e) => {
+            error!("Failed to search logs of process {}: {}", id, e);
+            let status = match e {
+                Error::ProcessNotFound { .. } => StatusCode::NOT_FOUND,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            (
+                status,
+                Json(json!({
+                    "success": false,
+                    "error": "Failed to search process logs",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Turn log search query parameters into a search
+fn log_search(query: LogSearchQuery) -> Result<crate::logs::LogSearch> {
+    let now = chrono::Utc::now();
+    let since = query
+        .since
+        .map(|since| crate::events::parse_since(&since, now))
+        .transpose()?;
+    let until = query
+        .until
+        .map(|until| crate::events::parse_since(&until, now))
+        .transpose()?;
+    let pattern = query
+        .grep
+        .map(|grep| regex::Regex::new(&grep))
+        .transpose()
+        .map_err(|e| Error::invalid_argument(format!("Invalid grep pattern: {}", e)))?;
+    Ok(crate::logs::LogSearch {
+        pattern,
+        since,
+        until,
+        level: query.level.map(|level| level.parse()).transpose()?,
+        streams: query.stream.into_iter().collect(),
+        limit: Some(query.limit.unwrap_or(100)),
+    })
+}
+
+/// Stream process logs as Server-Sent Events.
+///
+/// Sends the last `lines` lines of each selected stream as `backfill` events, then
+/// every new line as a `log` event, until the process is deleted or the client
+/// disconnects.
+async fn stream_process_logs(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<LogStreamQuery>,
+) -> Response {
+    let streams = match query.stream {
+        Some(stream) => vec![stream],
+        None => vec![LogStream::Out, LogStream::Err],
+    };
+
+    // Subscribe before reading the backfill so that no line falls in between
+    let (subscription, backfill) = {
+        let manager = state.manager.read().await;
+        let subscription = match manager.subscribe_process_logs(&id).await {
+            Ok(subscription) => subscription,
+            Err(e) => {
+                error!("Failed to stream logs for process {}: {}", id, e);
+                return (
+                    StatusCode::NOT_FOUND,
+                    Json(json!({
+                        "success": false,
+                        "error": "Failed to stream process logs",
+                        "message": e.to_string()
+                    })),
+                )
+                    .into_response();
+            }
+        };
+        let mut backfill = Vec::new();
+        for &stream in &streams {
+            match manager
+                .log_tail(&id, stream, query.lines.unwrap_or(0))
+                .await
+            {
+                Ok(lines) => backfill.extend(lines.into_iter().map(|line| {
+                    json!({
+                        "process_id": subscription.process_id(),
+                        "process_name": subscription.process_name(),
+                        "stream": stream,
+                        "line": line
+                    })
+                })),
+                Err(e) => warn!("Failed to read {} log of {}: {}", stream, id, e),
+            }
+        }
+        (subscription, backfill)
+    };
+
+    let backfill = futures_util::stream::iter(
+        backfill
+            .into_iter()
+            .map(|line| Event::default().event("backfill").json_data(line)),
+    );
+    let mut interval = interval(Duration::from_millis(500));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let live = futures_util::stream::unfold(
+        (subscription, interval),
+        move |(mut subscription, mut interval)| {
+            let state = state.clone();
+            let streams = streams.clone();
+            async move {
+                loop {
+                    tokio::select! {
+                        line = subscription.next() => match line {
+                            Some(mut line) if streams.contains(&line.stream) => {
+                                if subscription.format() == LogFormat::Json {
+                                    line.line = humanize_json_line(&line.line);
+                                }
+                                let event = Event::default().event("log").json_data(&line);
+                                return Some((event, (subscription, interval)));
+                            }
+                            Some(_) => {}
+                            None => return None,
+                        },
+                        _ = interval.tick() => {
+                            let process_id = subscription.process_id().to_string();
+                            let exists = state
+                                .manager
+                                .read()
+                                .await
+                                .get_process_info(&process_id)
+                                .await
+                                .is_ok();
+                            if !exists {
+                                return None;
+                            }
+                        }
+                    }
+                }
+            }
+        },
+    );
+
+    Sse::new(backfill.chain(live))
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
 /// Get system information
 async fn system_info(State(state): State<AppState>) -> impl IntoResponse {
     let system_metrics = {
@@ -709,7 +1156,7 @@ async fn handle_websocket(socket: WebSocket, state: AppState) {
 
 /// Convert ProcessStatus to PM2-compatible format
 fn process_status_to_pm2_format(status: &ProcessStatus) -> Value {
-    json!({
+    let mut value = json!({
         "pm_id": status.id.to_string(),
         "name": status.name,
         "namespace": status.namespace,
@@ -720,10 +1167,14 @@ fn process_status_to_pm2_format(status: &ProcessStatus) -> Value {
         "created_at": status.uptime.map(|u| u.timestamp()).unwrap_or(0),
         "status": status.state.to_string(),
         "restart_time": status.restarts,
+        "unstable_restarts": status.unstable_restarts,
+        "exp_backoff_restart_delay": status.backoff_delay,
+        "next_restart": status.next_restart,
         "cpu": status.cpu_usage,
         "memory": status.memory_usage,
         "user": "pmdaemon",
-        "watching": false,
+        "watching": status.watching,
+        "health": status.health,
         "instance_id": status.instance,
         "exec_interpreter": "none",
         "pm_exec_path": "",
@@ -749,7 +1200,15 @@ fn process_status_to_pm2_format(status: &ProcessStatus) -> Value {
             "status": status.state.to_string(),
             "port": status.assigned_port
         }
-    })
+    });
+
+    // Restart history, kept out of the literal above to stay within the macro's
+    // recursion limit
+    value["last_restart_reason"] = json!(status.last_restart_reason);
+    value["last_exit_code"] = json!(status.last_exit_code);
+    value["last_exit_signal"] = json!(status.last_exit_signal);
+    value["restart_history"] = json!(status.restart_history);
+    value
 }
 
 #[cfg(test)]
@@ -797,6 +1256,18 @@ mod tests {
             namespace: "default".to_string(),
             instance: Some(1),
             assigned_port: Some(8080),
+            watching: true,
+            health: Some(crate::health::HealthStatus {
+                state: crate::health::HealthState::Healthy,
+                ..Default::default()
+            }),
+            unstable_restarts: 0,
+            backoff_delay: None,
+            next_restart: None,
+            last_restart_reason: None,
+            last_exit_code: None,
+            last_exit_signal: None,
+            restart_history: Vec::new(),
         }
     }
 
@@ -824,11 +1295,188 @@ mod tests {
         assert_eq!(request.port_range, Some((3000, 3005)));
     }
 
+    #[test]
+    fn test_events_query_deserialize() {
+        let json = r#"{"process": "web", "since": "2h", "limit": 10}"#;
+        let query: EventsQuery = serde_json::from_str(json).unwrap();
+        assert_eq!(query.process, Some("web".to_string()));
+        assert_eq!(query.since, Some("2h".to_string()));
+        assert_eq!(query.limit, Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_list_events_invalid_since() {
+        let (state, _temp_dir) = create_test_app_state().await;
+        let query = EventsQuery {
+            process: None,
+            since: Some("last tuesday".to_string()),
+            limit: None,
+        };
+        let response = list_events(State(state), Query(query))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_list_process_crashes_without_records() {
+        let (state, _temp_dir) = create_test_app_state().await;
+        let response = list_process_crashes(
+            State(state),
+            Path("no-such-process-crashes".to_string()),
+            Query(CrashesQuery { limit: Some(5) }),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_stream_process_logs() {
+        let temp_dir = TempDir::new().unwrap();
+        let manager = ProcessManager::with_config_dir(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+        let config = crate::ProcessConfig::builder()
+            .name("streamed")
+            .script("sh")
+            .args(vec![
+                "-c",
+                "echo first; echo oops >&2; sleep 1; echo second; sleep 30",
+            ])
+            .build()
+            .unwrap();
+        manager.start(config).await.unwrap();
+        for _ in 0..50 {
+            let out = manager
+                .log_tail("streamed", LogStream::Out, 1)
+                .await
+                .unwrap();
+            if !out.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let (mut state, _state_dir) = create_test_app_state().await;
+        state.manager = Arc::new(RwLock::new(manager));
+        let missing = stream_process_logs(
+            State(state.clone()),
+            Path("no-such-process-stream".to_string()),
+            Query(LogStreamQuery {
+                stream: None,
+                lines: None,
+            }),
+        )
+        .await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+
+        let response = stream_process_logs(
+            State(state.clone()),
+            Path("streamed".to_string()),
+            Query(LogStreamQuery {
+                stream: Some(LogStream::Out),
+                lines: Some(10),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let mut body = response.into_body().into_data_stream();
+        let mut received = String::new();
+        while !received.contains("event: log") {
+            let chunk = tokio::time::timeout(Duration::from_secs(5), body.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            received.push_str(&String::from_utf8_lossy(&chunk));
+        }
+        assert!(received.starts_with("event: backfill\n"));
+        assert!(received.contains(r#""line":"first""#));
+        assert!(received.contains(r#""line":"second""#));
+        assert!(!received.contains("oops"));
+
+        state.manager.read().await.delete("streamed").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_search_process_logs_errors() {
+        let (state, _temp_dir) = create_test_app_state().await;
+        let query = |grep: &str, level: Option<&str>| LogSearchQuery {
+            grep: Some(grep.to_string()),
+            since: Some("10m".to_string()),
+            until: None,
+            level: level.map(str::to_string),
+            stream: Some(LogStream::Err),
+            limit: None,
+        };
+
+        for bad in [query("(unclosed", None), query("ok", Some("loud"))] {
+            let response = search_process_logs(
+                State(state.clone()),
+                Path("no-such-process-search".to_string()),
+                Query(bad),
+            )
+            .await
+            .into_response();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        let response = search_process_logs(
+            State(state),
+            Path("no-such-process-search".to_string()),
+            Query(query("timeout", Some("error"))),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_signal_request_deserialize() {
+        let request: SignalRequest = serde_json::from_str(r#"{"signal": "SIGHUP"}"#).unwrap();
+        assert_eq!(request.signal, "SIGHUP");
+        assert!(!request.namespace);
+
+        let json = r#"{"signal": "USR2", "namespace": true}"#;
+        let request: SignalRequest = serde_json::from_str(json).unwrap();
+        assert!(request.namespace);
+    }
+
+    #[tokio::test]
+    async fn test_signal_process_errors() {
+        let (state, _temp_dir) = create_test_app_state().await;
+
+        let request = SignalRequest {
+            signal: "SIGNOPE".to_string(),
+            namespace: false,
+        };
+        let response = signal_process(State(state.clone()), Path("app".to_string()), Json(request))
+            .await
+            .into_response();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let request = SignalRequest {
+            signal: "HUP".to_string(),
+            namespace: false,
+        };
+        let response = signal_process(
+            State(state),
+            Path("no-such-process-for-signal".to_string()),
+            Json(request),
+        )
+        .await
+        .into_response();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
     #[test]
     fn test_websocket_message_serialize() {
         let status = create_test_process_status();
         let msg = WebSocketMessage::ProcessUpdate {
-            process: status.clone(),
+            process: Box::new(status.clone()),
         };
 
         let json = serde_json::to_string(&msg).unwrap();
@@ -897,6 +1545,8 @@ mod tests {
         assert_eq!(pm2_format["pid"], 12345);
         assert_eq!(pm2_format["status"], "online");
         assert_eq!(pm2_format["restart_time"], 2);
+        assert_eq!(pm2_format["watching"], true);
+        assert_eq!(pm2_format["health"]["state"], "healthy");
         assert_eq!(pm2_format["cpu"], 25.5);
         assert_eq!(pm2_format["memory"], 1024 * 1024);
         assert_eq!(pm2_format["port"], 8080);
@@ -525,3 +525,72 @@ echo "Resource process completed"
     // Clean up
     env.cmd().args(["delete", &process_name]).assert().success();
 }
+
+#[test]
+#[cfg(unix)]
+fn test_processes_keep_logging_after_daemon_exits() {
+    use nix::sys::signal::{kill, Signal};
+    use nix::unistd::Pid;
+
+    let env = E2ETestEnvironment::new();
+    let process_name = env.unique_name("pumped-app");
+    let script = create_script(
+        env.temp_path(),
+        "ticker",
+        r#"#!/bin/bash
+i=0
+while true; do
+    echo "tick $i"
+    i=$((i+1))
+    sleep 0.1
+done
+"#,
+    );
+
+    let mut daemon = std::process::Command::new(assert_cmd::cargo::cargo_bin("pmdaemon"))
+        .arg("daemon")
+        .env("PMDAEMON_HOME", &env.config_dir)
+        .env("RUST_LOG", "error")
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .expect("Failed to start daemon");
+    let wait_for = |condition: &dyn Fn() -> bool| {
+        for _ in 0..100 {
+            if condition() {
+                return true;
+            }
+            thread::sleep(Duration::from_millis(50));
+        }
+        false
+    };
+    assert!(wait_for(&|| env.config_dir.join("pmdaemon.sock").exists()));
+
+    // Started through the daemon, with output pumped into the combined log
+    env.cmd()
+        .args(["start", script.to_str().unwrap(), "--name", &process_name])
+        .assert()
+        .success();
+    let logs_dir = env.config_dir.join("logs");
+    let out_log = logs_dir.join(format!("{}-out.log", process_name));
+    let combined_log = logs_dir.join(format!("{}.log", process_name));
+    let read = |path: &PathBuf| fs::read_to_string(path).unwrap_or_default();
+    assert!(wait_for(&|| read(&combined_log).contains("[out] tick 1\n")));
+
+    kill(Pid::from_raw(daemon.id() as i32), Signal::SIGTERM).unwrap();
+    assert!(daemon.wait().unwrap().success());
+
+    // The process keeps running and its output keeps reaching both logs
+    let (out_before, combined_before) = (read(&out_log).len(), read(&combined_log).len());
+    thread::sleep(Duration::from_millis(1000));
+    assert!(read(&out_log).len() > out_before);
+    assert!(read(&combined_log).len() > combined_before);
+    env.cmd()
+        .arg("list")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(&process_name))
+        .stdout(predicate::str::contains("online"));
+
+    env.cmd().args(["delete", &process_name]).assert().success();
+}